@@ -0,0 +1,126 @@
+use gather_integrity::*;
+use hdk::prelude::*;
+
+use crate::{event::get_live_event, global_collections::serialize_link_tag};
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct CheckInInput {
+    pub event_hash: ActionHash,
+    pub code: CheckInCode,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct MarkPresentInput {
+    pub event_hash: ActionHash,
+    pub attendee: AgentPubKey,
+}
+
+/// Issues a code that attendees can use to check themselves in for the next minutes,
+/// signed by us as one of the hosts of the event
+#[hdk_extern]
+pub fn create_check_in_code(event_hash: ActionHash) -> ExternResult<CheckInCode> {
+    let my_pub_key = agent_info()?.agent_initial_pubkey;
+    let revision = get_live_event(event_hash.clone())?.ok_or(wasm_error!(
+        WasmErrorInner::Guest(String::from("Could not find the Event"))
+    ))?;
+
+    let issued_at = sys_time()?;
+    let expires_at =
+        Timestamp::from_micros(issued_at.as_micros() + CHECK_IN_CODE_VALIDITY.as_micros() as i64);
+    let signature = sign(
+        my_pub_key.clone(),
+        CheckInCodePayload {
+            event_hash,
+            issued_at,
+            expires_at,
+        },
+    )?;
+    Ok(CheckInCode {
+        host: my_pub_key,
+        revision_hash: revision.action_address().clone(),
+        issued_at,
+        expires_at,
+        signature,
+    })
+}
+
+/// Checks ourselves in with a code issued by one of the hosts
+#[hdk_extern]
+pub fn check_in(input: CheckInInput) -> ExternResult<Record> {
+    let my_pub_key = agent_info()?.agent_initial_pubkey;
+    create_attendance(Attendance {
+        event_hash: input.event_hash,
+        attendee: my_pub_key,
+        check_in: CheckIn::WithCode { code: input.code },
+    })
+}
+
+/// Marks the attendee as present, which only the hosts of the event can do
+#[hdk_extern]
+pub fn mark_present(input: MarkPresentInput) -> ExternResult<Record> {
+    let revision = get_live_event(input.event_hash.clone())?.ok_or(wasm_error!(
+        WasmErrorInner::Guest(String::from("Could not find the Event"))
+    ))?;
+    create_attendance(Attendance {
+        event_hash: input.event_hash,
+        attendee: input.attendee,
+        check_in: CheckIn::ByHost {
+            revision_hash: revision.action_address().clone(),
+        },
+    })
+}
+
+#[hdk_extern]
+pub fn get_attendance(attendance_hash: ActionHash) -> ExternResult<Option<Record>> {
+    get(attendance_hash, GetOptions::default())
+}
+
+/// The agents that attended the event, with the link of their first check-in
+#[hdk_extern]
+pub fn get_attendees(event_hash: ActionHash) -> ExternResult<Vec<Link>> {
+    let mut links = get_links(event_hash, LinkTypes::EventAttendees, None)?;
+    links.sort_by(|a, b| a.timestamp.cmp(&b.timestamp));
+    let mut attendees: Vec<Link> = vec![];
+    for link in links {
+        if !attendees
+            .iter()
+            .any(|attendee| attendee.target == link.target)
+        {
+            attendees.push(link);
+        }
+    }
+    Ok(attendees)
+}
+
+/// Checking in an attendee that is already checked in returns their existing attendance
+fn create_attendance(attendance: Attendance) -> ExternResult<Record> {
+    let links = get_links(
+        attendance.event_hash.clone(),
+        LinkTypes::EventAttendees,
+        None,
+    )?;
+    let existing_attendance = links
+        .into_iter()
+        .filter(|link| {
+            link.target.clone().into_agent_pub_key().as_ref() == Some(&attendance.attendee)
+        })
+        .find_map(|link| deserialize_link_tag::<AttendanceTag>(link.tag));
+    if let Some(attendance_tag) = existing_attendance {
+        if let Some(record) = get(attendance_tag.attendance_hash, GetOptions::default())? {
+            return Ok(record);
+        }
+    }
+
+    let attendance_hash = create_entry(&EntryTypes::Attendance(attendance.clone()))?;
+    create_link(
+        attendance.event_hash,
+        attendance.attendee,
+        LinkTypes::EventAttendees,
+        serialize_link_tag(AttendanceTag {
+            attendance_hash: attendance_hash.clone(),
+        })?,
+    )?;
+    get(attendance_hash, GetOptions::default())?.ok_or(wasm_error!(WasmErrorInner::Guest(
+        String::from("Could not find the newly created Attendance")
+    )))
+}
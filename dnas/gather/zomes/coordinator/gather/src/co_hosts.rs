@@ -0,0 +1,206 @@
+use gather_integrity::*;
+use hdk::prelude::*;
+
+use crate::{
+    event::get_live_event,
+    get_entry_for_action,
+    notifications::{notify_alert, GatherAction, GatherNotification},
+    proposal::get_live_proposal,
+    revisions::get_revision_heads,
+};
+
+/// Accepts to host the event or proposal that lists us among its hosts,
+/// which allows us to act as one of its hosts
+#[hdk_extern]
+pub fn accept_host_invitation(event_or_proposal_hash: ActionHash) -> ExternResult<Record> {
+    let my_pub_key = agent_info()?.agent_initial_pubkey;
+    let hosts = get_latest_hosts(&event_or_proposal_hash)?;
+    if !hosts.contains(&my_pub_key) {
+        return Err(wasm_error!(WasmErrorInner::Guest(String::from(
+            "We are not one of the hosts"
+        ))));
+    }
+
+    let host_acceptance = HostAcceptance {
+        event_or_proposal_hash: get_accepted_hash(&event_or_proposal_hash)?,
+        host: my_pub_key.clone(),
+    };
+    let acceptance_hash = match get(hash_entry(&host_acceptance)?, GetOptions::default())? {
+        // We already accepted to host the proposal this event comes from
+        Some(record) => record.action_address().clone(),
+        None => create_entry(&EntryTypes::HostAcceptance(host_acceptance))?,
+    };
+    if let Some(EntryTypes::Event(_)) = get_entry_for_action(&event_or_proposal_hash)? {
+        create_link(
+            my_pub_key,
+            event_or_proposal_hash,
+            LinkTypes::HostedEvents,
+            (),
+        )?;
+    }
+    get(acceptance_hash, GetOptions::default())?.ok_or(wasm_error!(WasmErrorInner::Guest(
+        String::from("Could not find the newly created HostAcceptance")
+    )))
+}
+
+/// The hosts of the latest revision of the event or proposal that have accepted to host it
+#[hdk_extern]
+pub fn get_accepted_hosts(event_or_proposal_hash: ActionHash) -> ExternResult<Vec<AgentPubKey>> {
+    let hosts = get_latest_hosts(&event_or_proposal_hash)?;
+    let mut accepted_hosts = vec![];
+    for host in hosts {
+        if has_accepted(&event_or_proposal_hash, &host)? {
+            accepted_hosts.push(host);
+        }
+    }
+    Ok(accepted_hosts)
+}
+
+/// The events that we have accepted to host and still list us among their hosts
+#[hdk_extern]
+pub fn get_events_i_host(_: ()) -> ExternResult<Vec<Link>> {
+    let my_pub_key = agent_info()?.agent_initial_pubkey;
+    let links = get_links(my_pub_key.clone(), LinkTypes::HostedEvents, None)?;
+
+    let mut hosted_events: Vec<Link> = vec![];
+    for link in links {
+        let Some(event_hash) = link.target.clone().into_action_hash() else {
+            continue;
+        };
+        if hosted_events
+            .iter()
+            .any(|hosted| hosted.target == link.target)
+        {
+            continue;
+        }
+        if get_latest_hosts(&event_hash)?.contains(&my_pub_key) {
+            hosted_events.push(link);
+        }
+    }
+    Ok(hosted_events)
+}
+
+/// Links the new event to the hosts that don't need to accept to host it, which are us
+/// and the hosts that had already accepted to host its proposal
+pub fn link_hosted_event(event_hash: &ActionHash, event: &Event) -> ExternResult<()> {
+    for host in &event.hosts {
+        if has_accepted(event_hash, host)? {
+            create_link(
+                host.clone(),
+                event_hash.clone(),
+                LinkTypes::HostedEvents,
+                (),
+            )?;
+        }
+    }
+    Ok(())
+}
+
+/// Alerts the hosts that were added to the event that they need to accept to host it
+pub fn invite_event_hosts(
+    event_hash: &ActionHash,
+    previous_hosts: &[AgentPubKey],
+    hosts: &[AgentPubKey],
+) -> ExternResult<()> {
+    let invitees = new_hosts(previous_hosts, hosts)?;
+    if invitees.is_empty() {
+        return Ok(());
+    }
+    notify_alert(
+        invitees,
+        GatherNotification::EventAlert {
+            event_hash: event_hash.clone(),
+            action: GatherAction::EventHostInvitation {
+                action_hash: event_hash.clone(),
+            },
+        },
+    )
+}
+
+/// Alerts the hosts that were added to the proposal that they need to accept to host it
+pub fn invite_proposal_hosts(
+    proposal_hash: &ActionHash,
+    previous_hosts: &[AgentPubKey],
+    hosts: &[AgentPubKey],
+) -> ExternResult<()> {
+    let invitees = new_hosts(previous_hosts, hosts)?;
+    if invitees.is_empty() {
+        return Ok(());
+    }
+    notify_alert(
+        invitees,
+        GatherNotification::ProposalAlert {
+            proposal_hash: proposal_hash.clone(),
+            action: GatherAction::ProposalHostInvitation {
+                action_hash: proposal_hash.clone(),
+            },
+        },
+    )
+}
+
+/// The hosts of the latest revision of the event, proposal or private event
+pub fn get_latest_hosts(event_or_proposal_hash: &ActionHash) -> ExternResult<Vec<AgentPubKey>> {
+    let latest = match get_entry_for_action(event_or_proposal_hash)? {
+        Some(EntryTypes::Event(_)) => get_live_event(event_or_proposal_hash.clone())?,
+        Some(EntryTypes::Proposal(_)) => get_live_proposal(event_or_proposal_hash.clone())?,
+        Some(EntryTypes::PrivateEvent(_)) => get_revision_heads(event_or_proposal_hash)?
+            .into_iter()
+            .next(),
+        _ => None,
+    };
+    let Some(latest) = latest else {
+        return Err(wasm_error!(WasmErrorInner::Guest(String::from(
+            "Could not find the event or proposal"
+        ))));
+    };
+    match get_entry_for_action(latest.action_address())? {
+        Some(EntryTypes::Event(event)) => Ok(event.hosts),
+        Some(EntryTypes::Proposal(proposal)) => Ok(proposal.hosts),
+        Some(EntryTypes::PrivateEvent(private_event)) => Ok(private_event.hosts),
+        _ => Err(wasm_error!(WasmErrorInner::Guest(String::from(
+            "Malformed entry"
+        )))),
+    }
+}
+
+/// The added hosts other than ourselves
+fn new_hosts(
+    previous_hosts: &[AgentPubKey],
+    hosts: &[AgentPubKey],
+) -> ExternResult<Vec<AgentPubKey>> {
+    let my_pub_key = agent_info()?.agent_initial_pubkey;
+    Ok(hosts
+        .iter()
+        .filter(|host| !previous_hosts.contains(host) && **host != my_pub_key)
+        .cloned()
+        .collect())
+}
+
+/// Events created from a proposal are accepted by accepting to host the proposal
+fn get_accepted_hash(event_or_proposal_hash: &ActionHash) -> ExternResult<ActionHash> {
+    match get_entry_for_action(event_or_proposal_hash)? {
+        Some(EntryTypes::Event(Event {
+            from_proposal: Some(from_proposal),
+            ..
+        })) => Ok(from_proposal.proposal_hash),
+        _ => Ok(event_or_proposal_hash.clone()),
+    }
+}
+
+/// Mirrors `validate_host_has_accepted`, where creators host without accepting
+fn has_accepted(event_or_proposal_hash: &ActionHash, host: &AgentPubKey) -> ExternResult<bool> {
+    let accepted_hash = get_accepted_hash(event_or_proposal_hash)?;
+    for hash in [event_or_proposal_hash, &accepted_hash] {
+        let Some(record) = get(hash.clone(), GetOptions::default())? else {
+            continue;
+        };
+        if record.action().author() == host {
+            return Ok(true);
+        }
+    }
+    let acceptance_hash = hash_entry(HostAcceptance {
+        event_or_proposal_hash: accepted_hash,
+        host: host.clone(),
+    })?;
+    Ok(get(acceptance_hash, GetOptions::default())?.is_some())
+}
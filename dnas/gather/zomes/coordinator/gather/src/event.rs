@@ -1,7 +1,20 @@
 use gather_integrity::*;
 use hdk::prelude::*;
 
-use crate::global_collections::{all_open_proposals, all_upcoming_events, remove_from_collection};
+use crate::co_hosts::{get_latest_hosts, invite_event_hosts, link_hosted_event};
+use crate::global_collections::{
+    event_has_status, event_start_times, proposal_start_times, remove_from_collection,
+    reschedule_event, serialize_link_tag, transition_event,
+};
+use crate::my_events::remove_deleted_from_my_events;
+use crate::nearby_events::{index_event_location, remove_deleted_from_location_index};
+use crate::revisions::{
+    get_all_revisions, get_event_revisions, get_revision_heads, GetRevisionsInput,
+};
+use crate::rsvps::notify_promoted_by_capacity;
+use crate::search::{index_event_for_search, remove_event_from_search};
+use crate::tags::{remove_event_tags, update_event_tags};
+use crate::{entry_of, get_latest_revision, get_latest_revisions, LatestRevision};
 
 #[hdk_extern]
 pub fn create_event(event: Event) -> ExternResult<Record> {
@@ -10,14 +23,7 @@ pub fn create_event(event: Event) -> ExternResult<Record> {
         WasmErrorInner::Guest(String::from("Could not find the newly created Event"))
     ))?;
 
-    let path = all_upcoming_events();
-
-    create_link(
-        path.path_entry_hash()?,
-        event_hash.clone(),
-        LinkTypes::UpcomingEvents,
-        (),
-    )?;
+    transition_event(event_hash.clone(), record.clone(), EventTransition::Created)?;
 
     let my_agent_pub_key = agent_info()?.agent_latest_pubkey;
     create_link(
@@ -27,18 +33,30 @@ pub fn create_event(event: Event) -> ExternResult<Record> {
         (),
     )?;
 
+    update_event_tags(&event_hash, &record)?;
+    index_event_for_search(&event_hash, &record)?;
+    link_hosted_event(&event_hash, &event)?;
+    // The hosts of the proposal were already invited to host it
+    let invited_hosts = match &event.from_proposal {
+        Some(from_proposal) => get_latest_hosts(&from_proposal.proposal_hash)?,
+        None => vec![],
+    };
+    invite_event_hosts(&event_hash, &invited_hosts, &event.hosts)?;
+
     if let Some(from_proposal) = event.from_proposal {
-        remove_from_collection(
-            &from_proposal.proposal_hash,
-            all_open_proposals(),
-            LinkTypes::OpenProposals,
-        )?;
+        // The proposal can only be removed from the open proposals right after linking it to its event
         create_link(
-            from_proposal.proposal_hash,
+            from_proposal.proposal_hash.clone(),
             event_hash,
             LinkTypes::ProposalToEvent,
             (),
         )?;
+        remove_from_collection(
+            &from_proposal.proposal_hash,
+            ALL_OPEN_PROPOSALS,
+            LinkTypes::OpenProposals,
+            proposal_start_times(&from_proposal.proposal_hash)?,
+        )?;
     }
 
     Ok(record)
@@ -57,40 +75,51 @@ pub fn get_original_event(original_event_hash: ActionHash) -> ExternResult<Optio
     Ok(Some(record))
 }
 
+/// The latest revision of the event, unless it was deleted
 #[hdk_extern]
 pub fn get_latest_event(original_event_hash: ActionHash) -> ExternResult<Option<Record>> {
-    let links = get_links(original_event_hash.clone(), LinkTypes::Updates, None)?;
-    let latest_link = links
-        .into_iter()
-        .max_by(|link_a, link_b| link_a.timestamp.cmp(&link_b.timestamp));
-    let latest_event_hash = match latest_link {
-        Some(link) => ActionHash::try_from(link.target.clone()).map_err(|err| wasm_error!(err))?,
-        None => original_event_hash.clone(),
-    };
-    let Some(details) =     get_details(latest_event_hash, GetOptions::default())? else {return Ok(None);};
-    let record = match details {
-        Details::Record(details) => Ok(details.record),
-        _ => Err(wasm_error!(WasmErrorInner::Guest(String::from(
-            "Malformed get details response"
-        )))),
-    }?;
-
-    Ok(Some(record))
+    get_live_event(original_event_hash)
 }
 
+/// The latest revisions of the given events, in the same order, resolved all at once
 #[hdk_extern]
-pub fn get_all_event_revisions(original_event_hash: ActionHash) -> ExternResult<Vec<Record>> {
-    let Some(Details::Record(details)) =     get_details(original_event_hash, GetOptions::default())? else {return Ok(vec![]);};
+pub fn get_latest_events(
+    original_event_hashes: Vec<ActionHash>,
+) -> ExternResult<Vec<Option<Record>>> {
+    Ok(get_latest_revisions(original_event_hashes)?
+        .into_iter()
+        .map(|latest| latest.and_then(LatestRevision::live))
+        .collect())
+}
 
-    let mut records = vec![details.record];
+/// The latest revision of the event, or its deletion if one of its hosts deleted it
+#[hdk_extern]
+pub fn get_latest_event_revision(
+    original_event_hash: ActionHash,
+) -> ExternResult<Option<LatestRevision>> {
+    get_latest_revision(original_event_hash)
+}
 
-    for update in details.updates {
-        let mut update_records = get_all_event_revisions(update.action_address().clone())?;
+/// The latest revisions or the deletions of the given events, in the same order
+#[hdk_extern]
+pub fn get_latest_event_revisions(
+    original_event_hashes: Vec<ActionHash>,
+) -> ExternResult<Vec<Option<LatestRevision>>> {
+    get_latest_revisions(original_event_hashes)
+}
 
-        records.append(&mut update_records);
-    }
+/// The latest revision of the event, unless it was deleted
+pub fn get_live_event(original_event_hash: ActionHash) -> ExternResult<Option<Record>> {
+    Ok(get_latest_revision(original_event_hash)?.and_then(LatestRevision::live))
+}
 
-    Ok(records)
+/// All the revisions of the event, from the oldest to the most recent one
+#[hdk_extern]
+pub fn get_all_event_revisions(original_event_hash: ActionHash) -> ExternResult<Vec<Record>> {
+    get_event_revisions(GetRevisionsInput {
+        original_hash: original_event_hash,
+        limit: None,
+    })
 }
 
 #[derive(Serialize, Deserialize, Debug)]
@@ -101,15 +130,132 @@ pub struct UpdateEventInput {
 }
 #[hdk_extern]
 pub fn update_event(input: UpdateEventInput) -> ExternResult<Record> {
+    update_event_revision(input, vec![])
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct MergeEventRevisionsInput {
+    pub original_event_hash: ActionHash,
+    pub merged_event: Event,
+}
+
+/// Resolves the concurrent revisions of the event with an update of its latest revision
+/// that also merges all the other heads
+#[hdk_extern]
+pub fn merge_event_revisions(input: MergeEventRevisionsInput) -> ExternResult<Record> {
+    let heads = get_revision_heads(&input.original_event_hash)?;
+    let Some((latest, other_heads)) = heads.split_first() else {
+        return Err(wasm_error!(WasmErrorInner::Guest(String::from(
+            "Could not find the event"
+        ))));
+    };
+    if other_heads.is_empty() {
+        return Err(wasm_error!(WasmErrorInner::Guest(String::from(
+            "The event has no concurrent revisions to merge"
+        ))));
+    }
+    update_event_revision(
+        UpdateEventInput {
+            original_event_hash: input.original_event_hash,
+            previous_event_hash: latest.action_address().clone(),
+            updated_event: input.merged_event,
+        },
+        other_heads
+            .iter()
+            .map(|head| head.action_address().clone())
+            .collect(),
+    )
+}
+
+/// The revisions of the event that no other revision updates or merges, the first one
+/// being its latest revision
+#[hdk_extern]
+pub fn get_event_revision_heads(original_event_hash: ActionHash) -> ExternResult<Vec<Record>> {
+    get_revision_heads(&original_event_hash)
+}
+
+fn update_event_revision(
+    input: UpdateEventInput,
+    merged_heads: Vec<ActionHash>,
+) -> ExternResult<Record> {
+    if let Some(LatestRevision::Deleted { .. }) =
+        get_latest_revision(input.original_event_hash.clone())?
+    {
+        return Err(wasm_error!(WasmErrorInner::Guest(String::from(
+            "Cannot update a deleted event"
+        ))));
+    }
+    let previous_record =
+        get(input.previous_event_hash.clone(), GetOptions::default())?.ok_or(wasm_error!(
+            WasmErrorInner::Guest(String::from("Could not find the previous Event"))
+        ))?;
+    let previous_event = entry_of::<Event>(&previous_record)?;
     let updated_event_hash = update_entry(input.previous_event_hash.clone(), &input.updated_event)?;
     create_link(
         input.original_event_hash.clone(),
         updated_event_hash.clone(),
         LinkTypes::Updates,
-        (),
+        serialize_link_tag(UpdatesTag { merged_heads })?,
     )?;
     let record = get(updated_event_hash.clone(), GetOptions::default())?.ok_or(wasm_error!(
         WasmErrorInner::Guest(String::from("Could not find the newly updated Event"))
     ))?;
+    reschedule_event(input.original_event_hash.clone(), record.clone())?;
+    if event_has_status(&input.original_event_hash, EventStatus::Upcoming)? {
+        index_event_location(&input.original_event_hash, &record)?;
+    }
+    update_event_tags(&input.original_event_hash, &record)?;
+    index_event_for_search(&input.original_event_hash, &record)?;
+    notify_promoted_by_capacity(
+        &input.original_event_hash,
+        previous_event.capacity,
+        input.updated_event.capacity,
+    )?;
+    invite_event_hosts(
+        &input.original_event_hash,
+        &previous_event.hosts,
+        &input.updated_event.hosts,
+    )?;
     Ok(record)
 }
+
+/// Deletes the latest revision of the event, which only its hosts can do, and removes the
+/// event from every collection and index
+///
+/// Validation only allows removing the links created by other agents right after the deletion,
+/// so nothing but deletes of links can be committed after it
+#[hdk_extern]
+pub fn delete_event(original_event_hash: ActionHash) -> ExternResult<ActionHash> {
+    let Some(LatestRevision::Live { record, .. }) =
+        get_latest_revision(original_event_hash.clone())?
+    else {
+        return Err(wasm_error!(WasmErrorInner::Guest(String::from(
+            "Could not find the event, or it was already deleted"
+        ))));
+    };
+    let start_times = event_start_times(&original_event_hash)?;
+    let mut hosts: Vec<AgentPubKey> = vec![];
+    for revision in get_all_revisions(&original_event_hash)? {
+        hosts.extend(entry_of::<Event>(&revision)?.hosts);
+    }
+
+    let deletion_hash = delete_entry(record.action_address().clone())?;
+
+    for status in [
+        EventStatus::Upcoming,
+        EventStatus::Past,
+        EventStatus::Cancelled,
+    ] {
+        remove_from_collection(
+            &original_event_hash,
+            status.path(),
+            status.link_type(),
+            start_times.clone(),
+        )?;
+    }
+    remove_deleted_from_location_index(&original_event_hash)?;
+    remove_event_tags(&original_event_hash)?;
+    remove_event_from_search(&original_event_hash)?;
+    remove_deleted_from_my_events(&original_event_hash, hosts)?;
+    Ok(deletion_hash)
+}
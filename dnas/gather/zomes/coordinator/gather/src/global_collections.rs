@@ -1,171 +1,764 @@
+use std::collections::BTreeSet;
+
 use gather_integrity::*;
 use hdk::prelude::*;
 
-pub fn all_upcoming_events() -> Path {
-    Path::from("all_upcoming_events")
-}
+use crate::event::get_live_event;
+use crate::nearby_events::{index_event_location, remove_from_location_index};
+use crate::proposal::{get_live_proposal, get_original_proposal};
+use crate::revisions::get_all_revisions;
+use crate::{get_latest_revisions, LatestRevision};
+
 #[hdk_extern]
 pub fn get_all_upcoming_events(_: ()) -> ExternResult<Vec<Link>> {
-    get_from_path(all_upcoming_events(), LinkTypes::UpcomingEvents)
+    get_all_from_collection(ALL_UPCOMING_EVENTS, LinkTypes::UpcomingEvents)
+}
+/// Upcoming events are paginated starting from the soonest
+#[hdk_extern]
+pub fn get_upcoming_events_page(input: GetPageInput) -> ExternResult<CollectionPage> {
+    get_collection_page(
+        CollectionStatus::Event(EventStatus::Upcoming),
+        PageOrder::Ascending,
+        input,
+    )
+}
+#[hdk_extern]
+pub fn get_upcoming_events_in_range(input: GetInRangeInput) -> ExternResult<CollectionPage> {
+    get_page_in_range(CollectionStatus::Event(EventStatus::Upcoming), input)
 }
 #[hdk_extern]
 pub fn mark_event_as_past(event_hash: ActionHash) -> ExternResult<()> {
-    remove_from_collection(
-        &event_hash,
-        all_upcoming_events(),
-        LinkTypes::UpcomingEvents,
-    )?;
-    let path = all_past_events();
-    create_link(
-        path.path_entry_hash()?,
-        event_hash.clone(),
-        LinkTypes::PastEvents,
-        (),
-    )?;
-    Ok(())
+    let latest_event = must_get_latest_event(&event_hash)?;
+    transition_event(event_hash, latest_event, EventTransition::Passed)
+}
+#[derive(Serialize, Deserialize, Debug)]
+pub struct MarkEventAsCancelledInput {
+    pub event_hash: ActionHash,
+    pub cancellation_hash: ActionHash,
 }
 #[hdk_extern]
-pub fn mark_event_as_cancelled(event_hash: ActionHash) -> ExternResult<()> {
-    remove_from_collection(
-        &event_hash,
-        all_upcoming_events(),
-        LinkTypes::UpcomingEvents,
-    )?;
-    let path = all_cancelled_events();
-    create_link(
-        path.path_entry_hash()?,
-        event_hash.clone(),
-        LinkTypes::CancelledEvents,
-        (),
-    )?;
-    Ok(())
+pub fn mark_event_as_cancelled(input: MarkEventAsCancelledInput) -> ExternResult<()> {
+    let latest_event = must_get_latest_event(&input.event_hash)?;
+    transition_event(
+        input.event_hash,
+        latest_event,
+        EventTransition::Cancelled {
+            cancellation_hash: input.cancellation_hash,
+        },
+    )
+}
+#[derive(Serialize, Deserialize, Debug)]
+pub struct MarkEventAsUpcomingInput {
+    pub event_hash: ActionHash,
+    pub cancellation_deletion_hash: ActionHash,
 }
 #[hdk_extern]
-pub fn mark_event_as_upcoming(event_hash: ActionHash) -> ExternResult<()> {
+pub fn mark_event_as_upcoming(input: MarkEventAsUpcomingInput) -> ExternResult<()> {
     // No reason why the event can go from past to upcoming,
     // Only remove from cancelled
-
-    remove_from_collection(
-        &event_hash,
-        all_cancelled_events(),
-        LinkTypes::CancelledEvents,
-    )?;
-    let path = all_upcoming_events();
-    create_link(
-        path.path_entry_hash()?,
-        event_hash.clone(),
-        LinkTypes::UpcomingEvents,
-        (),
-    )?;
-    Ok(())
-}
-pub fn all_cancelled_events() -> Path {
-    Path::from("all_cancelled_events")
+    let latest_event = must_get_latest_event(&input.event_hash)?;
+    transition_event(
+        input.event_hash,
+        latest_event,
+        EventTransition::Uncancelled {
+            cancellation_deletion_hash: input.cancellation_deletion_hash,
+        },
+    )
 }
 #[hdk_extern]
 pub fn get_all_cancelled_events(_: ()) -> ExternResult<Vec<Link>> {
-    get_from_path(all_cancelled_events(), LinkTypes::CancelledEvents)
+    get_all_from_collection(ALL_CANCELLED_EVENTS, LinkTypes::CancelledEvents)
 }
-pub fn all_past_events() -> Path {
-    Path::from("all_past_events")
+/// Cancelled events are paginated starting from the most recent
+#[hdk_extern]
+pub fn get_cancelled_events_page(input: GetPageInput) -> ExternResult<CollectionPage> {
+    get_collection_page(
+        CollectionStatus::Event(EventStatus::Cancelled),
+        PageOrder::Descending,
+        input,
+    )
 }
 #[hdk_extern]
 pub fn get_all_past_events(_: ()) -> ExternResult<Vec<Link>> {
-    get_from_path(all_past_events(), LinkTypes::PastEvents)
+    get_all_from_collection(ALL_PAST_EVENTS, LinkTypes::PastEvents)
 }
+/// Past events are paginated starting from the most recent
 #[hdk_extern]
-pub fn mark_proposal_as_open(proposal_hash: ActionHash) -> ExternResult<()> {
+pub fn get_past_events_page(input: GetPageInput) -> ExternResult<CollectionPage> {
+    get_collection_page(
+        CollectionStatus::Event(EventStatus::Past),
+        PageOrder::Descending,
+        input,
+    )
+}
+#[derive(Serialize, Deserialize, Debug)]
+pub struct MarkProposalAsOpenInput {
+    pub proposal_hash: ActionHash,
+    pub cancellation_deletion_hash: ActionHash,
+}
+#[hdk_extern]
+pub fn mark_proposal_as_open(input: MarkProposalAsOpenInput) -> ExternResult<()> {
     // No reason why the proposal can go from expired to open,
     // Only remove from cancelled
-
-    remove_from_collection(
-        &proposal_hash,
-        all_cancelled_proposals(),
-        LinkTypes::CancelledProposals,
-    )?;
-    let path = all_open_proposals();
-    create_link(
-        path.path_entry_hash()?,
-        proposal_hash.clone(),
-        LinkTypes::OpenProposals,
-        (),
-    )?;
-    Ok(())
+    let latest_proposal = must_get_latest_proposal(&input.proposal_hash)?;
+    transition_proposal(
+        input.proposal_hash,
+        latest_proposal,
+        ProposalTransition::Uncancelled {
+            cancellation_deletion_hash: input.cancellation_deletion_hash,
+        },
+    )
 }
 #[hdk_extern]
 pub fn mark_proposal_as_expired(proposal_hash: ActionHash) -> ExternResult<()> {
-    remove_from_collection(
-        &proposal_hash,
-        all_open_proposals(),
-        LinkTypes::OpenProposals,
-    )?;
-    let path = all_expired_proposals();
-    create_link(
-        path.path_entry_hash()?,
-        proposal_hash.clone(),
-        LinkTypes::ExpiredProposals,
-        (),
-    )?;
-    Ok(())
+    let latest_proposal = must_get_latest_proposal(&proposal_hash)?;
+    transition_proposal(proposal_hash, latest_proposal, ProposalTransition::Expired)
 }
-#[hdk_extern]
-pub fn mark_proposal_as_cancelled(proposal_hash: ActionHash) -> ExternResult<()> {
-    remove_from_collection(
-        &proposal_hash,
-        all_open_proposals(),
-        LinkTypes::OpenProposals,
-    )?;
-    let path = all_cancelled_proposals();
-    create_link(
-        path.path_entry_hash()?,
-        proposal_hash.clone(),
-        LinkTypes::CancelledProposals,
-        (),
-    )?;
-    Ok(())
+#[derive(Serialize, Deserialize, Debug)]
+pub struct MarkProposalAsCancelledInput {
+    pub proposal_hash: ActionHash,
+    pub cancellation_hash: ActionHash,
 }
-pub fn all_open_proposals() -> Path {
-    Path::from("all_open_proposals")
+#[hdk_extern]
+pub fn mark_proposal_as_cancelled(input: MarkProposalAsCancelledInput) -> ExternResult<()> {
+    let latest_proposal = must_get_latest_proposal(&input.proposal_hash)?;
+    transition_proposal(
+        input.proposal_hash,
+        latest_proposal,
+        ProposalTransition::Cancelled {
+            cancellation_hash: input.cancellation_hash,
+        },
+    )
 }
 #[hdk_extern]
 pub fn get_all_open_proposals(_: ()) -> ExternResult<Vec<Link>> {
-    get_from_path(all_open_proposals(), LinkTypes::OpenProposals)
+    get_all_from_collection(ALL_OPEN_PROPOSALS, LinkTypes::OpenProposals)
 }
-pub fn all_expired_proposals() -> Path {
-    Path::from("all_expired_proposals")
+/// Open proposals are paginated starting from the soonest
+#[hdk_extern]
+pub fn get_open_proposals_page(input: GetPageInput) -> ExternResult<CollectionPage> {
+    get_collection_page(
+        CollectionStatus::Proposal(ProposalStatus::Open),
+        PageOrder::Ascending,
+        input,
+    )
+}
+#[hdk_extern]
+pub fn get_open_proposals_in_range(input: GetInRangeInput) -> ExternResult<CollectionPage> {
+    get_page_in_range(CollectionStatus::Proposal(ProposalStatus::Open), input)
 }
 #[hdk_extern]
 pub fn get_all_expired_proposals(_: ()) -> ExternResult<Vec<Link>> {
-    get_from_path(all_expired_proposals(), LinkTypes::ExpiredProposals)
+    get_all_from_collection(ALL_EXPIRED_PROPOSALS, LinkTypes::ExpiredProposals)
 }
-pub fn all_cancelled_proposals() -> Path {
-    Path::from("all_cancelled_proposals")
+/// Expired proposals are paginated starting from the most recent
+#[hdk_extern]
+pub fn get_expired_proposals_page(input: GetPageInput) -> ExternResult<CollectionPage> {
+    get_collection_page(
+        CollectionStatus::Proposal(ProposalStatus::Expired),
+        PageOrder::Descending,
+        input,
+    )
 }
 #[hdk_extern]
 pub fn get_all_cancelled_proposals(_: ()) -> ExternResult<Vec<Link>> {
-    get_from_path(all_cancelled_proposals(), LinkTypes::CancelledProposals)
+    get_all_from_collection(ALL_CANCELLED_PROPOSALS, LinkTypes::CancelledProposals)
+}
+/// Cancelled proposals are paginated starting from the most recent
+#[hdk_extern]
+pub fn get_cancelled_proposals_page(input: GetPageInput) -> ExternResult<CollectionPage> {
+    get_collection_page(
+        CollectionStatus::Proposal(ProposalStatus::Cancelled),
+        PageOrder::Descending,
+        input,
+    )
+}
+
+/** Pages */
+
+const FIRST_DAY: (i64, u32, u32) = (i64::MIN, u32::MIN, u32::MIN);
+const LAST_DAY: (i64, u32, u32) = (i64::MAX, u32::MAX, u32::MAX);
+
+/// Points to the last link of a page, to continue right after it
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct CollectionCursor {
+    pub start_time: Timestamp,
+    pub create_link_hash: ActionHash,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct GetInRangeInput {
+    pub start: Timestamp,
+    pub end: Timestamp,
+    pub limit: usize,
+    /// The `next_cursor` of the previous page
+    pub cursor: Option<CollectionCursor>,
+    /// Whether to also return the latest state of the events or proposals of the page
+    #[serde(default)]
+    pub hydrate: bool,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct GetPageInput {
+    pub limit: usize,
+    /// The `next_cursor` of the previous page
+    pub cursor: Option<CollectionCursor>,
+    /// Whether to also return the latest state of the events or proposals of the page
+    #[serde(default)]
+    pub hydrate: bool,
+}
+
+/// The links of a collection in a page, sorted by their start time
+#[derive(Serialize, Deserialize, Debug)]
+pub struct CollectionPage {
+    pub links: Vec<Link>,
+    /// Present only if there are more links in the range after this page
+    pub next_cursor: Option<CollectionCursor>,
+    /// The latest state of the target of each link, in the same order,
+    /// present only if the page was requested hydrated
+    pub hydrated: Option<Vec<HydratedLink>>,
+}
+
+/// The global collection that a page is taken from
+#[derive(Serialize, Deserialize, Debug, Clone, Copy)]
+#[serde(tag = "type", content = "status")]
+pub enum CollectionStatus {
+    Event(EventStatus),
+    Proposal(ProposalStatus),
+}
+
+impl CollectionStatus {
+    fn path(&self) -> &'static str {
+        match self {
+            CollectionStatus::Event(status) => status.path(),
+            CollectionStatus::Proposal(status) => status.path(),
+        }
+    }
+
+    fn link_type(&self) -> LinkTypes {
+        match self {
+            CollectionStatus::Event(status) => status.link_type(),
+            CollectionStatus::Proposal(status) => status.link_type(),
+        }
+    }
+
+    fn link_start_time(&self) -> fn(&Link) -> Option<Timestamp> {
+        match self {
+            CollectionStatus::Event(_) => event_link_start_time,
+            CollectionStatus::Proposal(_) => proposal_link_start_time,
+        }
+    }
+}
+
+/// What the UI needs to show the event or proposal that a link of a collection targets,
+/// so that it doesn't need to fetch it with one more zome call
+#[derive(Serialize, Deserialize, Debug)]
+pub struct HydratedLink {
+    pub original_hash: ActionHash,
+    pub latest: Option<LatestRevision>,
+    pub interested_count: usize,
+    pub status: CollectionStatus,
+}
+
+fn get_page_in_range(
+    status: CollectionStatus,
+    input: GetInRangeInput,
+) -> ExternResult<CollectionPage> {
+    let page = get_page(
+        status,
+        PageQuery {
+            start: Some(input.start),
+            end: Some(input.end),
+            order: PageOrder::Ascending,
+            limit: input.limit,
+            cursor: input.cursor,
+        },
+    )?;
+    hydrate_page(page, status, input.hydrate)
+}
+
+fn get_collection_page(
+    status: CollectionStatus,
+    order: PageOrder,
+    input: GetPageInput,
+) -> ExternResult<CollectionPage> {
+    let page = get_page(
+        status,
+        PageQuery {
+            start: None,
+            end: None,
+            order,
+            limit: input.limit,
+            cursor: input.cursor,
+        },
+    )?;
+    hydrate_page(page, status, input.hydrate)
+}
+
+/// Resolves the latest revisions and the interested agents of all the targets of the page
+/// at once
+fn hydrate_page(
+    mut page: CollectionPage,
+    status: CollectionStatus,
+    hydrate: bool,
+) -> ExternResult<CollectionPage> {
+    if !hydrate {
+        return Ok(page);
+    }
+    let original_hashes: Vec<ActionHash> = page
+        .links
+        .iter()
+        .filter_map(|link| link.target.clone().into_action_hash())
+        .collect();
+    let latest_revisions = get_latest_revisions(original_hashes.clone())?;
+    let interested_filter = LinkTypes::Interested.try_into_filter()?;
+    let inputs: Vec<GetLinksInput> = original_hashes
+        .iter()
+        .map(|hash| GetLinksInput::new(hash.clone().into(), interested_filter.clone(), None))
+        .collect();
+    let all_interested = HDK.with(|h| h.borrow().get_links(inputs))?;
+
+    let hydrated = original_hashes
+        .into_iter()
+        .zip(latest_revisions)
+        .zip(all_interested)
+        .map(|((original_hash, latest), interested)| HydratedLink {
+            original_hash,
+            latest,
+            interested_count: interested.len(),
+            status,
+        })
+        .collect();
+    page.hydrated = Some(hydrated);
+    Ok(page)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum PageOrder {
+    Ascending,
+    Descending,
+}
+
+struct PageQuery {
+    start: Option<Timestamp>,
+    end: Option<Timestamp>,
+    order: PageOrder,
+    limit: usize,
+    cursor: Option<CollectionCursor>,
+}
+
+/// Walks the day buckets of the collection in the order of the query, sorting the links
+/// of each bucket by start time and stopping as soon as the page is full
+fn get_page(status: CollectionStatus, query: PageQuery) -> ExternResult<CollectionPage> {
+    if query.limit == 0 {
+        return Err(wasm_error!(WasmErrorInner::Guest(String::from(
+            "The limit of a page must be at least one"
+        ))));
+    }
+    let collection = status.path();
+    let start_time_of = status.link_start_time();
+    let cursor_key = query
+        .cursor
+        .as_ref()
+        .map(|cursor| (cursor.start_time, cursor.create_link_hash.clone()));
+    let in_page = |key: &(Timestamp, ActionHash)| {
+        let after_start = !matches!(query.start, Some(start) if key.0 < start);
+        let before_end = !matches!(query.end, Some(end) if key.0 >= end);
+        let after_cursor = match (&cursor_key, query.order) {
+            (Some(cursor_key), PageOrder::Ascending) => key > cursor_key,
+            (Some(cursor_key), PageOrder::Descending) => key < cursor_key,
+            (None, _) => true,
+        };
+        after_start && before_end && after_cursor
+    };
+
+    let mut from = query.start.map(day_of).unwrap_or(FIRST_DAY);
+    let mut to = query.end.map(day_of).unwrap_or(LAST_DAY);
+    if let Some(cursor) = &query.cursor {
+        match query.order {
+            PageOrder::Ascending => from = from.max(day_of(cursor.start_time)),
+            PageOrder::Descending => to = to.min(day_of(cursor.start_time)),
+        }
+    }
+    let mut buckets = day_buckets_between(collection, from, to)?;
+    if query.order == PageOrder::Descending {
+        buckets.reverse();
+    }
+
+    let mut links: Vec<((Timestamp, ActionHash), Link)> = vec![];
+    for bucket in buckets {
+        let mut bucket_links: Vec<((Timestamp, ActionHash), Link)> =
+            get_links(bucket.path_entry_hash()?, status.link_type(), None)?
+                .into_iter()
+                .filter_map(|link| {
+                    let key = (start_time_of(&link)?, link.create_link_hash.clone());
+                    Some((key, link))
+                })
+                .filter(|(key, _)| in_page(key))
+                .collect();
+        bucket_links.sort_by(|(key_a, _), (key_b, _)| key_a.cmp(key_b));
+        if query.order == PageOrder::Descending {
+            bucket_links.reverse();
+        }
+        links.append(&mut bucket_links);
+
+        if links.len() > query.limit {
+            break;
+        }
+    }
+
+    let next_cursor = if links.len() > query.limit {
+        links.truncate(query.limit);
+        links
+            .last()
+            .map(|((start_time, create_link_hash), _)| CollectionCursor {
+                start_time: *start_time,
+                create_link_hash: create_link_hash.clone(),
+            })
+    } else {
+        None
+    };
+
+    Ok(CollectionPage {
+        links: links.into_iter().map(|(_, link)| link).collect(),
+        next_cursor,
+        hydrated: None,
+    })
+}
+
+fn event_link_start_time(link: &Link) -> Option<Timestamp> {
+    deserialize_link_tag::<EventCollectionTag>(link.tag.clone()).map(|tag| tag.start_time)
+}
+
+fn proposal_link_start_time(link: &Link) -> Option<Timestamp> {
+    deserialize_link_tag::<ProposalCollectionTag>(link.tag.clone()).map(|tag| tag.start_time)
+}
+
+/// The day buckets of the collection between the given days, both included, in ascending order
+///
+/// Only the buckets that have been created are visited, following the TimeIndex links
+/// from the collection to its years, months and days
+fn day_buckets_between(
+    collection: &str,
+    from: (i64, u32, u32),
+    to: (i64, u32, u32),
+) -> ExternResult<Vec<Path>> {
+    let mut buckets = vec![];
+    for (year, year_path) in time_index_children(Path::from(collection))? {
+        if year < from.0 || year > to.0 {
+            continue;
+        }
+        for (month, month_path) in time_index_children(year_path)? {
+            let month = (year, month as u32);
+            if month < (from.0, from.1) || month > (to.0, to.1) {
+                continue;
+            }
+            for (day, day_path) in time_index_children(month_path)? {
+                let day = (month.0, month.1, day as u32);
+                if day < from || day > to {
+                    continue;
+                }
+                buckets.push(day_path);
+            }
+        }
+    }
+    Ok(buckets)
+}
+
+fn time_index_children(path: Path) -> ExternResult<Vec<(i64, Path)>> {
+    let mut children: Vec<(i64, Path)> = path
+        .typed(LinkTypes::TimeIndex)?
+        .children_paths()?
+        .into_iter()
+        .filter_map(|child| {
+            let value = String::try_from(child.leaf()?).ok()?.parse::<i64>().ok()?;
+            Some((value, child.path))
+        })
+        .collect();
+    children.sort_by_key(|(value, _)| *value);
+    Ok(children)
 }
 
 /** Helpers */
 
-fn get_from_path(path: Path, link_type: LinkTypes) -> ExternResult<Vec<Link>> {
-    let mut links = get_links(path.path_entry_hash()?, link_type, None)?;
+fn get_all_from_collection(collection: &str, link_type: LinkTypes) -> ExternResult<Vec<Link>> {
+    let mut links = vec![];
+    for bucket in day_buckets_between(collection, FIRST_DAY, LAST_DAY)? {
+        links.append(&mut get_links(bucket.path_entry_hash()?, link_type, None)?);
+    }
     links.sort_by(|a, b| b.timestamp.cmp(&a.timestamp));
     Ok(links)
 }
 
+/// Removes the given event or proposal from the day buckets of all the given start times
 pub fn remove_from_collection(
     hash: &ActionHash,
-    path: Path,
+    collection: &str,
     link_type: LinkTypes,
+    start_times: Vec<Timestamp>,
 ) -> ExternResult<()> {
-    let links = get_links(path.path_entry_hash()?, link_type, None)?;
+    for link in get_links_to(hash, collection, link_type, start_times)? {
+        delete_link(link.create_link_hash)?;
+    }
+    Ok(())
+}
+
+/// Removes the given event or proposal from the collection of the status it transitions from,
+/// deleting the link of its latest transition last, since the next transition must come right
+/// after it and refer to it
+fn remove_previous_transition(
+    hash: &ActionHash,
+    collection: &str,
+    link_type: LinkTypes,
+    start_times: Vec<Timestamp>,
+) -> ExternResult<ActionHash> {
+    let mut links = get_links_to(hash, collection, link_type, start_times)?;
+    links.sort_by_key(|link| link.timestamp);
+    let Some(previous_transition) = links.pop() else {
+        return Err(wasm_error!(WasmErrorInner::Guest(format!(
+            "The event or proposal is not in the {collection} collection"
+        ))));
+    };
     for link in links {
-        if let Some(action_hash) = link.target.into_action_hash() {
-            if action_hash.eq(&hash) {
-                delete_link(link.create_link_hash)?;
+        delete_link(link.create_link_hash)?;
+    }
+    delete_link(previous_transition.create_link_hash.clone())?;
+    Ok(previous_transition.create_link_hash)
+}
+
+/// Whether the event is currently filed in the collection of the given status
+pub fn event_has_status(event_hash: &ActionHash, status: EventStatus) -> ExternResult<bool> {
+    let links = get_links_to(
+        event_hash,
+        status.path(),
+        status.link_type(),
+        event_start_times(event_hash)?,
+    )?;
+    Ok(!links.is_empty())
+}
+
+/// Whether the proposal is currently filed in the collection of the given status
+pub fn proposal_has_status(
+    proposal_hash: &ActionHash,
+    status: ProposalStatus,
+) -> ExternResult<bool> {
+    let links = get_links_to(
+        proposal_hash,
+        status.path(),
+        status.link_type(),
+        proposal_start_times(proposal_hash)?,
+    )?;
+    Ok(!links.is_empty())
+}
+
+fn get_links_to(
+    hash: &ActionHash,
+    collection: &str,
+    link_type: LinkTypes,
+    start_times: Vec<Timestamp>,
+) -> ExternResult<Vec<Link>> {
+    let buckets: BTreeSet<String> = start_times
+        .into_iter()
+        .map(|start_time| time_bucket_path(collection, start_time))
+        .collect();
+    let mut links = vec![];
+    for bucket in buckets {
+        for link in get_links(Path::from(bucket).path_entry_hash()?, link_type, None)? {
+            if link.target.clone().into_action_hash().as_ref() == Some(hash) {
+                links.push(link);
             }
         }
     }
+    Ok(links)
+}
+
+pub fn serialize_link_tag<T>(tag: T) -> ExternResult<LinkTag>
+where
+    SerializedBytes: TryFrom<T, Error = SerializedBytesError>,
+{
+    let bytes = SerializedBytes::try_from(tag).map_err(|err| wasm_error!(err))?;
+    Ok(LinkTag::new(bytes.bytes().clone()))
+}
+
+/// Removes the event from the collection of its previous status and files it under the day
+/// bucket of the given revision in the collection of its next status,
+/// since validation requires the transition to come right after the removal
+pub fn transition_event(
+    event_hash: ActionHash,
+    revision: Record,
+    transition: EventTransition,
+) -> ExternResult<()> {
+    let next_status = transition.next_status();
+    let start_time = event_start_time(&revision)?;
+    let path =
+        Path::from(time_bucket_path(next_status.path(), start_time)).typed(LinkTypes::TimeIndex)?;
+    path.ensure()?;
+    let previous_transition = match transition.previous_status() {
+        Some(previous_status) => Some(remove_previous_transition(
+            &event_hash,
+            previous_status.path(),
+            previous_status.link_type(),
+            event_start_times(&event_hash)?,
+        )?),
+        None => None,
+    };
+    let tag = EventCollectionTag {
+        transition,
+        revision_hash: revision.action_address().clone(),
+        start_time,
+        previous_transition,
+    };
+    create_link(
+        path.path_entry_hash()?,
+        event_hash.clone(),
+        next_status.link_type(),
+        serialize_link_tag(tag)?,
+    )?;
+
+    // Only upcoming events can be found by their location
+    if next_status == EventStatus::Upcoming {
+        index_event_location(&event_hash, &revision)
+    } else {
+        remove_from_location_index(&event_hash)
+    }
+}
+
+/// Moves an upcoming event to the day bucket of its new revision if its start time changed
+pub fn reschedule_event(event_hash: ActionHash, revision: Record) -> ExternResult<()> {
+    let start_time = event_start_time(&revision)?;
+    let upcoming_links = get_links_to(
+        &event_hash,
+        ALL_UPCOMING_EVENTS,
+        LinkTypes::UpcomingEvents,
+        event_start_times(&event_hash)?,
+    )?;
+    if upcoming_links.is_empty()
+        || upcoming_links
+            .iter()
+            .any(|link| event_link_start_time(link) == Some(start_time))
+    {
+        return Ok(());
+    }
+    transition_event(event_hash, revision, EventTransition::Rescheduled)
+}
+
+/// Removes the proposal from the collection of its previous status and files it under the day
+/// bucket of the given revision in the collection of its next status,
+/// since validation requires the transition to come right after the removal
+pub fn transition_proposal(
+    proposal_hash: ActionHash,
+    revision: Record,
+    transition: ProposalTransition,
+) -> ExternResult<()> {
+    let next_status = transition.next_status();
+    let created_at = proposal_created_at(&proposal_hash)?;
+    let start_time = proposal_revision_start_time(&revision, created_at)?;
+    let path =
+        Path::from(time_bucket_path(next_status.path(), start_time)).typed(LinkTypes::TimeIndex)?;
+    path.ensure()?;
+    let previous_transition = match transition.previous_status() {
+        Some(previous_status) => Some(remove_previous_transition(
+            &proposal_hash,
+            previous_status.path(),
+            previous_status.link_type(),
+            proposal_start_times(&proposal_hash)?,
+        )?),
+        None => None,
+    };
+    let tag = ProposalCollectionTag {
+        transition,
+        revision_hash: revision.action_address().clone(),
+        start_time,
+        previous_transition,
+    };
+    create_link(
+        path.path_entry_hash()?,
+        proposal_hash.clone(),
+        next_status.link_type(),
+        serialize_link_tag(tag)?,
+    )?;
     Ok(())
 }
+
+/// Moves an open proposal to the day bucket of its new revision if its start time changed
+pub fn reschedule_proposal(proposal_hash: ActionHash, revision: Record) -> ExternResult<()> {
+    let created_at = proposal_created_at(&proposal_hash)?;
+    let start_time = proposal_revision_start_time(&revision, created_at)?;
+    let open_links = get_links_to(
+        &proposal_hash,
+        ALL_OPEN_PROPOSALS,
+        LinkTypes::OpenProposals,
+        proposal_start_times(&proposal_hash)?,
+    )?;
+    if open_links.is_empty()
+        || open_links
+            .iter()
+            .any(|link| proposal_link_start_time(link) == Some(start_time))
+    {
+        return Ok(());
+    }
+    transition_proposal(proposal_hash, revision, ProposalTransition::Rescheduled)
+}
+
+fn must_get_latest_event(event_hash: &ActionHash) -> ExternResult<Record> {
+    get_live_event(event_hash.clone())?.ok_or(wasm_error!(WasmErrorInner::Guest(String::from(
+        "Could not find the event"
+    ))))
+}
+
+fn must_get_latest_proposal(proposal_hash: &ActionHash) -> ExternResult<Record> {
+    get_live_proposal(proposal_hash.clone())?.ok_or(wasm_error!(WasmErrorInner::Guest(
+        String::from("Could not find the proposal")
+    )))
+}
+
+fn event_start_time(revision: &Record) -> ExternResult<Timestamp> {
+    let event: Event = revision
+        .entry()
+        .to_app_option()
+        .map_err(|err| wasm_error!(err))?
+        .ok_or(wasm_error!(WasmErrorInner::Guest(String::from(
+            "Malformed event"
+        ))))?;
+    Ok(event.time.start_time())
+}
+
+/// The start times of all the revisions of the event,
+/// which are all the day buckets it may be filed under
+pub fn event_start_times(event_hash: &ActionHash) -> ExternResult<Vec<Timestamp>> {
+    get_all_revisions(event_hash)?
+        .iter()
+        .map(event_start_time)
+        .collect()
+}
+
+fn proposal_revision_start_time(
+    revision: &Record,
+    created_at: Timestamp,
+) -> ExternResult<Timestamp> {
+    let proposal: Proposal = revision
+        .entry()
+        .to_app_option()
+        .map_err(|err| wasm_error!(err))?
+        .ok_or(wasm_error!(WasmErrorInner::Guest(String::from(
+            "Malformed proposal"
+        ))))?;
+    Ok(proposal_start_time(&proposal, created_at))
+}
+
+fn proposal_created_at(proposal_hash: &ActionHash) -> ExternResult<Timestamp> {
+    let original_proposal = get_original_proposal(proposal_hash.clone())?.ok_or(wasm_error!(
+        WasmErrorInner::Guest(String::from("Could not find the proposal"))
+    ))?;
+    Ok(original_proposal.action().timestamp())
+}
+
+/// The start times of all the revisions of the proposal,
+/// which are all the day buckets it may be filed under
+pub fn proposal_start_times(proposal_hash: &ActionHash) -> ExternResult<Vec<Timestamp>> {
+    let created_at = proposal_created_at(proposal_hash)?;
+    get_all_revisions(proposal_hash)?
+        .iter()
+        .map(|revision| proposal_revision_start_time(revision, created_at))
+        .collect()
+}
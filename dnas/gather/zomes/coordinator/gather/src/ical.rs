@@ -0,0 +1,744 @@
+use std::time::Duration;
+
+use gather_integrity::*;
+use hdk::prelude::*;
+
+use crate::{
+    event::{create_event, get_live_event},
+    get_entry_for_action,
+    my_events::get_my_events,
+    occurrence_overrides::get_occurrence_override_entries,
+};
+
+const PRODID: &str = "-//darksoil.studio//Gather//EN";
+
+const MICROS_PER_SECOND: i64 = 1_000_000;
+const MICROS_PER_MINUTE: i64 = 60 * MICROS_PER_SECOND;
+const MICROS_PER_HOUR: i64 = 60 * MICROS_PER_MINUTE;
+const MICROS_PER_WEEK: i64 = 7 * MICROS_PER_DAY;
+
+/// Renders the latest revision of the event as an RFC 5545 VCALENDAR
+#[hdk_extern]
+pub fn export_event_to_ical(event_hash: ActionHash) -> ExternResult<String> {
+    let record = get_live_event(event_hash.clone())?.ok_or(ical_error("Event not found"))?;
+    let vevents = event_to_vevents(&event_hash, record)?;
+    Ok(vcalendar(vevents))
+}
+
+/// Renders all the events in my events as a single VCALENDAR, to be subscribed to as a feed
+///
+/// Events that were deleted or that can't be found right now are left out of the feed
+#[hdk_extern]
+pub fn export_my_events_to_ical(_: ()) -> ExternResult<String> {
+    let mut event_hashes: Vec<ActionHash> = Vec::new();
+    for link in get_my_events(())? {
+        let Some(hash) = link.target.into_action_hash() else {
+            continue;
+        };
+        if event_hashes.contains(&hash) {
+            continue;
+        }
+        if let Some(EntryTypes::Event(_)) = get_entry_for_action(&hash)? {
+            event_hashes.push(hash);
+        }
+    }
+
+    let mut vevents = Vec::new();
+    for event_hash in event_hashes {
+        if let Some(record) = get_live_event(event_hash.clone())? {
+            vevents.append(&mut event_to_vevents(&event_hash, record)?);
+        }
+    }
+    Ok(vcalendar(vevents))
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct ImportEventFromIcalInput {
+    pub ical: String,
+    pub image: EntryHash,
+    pub call_to_action_hash: ActionHash,
+    /// Where the event takes place if the VEVENT has no LOCATION
+    #[serde(default)]
+    pub location: Option<Location>,
+}
+/// Creates an event hosted by me from the first VEVENT of the given VCALENDAR
+///
+/// Only UTC and all-day times are supported, since there is no timezone database in the zome
+#[hdk_extern]
+pub fn import_event_from_ical(input: ImportEventFromIcalInput) -> ExternResult<Record> {
+    let properties = parse_first_vevent(&input.ical)?;
+    let property = |name: &str| properties.iter().find(|property| property.name == name);
+
+    let title = property("SUMMARY")
+        .map(|summary| unescape_text(&summary.value))
+        .ok_or(ical_error("The VEVENT has no SUMMARY"))?;
+    let description = property("DESCRIPTION")
+        .map(|description| unescape_text(&description.value))
+        .filter(|description| !description.trim().is_empty())
+        .unwrap_or(title.clone());
+    let location = match parse_location(property("LOCATION"), property("GEO"))? {
+        Some(location) => location,
+        None => input.location.ok_or(ical_error(
+            "The VEVENT has no LOCATION, so the location of the event must be given",
+        ))?,
+    };
+    let tags = property("CATEGORIES")
+        .map(|categories| parse_categories(&categories.value))
+        .unwrap_or_default();
+
+    let dtstart = property("DTSTART").ok_or(ical_error("The VEVENT has no DTSTART"))?;
+    let start_time = parse_ical_time(dtstart)?;
+    let end_time = match (property("DTEND"), property("DURATION")) {
+        (Some(dtend), _) => parse_ical_time(dtend)?,
+        (None, Some(duration)) => {
+            let duration = parse_ical_duration(&duration.value)?;
+            i64::try_from(duration.as_micros())
+                .ok()
+                .and_then(|micros| start_time.as_micros().checked_add(micros))
+                .map(Timestamp::from_micros)
+                .ok_or(ical_error("The DURATION of the VEVENT is too long"))?
+        }
+        // All-day events without an end last the whole day
+        (None, None) if dtstart.is_date() => start_time
+            .as_micros()
+            .checked_add(MICROS_PER_DAY)
+            .map(Timestamp::from_micros)
+            .ok_or(ical_error("Malformed DTSTART"))?,
+        // Timed events without an end last one hour
+        (None, None) => start_time
+            .as_micros()
+            .checked_add(MICROS_PER_HOUR)
+            .map(Timestamp::from_micros)
+            .ok_or(ical_error("Malformed DTSTART"))?,
+    };
+    if end_time < start_time {
+        return Err(ical_error("The VEVENT ends before it starts"));
+    }
+
+    let time = match property("RRULE") {
+        None => EventTime::Unique {
+            start_time,
+            end_time,
+        },
+        Some(rrule) => {
+            let mut recurrence = parse_rrule(&rrule.value)?;
+            for exdate in properties
+                .iter()
+                .filter(|property| property.name == "EXDATE")
+            {
+                for value in exdate.value.split(',') {
+                    let exclusion = IcalProperty {
+                        name: exdate.name.clone(),
+                        params: exdate.params.clone(),
+                        value: value.to_string(),
+                    };
+                    recurrence.exclusions.push(parse_ical_time(&exclusion)?);
+                }
+            }
+            EventTime::Recurring {
+                start_time,
+                event_duration: Duration::from_micros(
+                    (end_time.as_micros() - start_time.as_micros()) as u64,
+                ),
+                recurrence,
+            }
+        }
+    };
+
+    create_event(Event {
+        hosts: vec![agent_info()?.agent_initial_pubkey],
+        title,
+        description,
+        image: input.image,
+        location,
+        time,
+        cost: None,
+        tags,
+        capacity: None,
+        call_to_action_hash: input.call_to_action_hash,
+        from_proposal: None,
+    })
+}
+
+/** Export */
+
+/// The VEVENT of the event, followed by one VEVENT for each of its moved or relocated occurrences
+///
+/// The first host is the ORGANIZER and the rest are ATTENDEEs with the CHAIR role,
+/// since RFC 5545 only allows one ORGANIZER per VEVENT
+fn event_to_vevents(event_hash: &ActionHash, record: Record) -> ExternResult<Vec<Vec<String>>> {
+    let event: Event = record
+        .entry()
+        .to_app_option()
+        .map_err(|err| wasm_error!(err))?
+        .ok_or(ical_error("Malformed event"))?;
+
+    let uid = format!("{}@gather", ActionHashB64::from(event_hash.clone()));
+    let dtstamp = format_ical_time(sys_time()?);
+
+    let mut description = event.description.clone();
+    if let Some(cost) = &event.cost {
+        description.push_str(&format!("\n\nCost: {cost}"));
+    }
+
+    let mut common = vec![
+        format!("UID:{uid}"),
+        format!("DTSTAMP:{dtstamp}"),
+        format!(
+            "LAST-MODIFIED:{}",
+            format_ical_time(record.action().timestamp())
+        ),
+        format!("SUMMARY:{}", escape_text(&event.title)),
+        format!("DESCRIPTION:{}", escape_text(&description)),
+    ];
+    if !event.tags.is_empty() {
+        let categories: Vec<String> = event.tags.iter().map(|tag| escape_text(tag)).collect();
+        common.push(format!("CATEGORIES:{}", categories.join(",")));
+    }
+    for (i, host) in event.hosts.iter().enumerate() {
+        let host = AgentPubKeyB64::from(host.clone());
+        match i {
+            0 => common.push(format!("ORGANIZER;CN={host}:urn:holochain:agent:{host}")),
+            _ => common.push(format!(
+                "ATTENDEE;ROLE=CHAIR;CN={host}:urn:holochain:agent:{host}"
+            )),
+        }
+    }
+
+    let (start_time, first_end_time, rrule, mut exdates) = match &event.time {
+        EventTime::Unique {
+            start_time,
+            end_time,
+        } => (*start_time, *end_time, None, vec![]),
+        EventTime::Periodic {
+            start_time,
+            event_duration,
+            period_duration,
+            ocurrences: occurrences,
+        } => (
+            *start_time,
+            add_duration(*start_time, event_duration),
+            Some(periodic_rrule(period_duration, *occurrences)),
+            vec![],
+        ),
+        EventTime::Recurring {
+            start_time,
+            event_duration,
+            recurrence,
+        } => (
+            *start_time,
+            add_duration(*start_time, event_duration),
+            Some(recurrence_rrule(recurrence)),
+            recurrence.exclusions.clone(),
+        ),
+    };
+
+    let mut moved_occurrences: Vec<Vec<String>> = Vec::new();
+    for occurrence_override in get_occurrence_override_entries(event_hash)? {
+        let Some(occurrence) = event.time.occurrence(occurrence_override.occurrence_index) else {
+            continue;
+        };
+        let (start_time, end_time, location) = match occurrence_override.change {
+            OccurrenceChange::Cancelled => {
+                exdates.push(occurrence.start_time);
+                continue;
+            }
+            OccurrenceChange::Rescheduled {
+                start_time,
+                end_time,
+            } => (start_time, end_time, event.location.clone()),
+            OccurrenceChange::Relocated { location } => {
+                (occurrence.start_time, occurrence.end_time, location)
+            }
+        };
+        let mut vevent = vec![String::from("BEGIN:VEVENT")];
+        vevent.extend(common.clone());
+        vevent.push(format!(
+            "RECURRENCE-ID:{}",
+            format_ical_time(occurrence.start_time)
+        ));
+        vevent.push(format!("DTSTART:{}", format_ical_time(start_time)));
+        vevent.push(format!("DTEND:{}", format_ical_time(end_time)));
+        vevent.extend(location_properties(&location));
+        vevent.push(String::from("END:VEVENT"));
+        moved_occurrences.push(vevent);
+    }
+
+    let mut vevent = vec![String::from("BEGIN:VEVENT")];
+    vevent.extend(common);
+    vevent.push(format!("DTSTART:{}", format_ical_time(start_time)));
+    vevent.push(format!("DTEND:{}", format_ical_time(first_end_time)));
+    if let Some(rrule) = rrule {
+        vevent.push(format!("RRULE:{rrule}"));
+    }
+    if !exdates.is_empty() {
+        let exdates: Vec<String> = exdates.into_iter().map(format_ical_time).collect();
+        vevent.push(format!("EXDATE:{}", exdates.join(",")));
+    }
+    vevent.extend(location_properties(&event.location));
+    vevent.push(String::from("END:VEVENT"));
+
+    let mut vevents = vec![vevent];
+    vevents.append(&mut moved_occurrences);
+    Ok(vevents)
+}
+
+fn location_properties(location: &Location) -> Vec<String> {
+    let mut properties = Vec::new();
+    match location.address().or(location.url()) {
+        Some(location) => properties.push(format!("LOCATION:{}", escape_text(location))),
+        None => return properties,
+    }
+    if let Some(Coordinates {
+        latitude,
+        longitude,
+    }) = location.coordinates()
+    {
+        properties.push(format!("GEO:{latitude};{longitude}"));
+    }
+    if let Some(url) = location.url() {
+        properties.push(format!("URL:{url}"));
+    }
+    properties
+}
+
+fn vcalendar(vevents: Vec<Vec<String>>) -> String {
+    let mut lines = vec![
+        String::from("BEGIN:VCALENDAR"),
+        String::from("VERSION:2.0"),
+        format!("PRODID:{PRODID}"),
+        String::from("CALSCALE:GREGORIAN"),
+    ];
+    for vevent in vevents {
+        lines.extend(vevent);
+    }
+    lines.push(String::from("END:VCALENDAR"));
+
+    lines
+        .iter()
+        .map(|line| fold_line(line))
+        .collect::<Vec<String>>()
+        .join("\r\n")
+        + "\r\n"
+}
+
+/// Periodic events repeat every fixed duration, which maps to the coarsest frequency that
+/// divides it
+fn periodic_rrule(period_duration: &Duration, occurrences: Option<u64>) -> String {
+    let period = period_duration.as_micros() as i64;
+    let (frequency, interval) = [
+        ("WEEKLY", MICROS_PER_WEEK),
+        ("DAILY", MICROS_PER_DAY),
+        ("HOURLY", MICROS_PER_HOUR),
+        ("MINUTELY", MICROS_PER_MINUTE),
+    ]
+    .into_iter()
+    .find(|(_, unit)| period % unit == 0)
+    .map(|(frequency, unit)| (frequency, period / unit))
+    .unwrap_or(("SECONDLY", (period / MICROS_PER_SECOND).max(1)));
+
+    let mut rrule = format!("FREQ={frequency};INTERVAL={interval}");
+    if let Some(occurrences) = occurrences {
+        rrule.push_str(&format!(";COUNT={occurrences}"));
+    }
+    rrule
+}
+
+fn recurrence_rrule(recurrence: &Recurrence) -> String {
+    let frequency = match recurrence.frequency {
+        Frequency::Daily => "DAILY",
+        Frequency::Weekly => "WEEKLY",
+        Frequency::Monthly => "MONTHLY",
+        Frequency::Yearly => "YEARLY",
+    };
+    let mut rrule = format!("FREQ={frequency};INTERVAL={}", recurrence.interval);
+
+    let by_day: Vec<String> = recurrence
+        .by_weekday
+        .iter()
+        .map(|weekday| weekday_code(weekday).to_string())
+        .chain(
+            recurrence
+                .by_nth_weekday
+                .iter()
+                .map(|NthWeekday { nth, weekday }| format!("{nth}{}", weekday_code(weekday))),
+        )
+        .collect();
+    if !by_day.is_empty() {
+        rrule.push_str(&format!(";BYDAY={}", by_day.join(",")));
+    }
+    if !recurrence.by_month_day.is_empty() {
+        let by_month_day: Vec<String> = recurrence
+            .by_month_day
+            .iter()
+            .map(|month_day| month_day.to_string())
+            .collect();
+        rrule.push_str(&format!(";BYMONTHDAY={}", by_month_day.join(",")));
+    }
+    if let Some(until) = recurrence.until {
+        rrule.push_str(&format!(";UNTIL={}", format_ical_time(until)));
+    }
+    if let Some(count) = recurrence.count {
+        rrule.push_str(&format!(";COUNT={count}"));
+    }
+    rrule
+}
+
+const WEEKDAY_CODES: [(Weekday, &str); 7] = [
+    (Weekday::Monday, "MO"),
+    (Weekday::Tuesday, "TU"),
+    (Weekday::Wednesday, "WE"),
+    (Weekday::Thursday, "TH"),
+    (Weekday::Friday, "FR"),
+    (Weekday::Saturday, "SA"),
+    (Weekday::Sunday, "SU"),
+];
+
+fn weekday_code(weekday: &Weekday) -> &'static str {
+    WEEKDAY_CODES
+        .iter()
+        .find(|(w, _)| w == weekday)
+        .map(|(_, code)| *code)
+        .unwrap_or("MO")
+}
+
+fn add_duration(time: Timestamp, duration: &Duration) -> Timestamp {
+    Timestamp::from_micros(time.as_micros().saturating_add(duration.as_micros() as i64))
+}
+
+/// Formats the time as a UTC DATE-TIME, like 20230621T183000Z
+fn format_ical_time(time: Timestamp) -> String {
+    let micros = time.as_micros();
+    let (year, month, day) = civil_from_days(micros.div_euclid(MICROS_PER_DAY));
+    let seconds = micros.rem_euclid(MICROS_PER_DAY) / MICROS_PER_SECOND;
+    format!(
+        "{year:04}{month:02}{day:02}T{:02}{:02}{:02}Z",
+        seconds / 3600,
+        seconds / 60 % 60,
+        seconds % 60
+    )
+}
+
+fn escape_text(text: &str) -> String {
+    text.replace('\\', "\\\\")
+        .replace(';', "\\;")
+        .replace(',', "\\,")
+        .replace("\r\n", "\\n")
+        .replace('\n', "\\n")
+}
+
+/// Splits lines longer than 75 octets into continuation lines starting with a space
+fn fold_line(line: &str) -> String {
+    let mut folded = String::new();
+    let mut line_length = 0;
+    for c in line.chars() {
+        if line_length + c.len_utf8() > 75 {
+            folded.push_str("\r\n ");
+            line_length = 1;
+        }
+        folded.push(c);
+        line_length += c.len_utf8();
+    }
+    folded
+}
+
+/** Import */
+
+#[derive(Debug, Clone)]
+struct IcalProperty {
+    name: String,
+    params: Vec<(String, String)>,
+    value: String,
+}
+
+impl IcalProperty {
+    fn param(&self, name: &str) -> Option<&str> {
+        self.params
+            .iter()
+            .find(|(param, _)| param == name)
+            .map(|(_, value)| value.as_str())
+    }
+
+    fn is_date(&self) -> bool {
+        self.param("VALUE") == Some("DATE") || self.value.len() == 8
+    }
+}
+
+fn ical_error(message: &str) -> WasmError {
+    wasm_error!(WasmErrorInner::Guest(format!(
+        "Invalid iCalendar: {message}"
+    )))
+}
+
+/// The properties of the first VEVENT, skipping the ones of its nested components like VALARM
+fn parse_first_vevent(ical: &str) -> ExternResult<Vec<IcalProperty>> {
+    // Unfold the continuation lines
+    let unfolded = ical
+        .replace("\r\n ", "")
+        .replace("\r\n\t", "")
+        .replace("\n ", "")
+        .replace("\n\t", "");
+
+    let mut properties = Vec::new();
+    let mut depth: Option<usize> = None;
+    for line in unfolded.lines().filter(|line| !line.trim().is_empty()) {
+        let property = parse_property(line)?;
+        match (property.name.as_str(), depth) {
+            ("BEGIN", None) if property.value == "VEVENT" => depth = Some(0),
+            ("BEGIN", Some(d)) => depth = Some(d + 1),
+            ("END", Some(0)) => return Ok(properties),
+            ("END", Some(d)) => depth = Some(d - 1),
+            (_, Some(0)) => properties.push(property),
+            _ => {}
+        }
+    }
+    Err(ical_error("No complete VEVENT found"))
+}
+
+/// Parses a content line like `DTSTART;TZID="Europe/Stockholm":20230621T183000`
+fn parse_property(line: &str) -> ExternResult<IcalProperty> {
+    let mut in_quotes = false;
+    let Some(colon) = line.char_indices().find_map(|(i, c)| {
+        if c == '"' {
+            in_quotes = !in_quotes;
+        }
+        (c == ':' && !in_quotes).then_some(i)
+    }) else {
+        return Err(ical_error(&format!("Malformed content line \"{line}\"")));
+    };
+
+    let mut name_and_params = line[..colon].split(';');
+    let name = name_and_params.next().unwrap_or_default().to_uppercase();
+    let params = name_and_params
+        .filter_map(|param| param.split_once('='))
+        .map(|(param, value)| (param.to_uppercase(), value.trim_matches('"').to_string()))
+        .collect();
+
+    Ok(IcalProperty {
+        name,
+        params,
+        value: line[colon + 1..].to_string(),
+    })
+}
+
+fn unescape_text(text: &str) -> String {
+    let mut unescaped = String::new();
+    let mut chars = text.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            unescaped.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('n') | Some('N') => unescaped.push('\n'),
+            Some(escaped) => unescaped.push(escaped),
+            None => {}
+        }
+    }
+    unescaped
+}
+
+/// A LOCATION that is a link is an online location, any other is a physical address,
+/// and `None` if there is no LOCATION
+///
+/// The URL property is ignored, since most calendars use it to link to the page of the event
+/// rather than to where it is streamed
+fn parse_location(
+    location: Option<&IcalProperty>,
+    geo: Option<&IcalProperty>,
+) -> ExternResult<Option<Location>> {
+    let Some(location) = location.map(|location| unescape_text(&location.value)) else {
+        return Ok(None);
+    };
+    if location.starts_with("https://") || location.starts_with("http://") {
+        return Ok(Some(Location::Online { url: location }));
+    }
+
+    let coordinates = match geo {
+        Some(geo) => {
+            let malformed = || ical_error(&format!("Malformed GEO \"{}\"", geo.value));
+            let (latitude, longitude) = geo.value.split_once(';').ok_or_else(malformed)?;
+            Some(Coordinates {
+                latitude: latitude.trim().parse().map_err(|_| malformed())?,
+                longitude: longitude.trim().parse().map_err(|_| malformed())?,
+            })
+        }
+        None => None,
+    };
+    Ok(Some(Location::Physical {
+        address: location,
+        coordinates,
+    }))
+}
+
+/// Normalizes the categories into tags, dropping the ones that can't be tags
+fn parse_categories(categories: &str) -> Vec<String> {
+    let mut tags: Vec<String> = Vec::new();
+    for category in categories.split(',') {
+        let tag = normalize_tag(&unescape_text(category));
+        if !tag.is_empty() && tag.chars().count() <= MAX_TAG_LENGTH && !tags.contains(&tag) {
+            tags.push(tag);
+        }
+    }
+    tags.truncate(MAX_TAGS);
+    tags
+}
+
+/// Parses a DATE like 20230621 or a UTC DATE-TIME like 20230621T183000Z
+fn parse_ical_time(property: &IcalProperty) -> ExternResult<Timestamp> {
+    if let Some(tzid) = property.param("TZID") {
+        if !["UTC", "Etc/UTC", "GMT"].contains(&tzid) {
+            return Err(ical_error(&format!(
+                "{} has the timezone {tzid}, only UTC times are supported",
+                property.name
+            )));
+        }
+    }
+
+    let value = property.value.trim();
+    let malformed = || ical_error(&format!("Malformed time \"{value}\""));
+    let number = |range: std::ops::Range<usize>, max: i64| -> ExternResult<i64> {
+        value
+            .get(range)
+            .and_then(|digits| digits.parse::<i64>().ok())
+            .filter(|number| (0..=max).contains(number))
+            .ok_or_else(malformed)
+    };
+    let year = number(0..4, 9999)?;
+    let month = number(4..6, 12)? as u32;
+    if month == 0 {
+        return Err(malformed());
+    }
+    let day = number(6..8, days_in_month(year, month) as i64)? as u32;
+    if day == 0 {
+        return Err(malformed());
+    }
+    let days = days_from_civil(year, month, day);
+    if property.is_date() {
+        return Ok(Timestamp::from_micros(days * MICROS_PER_DAY));
+    }
+
+    if value.get(8..9) != Some("T") {
+        return Err(malformed());
+    }
+    if !value.ends_with('Z') && property.param("TZID").is_none() {
+        return Err(ical_error(&format!(
+            "{} is a floating time, only UTC times are supported",
+            property.name
+        )));
+    }
+    // A second of 60 is a leap second
+    let seconds = number(9..11, 23)? * 3600 + number(11..13, 59)? * 60 + number(13..15, 60)?;
+    Ok(Timestamp::from_micros(
+        days * MICROS_PER_DAY + seconds * MICROS_PER_SECOND,
+    ))
+}
+
+/// Parses a DURATION like PT1H30M or P1W
+fn parse_ical_duration(value: &str) -> ExternResult<Duration> {
+    let malformed = || ical_error(&format!("Malformed duration \"{value}\""));
+    let value = value.trim().trim_start_matches('+');
+    let Some(value) = value.strip_prefix('P') else {
+        return Err(malformed());
+    };
+
+    let mut seconds: u64 = 0;
+    let mut number = String::new();
+    for c in value.chars() {
+        match c {
+            'T' => {}
+            '0'..='9' => number.push(c),
+            _ => {
+                let amount: u64 = number.parse().map_err(|_| malformed())?;
+                number.clear();
+                let unit = match c {
+                    'W' => 7 * 24 * 3600,
+                    'D' => 24 * 3600,
+                    'H' => 3600,
+                    'M' => 60,
+                    'S' => 1,
+                    _ => return Err(malformed()),
+                };
+                seconds = amount
+                    .checked_mul(unit)
+                    .and_then(|amount| seconds.checked_add(amount))
+                    .ok_or_else(malformed)?;
+            }
+        }
+    }
+    Ok(Duration::from_secs(seconds))
+}
+
+/// Parses the RRULE parts that `Recurrence` can express, rejecting the rest so that
+/// imported events never repeat differently than in their original calendar
+fn parse_rrule(rrule: &str) -> ExternResult<Recurrence> {
+    let mut recurrence = Recurrence {
+        frequency: Frequency::Daily,
+        interval: 1,
+        by_weekday: vec![],
+        by_nth_weekday: vec![],
+        by_month_day: vec![],
+        until: None,
+        count: None,
+        exclusions: vec![],
+    };
+    let malformed = |part: &str| ical_error(&format!("Unsupported RRULE part \"{part}\""));
+
+    for part in rrule.split(';').filter(|part| !part.is_empty()) {
+        let (name, value) = part.split_once('=').ok_or(malformed(part))?;
+        match name.to_uppercase().as_str() {
+            "FREQ" => {
+                recurrence.frequency = match value {
+                    "DAILY" => Frequency::Daily,
+                    "WEEKLY" => Frequency::Weekly,
+                    "MONTHLY" => Frequency::Monthly,
+                    "YEARLY" => Frequency::Yearly,
+                    _ => return Err(malformed(part)),
+                }
+            }
+            "INTERVAL" => recurrence.interval = value.parse().map_err(|_| malformed(part))?,
+            "COUNT" => recurrence.count = Some(value.parse().map_err(|_| malformed(part))?),
+            "UNTIL" => {
+                recurrence.until = Some(parse_ical_time(&IcalProperty {
+                    name: String::from("UNTIL"),
+                    params: vec![],
+                    value: value.to_string(),
+                })?)
+            }
+            "BYDAY" => {
+                for day in value.split(',') {
+                    if !day.is_ascii() {
+                        return Err(malformed(part));
+                    }
+                    let (nth, code) = day.split_at(day.len().saturating_sub(2));
+                    let weekday = WEEKDAY_CODES
+                        .iter()
+                        .find(|(_, c)| *c == code)
+                        .map(|(weekday, _)| *weekday)
+                        .ok_or(malformed(part))?;
+                    match nth {
+                        "" => recurrence.by_weekday.push(weekday),
+                        _ => recurrence.by_nth_weekday.push(NthWeekday {
+                            nth: nth
+                                .trim_start_matches('+')
+                                .parse()
+                                .map_err(|_| malformed(part))?,
+                            weekday,
+                        }),
+                    }
+                }
+            }
+            "BYMONTHDAY" => {
+                for month_day in value.split(',') {
+                    recurrence
+                        .by_month_day
+                        .push(month_day.parse().map_err(|_| malformed(part))?);
+                }
+            }
+            "WKST" if value == "MO" => {}
+            _ => return Err(malformed(part)),
+        }
+    }
+
+    Ok(recurrence)
+}
@@ -1,15 +1,28 @@
+pub mod attendance;
+pub mod co_hosts;
 pub mod event;
 pub mod global_collections;
+pub mod ical;
 pub mod interested;
 pub mod my_events;
+pub mod nearby_events;
 pub mod notifications;
+pub mod occurrence_overrides;
+pub mod private_events;
 pub mod proposal;
+pub mod revisions;
+pub mod rsvps;
+pub mod search;
+pub mod sweeps;
+pub mod tags;
 
 use gather_integrity::*;
 use hdk::prelude::*;
 
 #[hdk_extern]
 pub fn init(_: ()) -> ExternResult<InitCallbackResult> {
+    schedule("sweep_my_events")?;
+    private_events::publish_my_encryption_key()?;
     Ok(InitCallbackResult::Pass)
 }
 
@@ -115,7 +128,7 @@ fn signal_action(action: SignedActionHashed) -> ExternResult<()> {
         _ => Ok(()),
     }
 }
-fn get_entry_for_action(action_hash: &ActionHash) -> ExternResult<Option<EntryTypes>> {
+pub(crate) fn get_entry_for_action(action_hash: &ActionHash) -> ExternResult<Option<EntryTypes>> {
     let record = match get_details(action_hash.clone(), GetOptions::default())? {
         Some(Details::Record(record_details)) => record_details.record,
         _ => {
@@ -144,3 +157,95 @@ fn get_entry_for_action(action_hash: &ActionHash) -> ExternResult<Option<EntryTy
         entry,
     )?)
 }
+
+/// The latest revision of an event or a proposal, or the deletion that removed it
+///
+/// `conflicting_heads` are the other revisions that were made concurrently with the latest one,
+/// which a host needs to merge
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(tag = "type")]
+pub enum LatestRevision {
+    Live {
+        record: Record,
+        conflicting_heads: Vec<ActionHash>,
+    },
+    Deleted {
+        deletion: SignedActionHashed,
+    },
+}
+
+impl LatestRevision {
+    pub fn live(self) -> Option<Record> {
+        match self {
+            LatestRevision::Live { record, .. } => Some(record),
+            LatestRevision::Deleted { .. } => None,
+        }
+    }
+}
+
+/// Resolves the latest revision of the event or proposal from the heads of its revisions,
+/// and reports it deleted if its hosts deleted any of them
+pub(crate) fn get_latest_revision(
+    original_hash: ActionHash,
+) -> ExternResult<Option<LatestRevision>> {
+    let mut latest_revisions = get_latest_revisions(vec![original_hash])?;
+    Ok(latest_revisions.pop().flatten())
+}
+
+/// The latest revisions of the given events or proposals, in the same order,
+/// fetching the details of the heads of all of them in a single call
+pub(crate) fn get_latest_revisions(
+    original_hashes: Vec<ActionHash>,
+) -> ExternResult<Vec<Option<LatestRevision>>> {
+    let all_heads = revisions::get_all_revision_heads(original_hashes)?;
+    let inputs: Vec<GetInput> = all_heads
+        .iter()
+        .flatten()
+        .map(|head| GetInput::new(head.action_address().clone().into(), GetOptions::default()))
+        .collect();
+    let mut all_details = HDK.with(|h| h.borrow().get_details(inputs))?.into_iter();
+
+    let mut latest_revisions = vec![];
+    for heads in all_heads {
+        let mut deletion = None;
+        for details in all_details.by_ref().take(heads.len()) {
+            match details {
+                Some(Details::Record(details)) => {
+                    if deletion.is_none() {
+                        deletion = details.deletes.into_iter().next();
+                    }
+                }
+                Some(_) => {
+                    return Err(wasm_error!(WasmErrorInner::Guest(String::from(
+                        "Malformed get details response"
+                    ))))
+                }
+                None => {}
+            }
+        }
+        if let Some(deletion) = deletion {
+            latest_revisions.push(Some(LatestRevision::Deleted { deletion }));
+            continue;
+        }
+        let mut heads = heads.into_iter();
+        latest_revisions.push(heads.next().map(|record| LatestRevision::Live {
+            record,
+            conflicting_heads: heads.map(|head| head.action_address().clone()).collect(),
+        }));
+    }
+    Ok(latest_revisions)
+}
+
+/// The app entry of the record, failing if it has none or it is not a `T`
+pub(crate) fn entry_of<T>(record: &Record) -> ExternResult<T>
+where
+    T: TryFrom<SerializedBytes, Error = SerializedBytesError>,
+{
+    record
+        .entry()
+        .to_app_option()
+        .map_err(|err| wasm_error!(err))?
+        .ok_or(wasm_error!(WasmErrorInner::Guest(String::from(
+            "Malformed entry"
+        ))))
+}
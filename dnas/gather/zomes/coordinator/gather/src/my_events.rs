@@ -38,3 +38,40 @@ pub fn remove_from_my_events(event_or_proposal_hash: ActionHash) -> ExternResult
 
     Ok(())
 }
+
+/// Unlinks the deleted event or proposal from the agents interested in it or in any of its
+/// occurrences, that RSVPed to it or that attended it, and from the MyEvents and HostedEvents
+/// links of those agents and of its hosts
+pub fn remove_deleted_from_my_events(
+    event_or_proposal_hash: &ActionHash,
+    hosts: Vec<AgentPubKey>,
+) -> ExternResult<()> {
+    let mut agents = hosts;
+    agents.push(agent_info()?.agent_initial_pubkey);
+    for link_type in [
+        LinkTypes::Interested,
+        LinkTypes::InterestedInOccurrence,
+        LinkTypes::EventRsvps,
+        LinkTypes::EventAttendees,
+    ] {
+        for link in get_links(event_or_proposal_hash.clone(), link_type, None)? {
+            if let Some(agent) = link.target.into_agent_pub_key() {
+                agents.push(agent);
+            }
+            delete_link(link.create_link_hash)?;
+        }
+    }
+    agents.sort();
+    agents.dedup();
+
+    for agent in agents {
+        for link_type in [LinkTypes::MyEvents, LinkTypes::HostedEvents] {
+            for link in get_links(agent.clone(), link_type, None)? {
+                if link.target.clone().into_action_hash().as_ref() == Some(event_or_proposal_hash) {
+                    delete_link(link.create_link_hash)?;
+                }
+            }
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,256 @@
+use std::collections::BTreeSet;
+
+use gather_integrity::*;
+use hdk::prelude::*;
+
+use crate::{global_collections::serialize_link_tag, revisions::get_all_revisions};
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct TimeWindow {
+    pub start: Timestamp,
+    pub end: Timestamp,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct GetEventsNearInput {
+    pub latitude: f64,
+    pub longitude: f64,
+    pub radius_km: f64,
+    /// Only the events that overlap with this window are returned
+    pub time_window: TimeWindow,
+}
+
+/// The upcoming events located within `radius_km` of the given coordinates,
+/// sorted by their start time
+///
+/// Only the geohash cells that overlap with the searched area are visited,
+/// so the cost depends on the radius and not on the number of events in the network
+#[hdk_extern]
+pub fn get_events_near(input: GetEventsNearInput) -> ExternResult<Vec<Link>> {
+    let center = Coordinates {
+        latitude: input.latitude,
+        longitude: input.longitude,
+    };
+    if !(-90.0..=90.0).contains(&center.latitude) || !(-180.0..=180.0).contains(&center.longitude) {
+        return Err(wasm_error!(WasmErrorInner::Guest(String::from(
+            "Invalid coordinates"
+        ))));
+    }
+    if !input.radius_km.is_finite() || input.radius_km < 0.0 {
+        return Err(wasm_error!(WasmErrorInner::Guest(String::from(
+            "The radius must be a positive number of kilometers"
+        ))));
+    }
+
+    let area = BoundingBox::around(center, input.radius_km);
+    let mut links: Vec<(Timestamp, Link)> = vec![];
+    for cell_path in cells_overlapping(Path::from(UPCOMING_EVENTS_BY_LOCATION), "", &area)? {
+        for link in get_links(
+            cell_path.path_entry_hash()?,
+            LinkTypes::UpcomingEventsByLocation,
+            None,
+        )? {
+            let Some(tag) = deserialize_link_tag::<EventLocationTag>(link.tag.clone()) else {
+                continue;
+            };
+            let in_window = tag.start_time < input.time_window.end
+                && !matches!(tag.end_time, Some(end_time) if end_time <= input.time_window.start);
+            if in_window && distance_km(center, tag.coordinates) <= input.radius_km {
+                links.push((tag.start_time, link));
+            }
+        }
+    }
+
+    links.sort_by_key(|(start_time, _)| *start_time);
+    Ok(links.into_iter().map(|(_, link)| link).collect())
+}
+
+/// Files the upcoming event under the geohash of the coordinates of the given revision,
+/// removing it from the cells where its previous revisions were filed
+pub fn index_event_location(event_hash: &ActionHash, revision: &Record) -> ExternResult<()> {
+    let event: Event = revision
+        .entry()
+        .to_app_option()
+        .map_err(|err| wasm_error!(err))?
+        .ok_or(wasm_error!(WasmErrorInner::Guest(String::from(
+            "Malformed event"
+        ))))?;
+    let tag = event
+        .location
+        .coordinates()
+        .map(|coordinates| EventLocationTag {
+            revision_hash: revision.action_address().clone(),
+            coordinates,
+            start_time: event.time.start_time(),
+            end_time: event.time.end_time(),
+        });
+
+    let mut already_indexed = false;
+    for link in get_location_links_to(event_hash)? {
+        let unchanged = match (&tag, deserialize_link_tag::<EventLocationTag>(link.tag)) {
+            (Some(tag), Some(link_tag)) => {
+                tag.coordinates == link_tag.coordinates
+                    && tag.start_time == link_tag.start_time
+                    && tag.end_time == link_tag.end_time
+            }
+            _ => false,
+        };
+        if unchanged && !already_indexed {
+            already_indexed = true;
+        } else {
+            delete_link(link.create_link_hash)?;
+        }
+    }
+
+    let Some(tag) = tag else {
+        return Ok(());
+    };
+    if already_indexed {
+        return Ok(());
+    }
+    let path = Path::from(geohash_path(tag.coordinates)).typed(LinkTypes::GeohashIndex)?;
+    path.ensure()?;
+    create_link(
+        path.path_entry_hash()?,
+        event_hash.clone(),
+        LinkTypes::UpcomingEventsByLocation,
+        serialize_link_tag(tag)?,
+    )?;
+    Ok(())
+}
+
+/// Removes the event from the location index once it's no longer upcoming,
+/// skipping the links that only its hosts are allowed to remove
+pub fn remove_from_location_index(event_hash: &ActionHash) -> ExternResult<()> {
+    let my_pub_key = agent_info()?.agent_initial_pubkey;
+    let now = sys_time()?;
+    for link in get_location_links_to(event_hash)? {
+        let Some(tag) = deserialize_link_tag::<EventLocationTag>(link.tag) else {
+            continue;
+        };
+        let ended = matches!(tag.end_time, Some(end_time) if end_time <= now);
+        if ended || is_host_of_revision(&my_pub_key, &tag.revision_hash)? {
+            delete_link(link.create_link_hash)?;
+        }
+    }
+    Ok(())
+}
+
+/// Removes every link to the deleted event from the location index, including the ones
+/// created by the hosts of its other revisions
+pub fn remove_deleted_from_location_index(event_hash: &ActionHash) -> ExternResult<()> {
+    for link in get_location_links_to(event_hash)? {
+        delete_link(link.create_link_hash)?;
+    }
+    Ok(())
+}
+
+fn is_host_of_revision(agent: &AgentPubKey, revision_hash: &ActionHash) -> ExternResult<bool> {
+    let Some(record) = get(revision_hash.clone(), GetOptions::default())? else {
+        return Ok(false);
+    };
+    let event: Option<Event> = record
+        .entry()
+        .to_app_option()
+        .map_err(|err| wasm_error!(err))?;
+    Ok(matches!(event, Some(event) if event.hosts.contains(agent)))
+}
+
+/// The links to the event from the cells of the coordinates of all its revisions,
+/// which are all the cells it may be filed under
+fn get_location_links_to(event_hash: &ActionHash) -> ExternResult<Vec<Link>> {
+    let mut paths: BTreeSet<String> = BTreeSet::new();
+    for revision in get_all_revisions(event_hash)? {
+        let event: Option<Event> = revision
+            .entry()
+            .to_app_option()
+            .map_err(|err| wasm_error!(err))?;
+        if let Some(coordinates) = event.and_then(|event| event.location.coordinates()) {
+            paths.insert(geohash_path(coordinates));
+        }
+    }
+
+    let mut links = vec![];
+    for path in paths {
+        for link in get_links(
+            Path::from(path).path_entry_hash()?,
+            LinkTypes::UpcomingEventsByLocation,
+            None,
+        )? {
+            if link.target.clone().into_action_hash().as_ref() == Some(event_hash) {
+                links.push(link);
+            }
+        }
+    }
+    Ok(links)
+}
+
+/// The geohash cells with events under the given prefix that overlap with the area,
+/// following the GeohashIndex links one character at a time
+fn cells_overlapping(path: Path, prefix: &str, area: &BoundingBox) -> ExternResult<Vec<Path>> {
+    if prefix.len() == GEOHASH_PRECISION {
+        return Ok(vec![path]);
+    }
+    let mut cells = vec![];
+    for child in path.typed(LinkTypes::GeohashIndex)?.children_paths()? {
+        let Some(leaf) = child.leaf().and_then(|leaf| String::try_from(leaf).ok()) else {
+            continue;
+        };
+        let child_prefix = format!("{prefix}{leaf}");
+        let Some(cell) = geohash_cell(&child_prefix) else {
+            continue;
+        };
+        if area.overlaps(&cell) {
+            cells.append(&mut cells_overlapping(child.path, &child_prefix, area)?);
+        }
+    }
+    Ok(cells)
+}
+
+/// The smallest range of latitudes and longitudes that contains a circle,
+/// with longitudes beyond -180 or 180 if it crosses the antimeridian
+struct BoundingBox {
+    min_latitude: f64,
+    max_latitude: f64,
+    min_longitude: f64,
+    max_longitude: f64,
+}
+
+impl BoundingBox {
+    fn around(center: Coordinates, radius_km: f64) -> Self {
+        let latitude_delta = (radius_km / EARTH_RADIUS_KM).to_degrees();
+        let min_latitude = center.latitude - latitude_delta;
+        let max_latitude = center.latitude + latitude_delta;
+        if min_latitude <= -90.0 || max_latitude >= 90.0 {
+            // The circle contains a pole, so it spans all longitudes
+            return BoundingBox {
+                min_latitude: min_latitude.max(-90.0),
+                max_latitude: max_latitude.min(90.0),
+                min_longitude: -180.0,
+                max_longitude: 180.0,
+            };
+        }
+
+        let longitude_delta = (latitude_delta.to_radians().sin()
+            / center.latitude.to_radians().cos())
+        .min(1.0)
+        .asin()
+        .to_degrees();
+        BoundingBox {
+            min_latitude,
+            max_latitude,
+            min_longitude: center.longitude - longitude_delta,
+            max_longitude: center.longitude + longitude_delta,
+        }
+    }
+
+    fn overlaps(&self, cell: &GeohashCell) -> bool {
+        if cell.max_latitude < self.min_latitude || cell.min_latitude > self.max_latitude {
+            return false;
+        }
+        [-360.0, 0.0, 360.0].iter().any(|shift| {
+            cell.max_longitude + shift >= self.min_longitude
+                && cell.min_longitude + shift <= self.max_longitude
+        })
+    }
+}
@@ -1,6 +1,8 @@
 use std::fmt::Debug;
 
-use gather_integrity::{Event, Proposal};
+use gather_integrity::{
+    civil_from_days, Cost, Event, EventTime, Location, Proposal, MICROS_PER_DAY,
+};
 use hc_zome_trait_pending_notifications::*;
 use hc_zome_traits::*;
 use hdk::prelude::*;
@@ -8,7 +10,11 @@ use hrl::Hrl;
 use serde::de::DeserializeOwned;
 use xliff::t::T;
 
-use crate::{event::get_latest_event, proposal::get_latest_proposal};
+use crate::{
+    event::get_live_event,
+    proposal::get_live_proposal,
+    revisions::{get_revision_changes, FieldChange, RevisionChanges},
+};
 
 struct AlertsNotifications;
 
@@ -28,9 +34,10 @@ impl PendingNotifications for AlertsNotifications {
 
         match notification {
             GatherNotification::EventAlert { event_hash, action } => {
-                let latest_event = get_latest_event(event_hash.clone())?.ok_or(wasm_error!(
-                    WasmErrorInner::Guest(format!("Failed to get latest event"))
-                ))?;
+                // The event was deleted since
+                let Some(latest_event) = get_live_event(event_hash.clone())? else {
+                    return Ok(None);
+                };
 
                 let event = Event::try_from(latest_event.entry().as_option().ok_or(
                     wasm_error!(WasmErrorInner::Guest(format!(
@@ -57,11 +64,10 @@ impl PendingNotifications for AlertsNotifications {
                 proposal_hash,
                 action,
             } => {
-                let latest_proposal = get_latest_proposal(proposal_hash.clone())?.ok_or(
-                    wasm_error!(WasmErrorInner::Guest(format!(
-                        "Failed to get latest proposal: could not get proposal"
-                    ))),
-                )?;
+                // The proposal was deleted since
+                let Some(latest_proposal) = get_live_proposal(proposal_hash.clone())? else {
+                    return Ok(None);
+                };
 
                 let proposal = Proposal::try_from(latest_proposal.entry().as_option().ok_or(
                     wasm_error!(WasmErrorInner::Guest(format!(
@@ -107,7 +113,7 @@ fn t(locale: String, source: &str) -> String {
     }
 }
 
-fn call_assemble<
+pub(crate) fn call_assemble<
     P: Serialize + DeserializeOwned + Debug,
     R: Serialize + DeserializeOwned + Debug,
 >(
@@ -130,6 +136,35 @@ fn call_assemble<
     }
 }
 
+#[derive(Serialize, Deserialize, Debug)]
+struct NotifyAlertInput {
+    alert: SerializedBytes,
+    agents: Vec<AgentPubKey>,
+}
+
+/// Sends the notification to the given agents through the alerts zome,
+/// the same way the UI does with `notifyAlert`
+pub fn notify_alert(
+    agents: Vec<AgentPubKey>,
+    notification: GatherNotification,
+) -> ExternResult<()> {
+    let alert = SerializedBytes::try_from(notification).map_err(|err| wasm_error!(err))?;
+    let result = call(
+        CallTargetCell::Local,
+        ZomeName::from("alerts"),
+        "notify_alert".into(),
+        None,
+        NotifyAlertInput { alert, agents },
+    )?;
+
+    match result {
+        ZomeCallResponse::Ok(_) => Ok(()),
+        r => Err(wasm_error!(WasmErrorInner::Guest(format!(
+            "Error calling alerts: {r:?}"
+        )))),
+    }
+}
+
 fn get_body(action: GatherAction, locale: String) -> ExternResult<String> {
     let t = match action {
         GatherAction::ProposalCreated { .. } => t(locale, "Proposal was created."),
@@ -139,9 +174,14 @@ fn get_body(action: GatherAction, locale: String) -> ExternResult<String> {
             locale,
             "Proposal expired without meeting the minimum required needs.",
         ),
-        GatherAction::ProposalUpdated { .. } => t(locale, "Proposal was updated."),
+        GatherAction::ProposalUpdated { action_hash } => {
+            match describe_changes(&locale, get_revision_changes::<Proposal>(&action_hash)?) {
+                Some(changes) => changes,
+                None => t(locale, "Proposal was updated."),
+            }
+        }
         GatherAction::EventCreated { action_hash } => {
-            let latest_event = get_latest_event(action_hash.clone())?.ok_or(wasm_error!(
+            let latest_event = get_live_event(action_hash.clone())?.ok_or(wasm_error!(
                 WasmErrorInner::Guest(format!("Failed to get latest event"))
             ))?;
 
@@ -155,7 +195,24 @@ fn get_body(action: GatherAction, locale: String) -> ExternResult<String> {
         }
         GatherAction::EventCancelled { .. } => t(locale, "Event was cancelled."),
         GatherAction::EventUncancelled { .. } => t(locale, "Event was uncancelled!"),
-        GatherAction::EventUpdated { .. } => t(locale, "Event was updated."),
+        GatherAction::EventUpdated { action_hash } => {
+            match describe_changes(&locale, get_revision_changes::<Event>(&action_hash)?) {
+                Some(changes) => changes,
+                None => t(locale, "Event was updated."),
+            }
+        }
+        GatherAction::OccurrenceChanged { .. } => {
+            t(locale, "One of the sessions of the event has changed.")
+        }
+        GatherAction::RsvpConfirmed { .. } => {
+            t(locale, "A seat was freed: you are now confirmed to attend!")
+        }
+        GatherAction::EventHostInvitation { .. } => {
+            t(locale, "You were invited to host this event.")
+        }
+        GatherAction::ProposalHostInvitation { .. } => {
+            t(locale, "You were invited to host this proposal.")
+        }
         // TODO: implement commitments notifications
         // GatherAction::CommitmentCreated{ action_hash } => {
         //     let maybe_commitment: Option<Record> = call_assemble("get_commitment".into(), action_hash.clone())?.ok_or(wasm_error!(
@@ -180,6 +237,92 @@ fn get_body(action: GatherAction, locale: String) -> ExternResult<String> {
     Ok(t)
 }
 
+/// One sentence for each change that attendees need to know about,
+/// `None` if the update changed none of them
+fn describe_changes(locale: &str, revision_changes: Option<RevisionChanges>) -> Option<String> {
+    let sentences: Vec<String> = revision_changes?
+        .changes
+        .into_iter()
+        .filter_map(|change| {
+            let (template, from, to) = match change {
+                FieldChange::Title { from, to } => {
+                    ("Title changed from \"{from}\" to \"{to}\".", from, to)
+                }
+                FieldChange::Time { from, to } => (
+                    "Time changed from {from} to {to}.",
+                    describe_time(locale, from),
+                    describe_time(locale, to),
+                ),
+                FieldChange::Location { from, to } => (
+                    "Location changed from {from} to {to}.",
+                    describe_location(locale, from),
+                    describe_location(locale, to),
+                ),
+                FieldChange::Cost { from, to } => {
+                    let describe_cost = |cost: Option<Cost>| match cost {
+                        Some(cost) => cost.to_string(),
+                        None => t(locale.to_string(), "to be announced"),
+                    };
+                    (
+                        "Cost changed from {from} to {to}.",
+                        describe_cost(from),
+                        describe_cost(to),
+                    )
+                }
+                FieldChange::Capacity { from, to } => {
+                    let describe_capacity = |capacity: Option<u32>| match capacity {
+                        Some(capacity) => capacity.to_string(),
+                        None => t(locale.to_string(), "unlimited"),
+                    };
+                    (
+                        "Capacity changed from {from} to {to}.",
+                        describe_capacity(from),
+                        describe_capacity(to),
+                    )
+                }
+                _ => return None,
+            };
+            Some(
+                t(locale.to_string(), template)
+                    .replace("{from}", &from)
+                    .replace("{to}", &to),
+            )
+        })
+        .collect();
+    if sentences.is_empty() {
+        return None;
+    }
+    Some(sentences.join(" "))
+}
+
+fn describe_time(locale: &str, time: Option<EventTime>) -> String {
+    match time {
+        Some(time) => format_time(time.start_time()),
+        None => t(locale.to_string(), "to be announced"),
+    }
+}
+
+fn describe_location(locale: &str, location: Location) -> String {
+    match location {
+        Location::Physical { address, .. } => address,
+        Location::Online { url } => url,
+        Location::Hybrid { address, url, .. } => format!("{address} ({url})"),
+        Location::ToBeAnnounced => t(locale.to_string(), "to be announced"),
+    }
+}
+
+/// Notifications are rendered in the zome, which doesn't know the time zone of the agent
+fn format_time(time: Timestamp) -> String {
+    let micros = time.as_micros();
+    let (year, month, day) = civil_from_days(micros.div_euclid(MICROS_PER_DAY));
+    let minutes = micros.rem_euclid(MICROS_PER_DAY) / 60_000_000;
+    format!(
+        "{year:04}-{month:02}-{day:02} {:02}:{:02} UTC",
+        minutes / 60,
+        minutes % 60
+    )
+}
+
 #[derive(Serialize, Deserialize, Debug, SerializedBytes)]
 #[serde(tag = "type")]
 pub enum GatherNotification {
@@ -224,6 +367,19 @@ pub enum GatherAction {
     EventUncancelled {
         action_hash: ActionHash,
     },
+    OccurrenceChanged {
+        action_hash: ActionHash,
+        occurrence_index: u64,
+    },
+    RsvpConfirmed {
+        action_hash: ActionHash,
+    },
+    EventHostInvitation {
+        action_hash: ActionHash,
+    },
+    ProposalHostInvitation {
+        action_hash: ActionHash,
+    },
     CommitmentCreated {
         action_hash: ActionHash,
     },
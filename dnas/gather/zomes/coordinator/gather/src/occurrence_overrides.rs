@@ -0,0 +1,313 @@
+use std::collections::BTreeMap;
+
+use gather_integrity::*;
+use hdk::prelude::*;
+
+use crate::{
+    event::get_live_event,
+    global_collections::serialize_link_tag,
+    my_events::remove_from_my_events,
+    notifications::{notify_alert, GatherAction, GatherNotification},
+};
+
+#[hdk_extern]
+pub fn create_occurrence_override(occurrence_override: OccurrenceOverride) -> ExternResult<Record> {
+    let override_hash = create_entry(&EntryTypes::OccurrenceOverride(occurrence_override.clone()))?;
+    create_link(
+        occurrence_override.event_hash.clone(),
+        override_hash.clone(),
+        LinkTypes::OccurrenceOverrides,
+        (),
+    )?;
+    let record = get(override_hash.clone(), GetOptions::default())?.ok_or(wasm_error!(
+        WasmErrorInner::Guest(String::from(
+            "Could not find the newly created OccurrenceOverride"
+        ))
+    ))?;
+
+    notify_interested_in_occurrence(
+        &occurrence_override.event_hash,
+        occurrence_override.occurrence_index,
+        override_hash,
+    )?;
+
+    Ok(record)
+}
+
+/// Undoes the change that the override made to its occurrence
+#[hdk_extern]
+pub fn delete_occurrence_override(override_hash: ActionHash) -> ExternResult<()> {
+    let record = get(override_hash.clone(), GetOptions::default())?.ok_or(wasm_error!(
+        WasmErrorInner::Guest(String::from("Could not find the OccurrenceOverride"))
+    ))?;
+    let occurrence_override: OccurrenceOverride = record
+        .entry()
+        .to_app_option()
+        .map_err(|err| wasm_error!(err))?
+        .ok_or(wasm_error!(WasmErrorInner::Guest(String::from(
+            "Malformed OccurrenceOverride"
+        ))))?;
+
+    let links = get_links(
+        occurrence_override.event_hash.clone(),
+        LinkTypes::OccurrenceOverrides,
+        None,
+    )?;
+    for link in links {
+        if link.target.into_action_hash().as_ref() == Some(&override_hash) {
+            delete_link(link.create_link_hash)?;
+        }
+    }
+    delete_entry(override_hash.clone())?;
+
+    notify_interested_in_occurrence(
+        &occurrence_override.event_hash,
+        occurrence_override.occurrence_index,
+        override_hash,
+    )
+}
+
+#[hdk_extern]
+pub fn get_occurrence_override(override_hash: ActionHash) -> ExternResult<Option<Record>> {
+    get(override_hash, GetOptions::default())
+}
+
+#[hdk_extern]
+pub fn get_occurrence_overrides(event_hash: ActionHash) -> ExternResult<Vec<Link>> {
+    get_links(event_hash, LinkTypes::OccurrenceOverrides, None)
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct OccurrenceInput {
+    pub event_hash: ActionHash,
+    pub occurrence_index: u64,
+}
+
+#[hdk_extern]
+pub fn add_myself_as_interested_in_occurrence(input: OccurrenceInput) -> ExternResult<()> {
+    let my_pub_key = agent_info()?.agent_initial_pubkey;
+
+    create_link(
+        input.event_hash.clone(),
+        my_pub_key.clone(),
+        LinkTypes::InterestedInOccurrence,
+        serialize_link_tag(OccurrenceTag {
+            occurrence_index: input.occurrence_index,
+        })?,
+    )?;
+
+    create_link(
+        my_pub_key,
+        input.event_hash.clone(),
+        LinkTypes::MyEvents,
+        (),
+    )?;
+
+    Ok(())
+}
+
+#[hdk_extern]
+pub fn remove_myself_as_interested_in_occurrence(input: OccurrenceInput) -> ExternResult<()> {
+    let my_pub_key = agent_info()?.agent_initial_pubkey;
+
+    let links = get_links(
+        input.event_hash.clone(),
+        LinkTypes::InterestedInOccurrence,
+        None,
+    )?;
+
+    let mut still_interested = false;
+    for link in links {
+        if link.target.into_agent_pub_key().as_ref() != Some(&my_pub_key) {
+            continue;
+        }
+        match deserialize_link_tag::<OccurrenceTag>(link.tag) {
+            Some(tag) if tag.occurrence_index == input.occurrence_index => {
+                delete_link(link.create_link_hash)?;
+            }
+            _ => still_interested = true,
+        }
+    }
+
+    // Keep the event in my events while I'm interested in the whole event or in other occurrences
+    let interested_in_event = get_links(input.event_hash.clone(), LinkTypes::Interested, None)?
+        .into_iter()
+        .any(|link| link.target.into_agent_pub_key().as_ref() == Some(&my_pub_key));
+    if !still_interested && !interested_in_event {
+        remove_from_my_events(input.event_hash)?;
+    }
+
+    Ok(())
+}
+
+#[hdk_extern]
+pub fn get_interested_in_occurrence(input: OccurrenceInput) -> ExternResult<Vec<Link>> {
+    let links = get_links(input.event_hash, LinkTypes::InterestedInOccurrence, None)?;
+    Ok(links
+        .into_iter()
+        .filter(|link| {
+            matches!(
+                deserialize_link_tag::<OccurrenceTag>(link.tag.clone()),
+                Some(tag) if tag.occurrence_index == input.occurrence_index
+            )
+        })
+        .collect())
+}
+
+/// An occurrence of an event with its overrides applied
+#[derive(Serialize, Deserialize, Debug)]
+pub struct EventOccurrence {
+    pub index: u64,
+    pub start_time: Timestamp,
+    pub end_time: Timestamp,
+    pub location: Location,
+    pub cancelled: bool,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct GetEventOccurrencesInput {
+    pub event_hash: ActionHash,
+    pub from: Timestamp,
+    pub to: Timestamp,
+}
+/// The occurrences of the latest revision of the event that overlap with the given window,
+/// after applying their overrides
+#[hdk_extern]
+pub fn get_event_occurrences(
+    input: GetEventOccurrencesInput,
+) -> ExternResult<Vec<EventOccurrence>> {
+    let Some(record) = get_live_event(input.event_hash.clone())? else {
+        return Ok(vec![]);
+    };
+    let Some(event) = record
+        .entry()
+        .to_app_option::<Event>()
+        .map_err(|err| wasm_error!(err))?
+    else {
+        return Ok(vec![]);
+    };
+
+    let to_event_occurrence = |occurrence: Occurrence| EventOccurrence {
+        index: occurrence.index,
+        start_time: occurrence.start_time,
+        end_time: occurrence.end_time,
+        location: event.location.clone(),
+        cancelled: false,
+    };
+    let mut occurrences: BTreeMap<u64, EventOccurrence> = event
+        .time
+        .occurrences_between(input.from, input.to)
+        .into_iter()
+        .map(|occurrence| (occurrence.index, to_event_occurrence(occurrence)))
+        .collect();
+
+    let mut changes_by_index: BTreeMap<u64, Vec<OccurrenceChange>> = BTreeMap::new();
+    for occurrence_override in get_occurrence_override_entries(&input.event_hash)? {
+        changes_by_index
+            .entry(occurrence_override.occurrence_index)
+            .or_default()
+            .push(occurrence_override.change);
+    }
+
+    for (index, changes) in changes_by_index {
+        // A rescheduled occurrence may move into the window from outside of it
+        let rescheduled = changes
+            .iter()
+            .any(|change| matches!(change, OccurrenceChange::Rescheduled { .. }));
+        let occurrence = match occurrences.remove(&index) {
+            Some(occurrence) => Some(occurrence),
+            None if rescheduled => event.time.occurrence(index).map(to_event_occurrence),
+            None => None,
+        };
+        let Some(mut occurrence) = occurrence else {
+            continue;
+        };
+
+        for change in changes {
+            match change {
+                OccurrenceChange::Cancelled => occurrence.cancelled = true,
+                OccurrenceChange::Rescheduled {
+                    start_time,
+                    end_time,
+                } => {
+                    occurrence.start_time = start_time;
+                    occurrence.end_time = end_time;
+                }
+                OccurrenceChange::Relocated { location } => occurrence.location = location,
+            }
+        }
+
+        if occurrence.start_time < input.to && occurrence.end_time > input.from {
+            occurrences.insert(index, occurrence);
+        }
+    }
+
+    let mut occurrences: Vec<EventOccurrence> = occurrences.into_values().collect();
+    occurrences.sort_by_key(|occurrence| occurrence.start_time);
+    Ok(occurrences)
+}
+
+/// The overrides of the occurrences of the event, in the order they were created
+pub(crate) fn get_occurrence_override_entries(
+    event_hash: &ActionHash,
+) -> ExternResult<Vec<OccurrenceOverride>> {
+    let mut links = get_links(event_hash.clone(), LinkTypes::OccurrenceOverrides, None)?;
+    links.sort_by_key(|link| link.timestamp);
+
+    let mut overrides = Vec::new();
+    for link in links {
+        let Some(override_hash) = link.target.into_action_hash() else {
+            continue;
+        };
+        let Some(record) = get(override_hash, GetOptions::default())? else {
+            continue;
+        };
+        if let Some(occurrence_override) = record
+            .entry()
+            .to_app_option::<OccurrenceOverride>()
+            .map_err(|err| wasm_error!(err))?
+        {
+            overrides.push(occurrence_override);
+        }
+    }
+    Ok(overrides)
+}
+
+/// Alerts the agents interested in the whole event or in the given occurrence,
+/// other than myself, that the occurrence changed
+fn notify_interested_in_occurrence(
+    event_hash: &ActionHash,
+    occurrence_index: u64,
+    override_hash: ActionHash,
+) -> ExternResult<()> {
+    let my_pub_key = agent_info()?.agent_initial_pubkey;
+
+    let mut agents: Vec<AgentPubKey> = Vec::new();
+    let interested_links = get_links(event_hash.clone(), LinkTypes::Interested, None)?
+        .into_iter()
+        .chain(get_interested_in_occurrence(OccurrenceInput {
+            event_hash: event_hash.clone(),
+            occurrence_index,
+        })?);
+    for link in interested_links {
+        if let Some(agent) = link.target.into_agent_pub_key() {
+            if agent != my_pub_key && !agents.contains(&agent) {
+                agents.push(agent);
+            }
+        }
+    }
+    if agents.is_empty() {
+        return Ok(());
+    }
+
+    notify_alert(
+        agents,
+        GatherNotification::EventAlert {
+            event_hash: event_hash.clone(),
+            action: GatherAction::OccurrenceChanged {
+                action_hash: override_hash,
+                occurrence_index,
+            },
+        },
+    )
+}
@@ -0,0 +1,267 @@
+use std::collections::BTreeSet;
+
+use gather_integrity::*;
+use hdk::prelude::*;
+
+use crate::{entry_of, global_collections::serialize_link_tag, revisions::get_revision_heads};
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct CreatePrivateEventInput {
+    pub event: Event,
+    pub invitees: Vec<AgentPubKey>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct UpdatePrivateEventInput {
+    pub original_private_event_hash: ActionHash,
+    pub previous_private_event_hash: ActionHash,
+    pub updated_event: Event,
+    pub invitees: Vec<AgentPubKey>,
+}
+
+/// The latest revision of a private event, together with its decrypted event
+#[derive(Serialize, Deserialize, Debug)]
+pub struct DecryptedPrivateEvent {
+    pub record: Record,
+    pub event: Event,
+}
+
+/// Creates an invite-only event, whose content can only be read by its hosts and the invitees
+///
+/// All of them must have published their encryption key, which happens when they first
+/// use the app
+#[hdk_extern]
+pub fn create_private_event(input: CreatePrivateEventInput) -> ExternResult<Record> {
+    let private_event = encrypt_private_event(input.event, input.invitees)?;
+    let private_event_hash = create_entry(&EntryTypes::PrivateEvent(private_event.clone()))?;
+    let record = get(private_event_hash.clone(), GetOptions::default())?.ok_or(wasm_error!(
+        WasmErrorInner::Guest(String::from(
+            "Could not find the newly created PrivateEvent"
+        ))
+    ))?;
+
+    for agent in private_event.participants() {
+        invite(&agent, &private_event_hash, &private_event_hash)?;
+    }
+
+    Ok(record)
+}
+
+/// The latest revision of the private event, which is the first of its revision heads when
+/// its hosts updated it concurrently, `None` if it doesn't exist or if we are not one of its
+/// hosts or invitees
+#[hdk_extern]
+pub fn get_private_event(
+    original_private_event_hash: ActionHash,
+) -> ExternResult<Option<DecryptedPrivateEvent>> {
+    let Some(record) = get_revision_heads(&original_private_event_hash)?
+        .into_iter()
+        .next()
+    else {
+        return Ok(None);
+    };
+    let private_event = entry_of::<PrivateEvent>(&record)?;
+    let Some(event) = decrypt_private_event(&private_event)? else {
+        return Ok(None);
+    };
+    Ok(Some(DecryptedPrivateEvent { record, event }))
+}
+
+/// Encrypts the updated event with a new shared secret, so that the agents that are no longer
+/// invited can't read it, and updates the invitations accordingly
+#[hdk_extern]
+pub fn update_private_event(input: UpdatePrivateEventInput) -> ExternResult<Record> {
+    let previous_record = get(
+        input.previous_private_event_hash.clone(),
+        GetOptions::default(),
+    )?
+    .ok_or(wasm_error!(WasmErrorInner::Guest(String::from(
+        "Could not find the previous PrivateEvent"
+    ))))?;
+    let previous_private_event = entry_of::<PrivateEvent>(&previous_record)?;
+
+    let private_event = encrypt_private_event(input.updated_event, input.invitees)?;
+    let updated_private_event_hash =
+        update_entry(input.previous_private_event_hash, &private_event)?;
+    create_link(
+        input.original_private_event_hash.clone(),
+        updated_private_event_hash.clone(),
+        LinkTypes::Updates,
+        (),
+    )?;
+    let record = get(updated_private_event_hash.clone(), GetOptions::default())?.ok_or(
+        wasm_error!(WasmErrorInner::Guest(String::from(
+            "Could not find the newly updated PrivateEvent"
+        ))),
+    )?;
+
+    let previous_participants: BTreeSet<AgentPubKey> =
+        previous_private_event.participants().into_iter().collect();
+    let participants: BTreeSet<AgentPubKey> = private_event.participants().into_iter().collect();
+    for agent in previous_participants.difference(&participants) {
+        remove_invitation(agent, &input.original_private_event_hash)?;
+    }
+    for agent in participants.difference(&previous_participants) {
+        invite(
+            agent,
+            &input.original_private_event_hash,
+            &updated_private_event_hash,
+        )?;
+    }
+
+    Ok(record)
+}
+
+/// The private events that we host or are invited to
+#[hdk_extern]
+pub fn get_my_invitations(_: ()) -> ExternResult<Vec<Link>> {
+    let my_pub_key = agent_info()?.agent_initial_pubkey;
+    get_links(my_pub_key, LinkTypes::Invitations, None)
+}
+
+/// Removes the private event from our invitations
+#[hdk_extern]
+pub fn dismiss_invitation(original_private_event_hash: ActionHash) -> ExternResult<()> {
+    let my_pub_key = agent_info()?.agent_initial_pubkey;
+    remove_invitation(&my_pub_key, &original_private_event_hash)
+}
+
+/// Publishes the X25519 key that others use to encrypt the private events they invite us to,
+/// unless it's already published
+pub fn publish_my_encryption_key() -> ExternResult<X25519PubKey> {
+    if let Some(key) = get_my_encryption_key()? {
+        return Ok(key);
+    }
+    let key = create_x25519_keypair()?;
+    let my_pub_key = agent_info()?.agent_initial_pubkey;
+    create_link(
+        my_pub_key.clone(),
+        my_pub_key,
+        LinkTypes::EncryptionKeys,
+        serialize_link_tag(EncryptionKeyTag { key: key.clone() })?,
+    )?;
+    Ok(key)
+}
+
+/// Our encryption key is looked up in our source chain, since its private key
+/// is only stored on this device
+fn get_my_encryption_key() -> ExternResult<Option<X25519PubKey>> {
+    let scoped_link_type = ScopedLinkType::try_from(LinkTypes::EncryptionKeys)?;
+    let records = query(ChainQueryFilter::new().action_type(ActionType::CreateLink))?;
+    for record in records.into_iter().rev() {
+        let Action::CreateLink(create_link) = record.action() else {
+            continue;
+        };
+        if create_link.zome_index != scoped_link_type.zome_index
+            || create_link.link_type != scoped_link_type.zome_type
+        {
+            continue;
+        }
+        if let Some(tag) = deserialize_link_tag::<EncryptionKeyTag>(create_link.tag.clone()) {
+            return Ok(Some(tag.key));
+        }
+    }
+    Ok(None)
+}
+
+fn get_encryption_key(agent: &AgentPubKey) -> ExternResult<Option<X25519PubKey>> {
+    let links = get_links(agent.clone(), LinkTypes::EncryptionKeys, None)?;
+    Ok(links
+        .into_iter()
+        .max_by_key(|link| link.timestamp)
+        .and_then(|link| deserialize_link_tag::<EncryptionKeyTag>(link.tag))
+        .map(|tag| tag.key))
+}
+
+/// Encrypts the event with a random shared secret, which is then encrypted
+/// for each of the hosts and the invitees
+fn encrypt_private_event(event: Event, invitees: Vec<AgentPubKey>) -> ExternResult<PrivateEvent> {
+    let hosts = event.hosts.clone();
+    let bytes = SerializedBytes::try_from(event).map_err(|err| wasm_error!(err))?;
+    let key_ref = x_salsa20_poly1305_shared_secret_create_random(None)?;
+    let encrypted_event =
+        x_salsa20_poly1305_encrypt(key_ref.clone(), bytes.bytes().to_vec().into())?;
+
+    let my_key = publish_my_encryption_key()?;
+    let mut private_event = PrivateEvent {
+        hosts,
+        invitees,
+        encrypted_event,
+        keys: vec![],
+    };
+    let my_pub_key = agent_info()?.agent_initial_pubkey;
+    for agent in private_event.participants() {
+        let recipient = if agent == my_pub_key {
+            my_key.clone()
+        } else {
+            get_encryption_key(&agent)?.ok_or(wasm_error!(WasmErrorInner::Guest(format!(
+                "{agent} hasn't published an encryption key yet"
+            ))))?
+        };
+        let encrypted_key = x_salsa20_poly1305_shared_secret_export(
+            my_key.clone(),
+            recipient.clone(),
+            key_ref.clone(),
+        )?;
+        private_event.keys.push(EncryptedKey {
+            agent,
+            sender: my_key.clone(),
+            recipient,
+            encrypted_key,
+        });
+    }
+    Ok(private_event)
+}
+
+/// `None` if the shared secret wasn't encrypted for us
+fn decrypt_private_event(private_event: &PrivateEvent) -> ExternResult<Option<Event>> {
+    let my_pub_key = agent_info()?.agent_initial_pubkey;
+    let Some(key) = private_event
+        .keys
+        .iter()
+        .find(|key| key.agent == my_pub_key)
+    else {
+        return Ok(None);
+    };
+    let key_ref = x_salsa20_poly1305_shared_secret_ingest(
+        key.recipient.clone(),
+        key.sender.clone(),
+        key.encrypted_key.clone(),
+        None,
+    )?;
+    let data = x_salsa20_poly1305_decrypt(key_ref, private_event.encrypted_event.clone())?.ok_or(
+        wasm_error!(WasmErrorInner::Guest(String::from(
+            "Could not decrypt the private event"
+        ))),
+    )?;
+    let event = Event::try_from(SerializedBytes::from(UnsafeBytes::from(
+        data.as_ref().to_vec(),
+    )))
+    .map_err(|err| wasm_error!(err))?;
+    Ok(Some(event))
+}
+
+fn invite(
+    agent: &AgentPubKey,
+    private_event_hash: &ActionHash,
+    revision_hash: &ActionHash,
+) -> ExternResult<()> {
+    create_link(
+        agent.clone(),
+        private_event_hash.clone(),
+        LinkTypes::Invitations,
+        serialize_link_tag(InvitationTag {
+            revision_hash: revision_hash.clone(),
+        })?,
+    )?;
+    Ok(())
+}
+
+fn remove_invitation(agent: &AgentPubKey, private_event_hash: &ActionHash) -> ExternResult<()> {
+    for link in get_links(agent.clone(), LinkTypes::Invitations, None)? {
+        if link.target.clone().into_action_hash().as_ref() == Some(private_event_hash) {
+            delete_link(link.create_link_hash)?;
+        }
+    }
+    Ok(())
+}
@@ -1,7 +1,18 @@
 use gather_integrity::*;
 use hdk::prelude::*;
 
-use crate::global_collections::all_open_proposals;
+use crate::co_hosts::invite_proposal_hosts;
+use crate::global_collections::{
+    proposal_start_times, remove_from_collection, reschedule_proposal, serialize_link_tag,
+    transition_proposal,
+};
+use crate::my_events::remove_deleted_from_my_events;
+use crate::revisions::{
+    get_all_revisions, get_proposal_revisions, get_revision_heads, GetRevisionsInput,
+};
+use crate::search::{index_proposal_for_search, remove_proposal_from_search};
+use crate::tags::{remove_proposal_tags, update_proposal_tags};
+use crate::{entry_of, get_latest_revision, get_latest_revisions, LatestRevision};
 
 #[hdk_extern]
 pub fn create_proposal(proposal: Proposal) -> ExternResult<Record> {
@@ -10,13 +21,10 @@ pub fn create_proposal(proposal: Proposal) -> ExternResult<Record> {
         WasmErrorInner::Guest(String::from("Could not find the newly created Proposal"))
     ))?;
 
-    let path = all_open_proposals();
-
-    create_link(
-        path.path_entry_hash()?,
+    transition_proposal(
         proposal_hash.clone(),
-        LinkTypes::OpenProposals,
-        (),
+        record.clone(),
+        ProposalTransition::Created,
     )?;
 
     let my_agent_pub_key = agent_info()?.agent_latest_pubkey;
@@ -26,28 +34,48 @@ pub fn create_proposal(proposal: Proposal) -> ExternResult<Record> {
         LinkTypes::MyEvents,
         (),
     )?;
+    update_proposal_tags(&proposal_hash, &record)?;
+    index_proposal_for_search(&proposal_hash, &record)?;
+    invite_proposal_hosts(&proposal_hash, &[], &proposal.hosts)?;
     Ok(record)
 }
 
+/// The latest revision of the proposal, unless it was deleted
 #[hdk_extern]
 pub fn get_latest_proposal(original_proposal_hash: ActionHash) -> ExternResult<Option<Record>> {
-    let links = get_links(original_proposal_hash.clone(), LinkTypes::Updates, None)?;
-    let latest_link = links
+    get_live_proposal(original_proposal_hash)
+}
+
+/// The latest revisions of the given proposals, in the same order, resolved all at once
+#[hdk_extern]
+pub fn get_latest_proposals(
+    original_proposal_hashes: Vec<ActionHash>,
+) -> ExternResult<Vec<Option<Record>>> {
+    Ok(get_latest_revisions(original_proposal_hashes)?
         .into_iter()
-        .max_by(|link_a, link_b| link_a.timestamp.cmp(&link_b.timestamp));
-    let latest_proposal_hash = match latest_link {
-        Some(link) => ActionHash::try_from(link.target.clone()).map_err(|err| wasm_error!(err))?,
-        None => original_proposal_hash.clone(),
-    };
-    let Some(details) =     get_details(latest_proposal_hash, GetOptions::default())? else {return Ok(None);};
-    let record = match details {
-        Details::Record(details) => Ok(details.record),
-        _ => Err(wasm_error!(WasmErrorInner::Guest(String::from(
-            "Malformed get details response"
-        )))),
-    }?;
+        .map(|latest| latest.and_then(LatestRevision::live))
+        .collect())
+}
 
-    Ok(Some(record))
+/// The latest revision of the proposal, or its deletion if one of its hosts deleted it
+#[hdk_extern]
+pub fn get_latest_proposal_revision(
+    original_proposal_hash: ActionHash,
+) -> ExternResult<Option<LatestRevision>> {
+    get_latest_revision(original_proposal_hash)
+}
+
+/// The latest revisions or the deletions of the given proposals, in the same order
+#[hdk_extern]
+pub fn get_latest_proposal_revisions(
+    original_proposal_hashes: Vec<ActionHash>,
+) -> ExternResult<Vec<Option<LatestRevision>>> {
+    get_latest_revisions(original_proposal_hashes)
+}
+
+/// The latest revision of the proposal, unless it was deleted
+pub fn get_live_proposal(original_proposal_hash: ActionHash) -> ExternResult<Option<Record>> {
+    Ok(get_latest_revision(original_proposal_hash)?.and_then(LatestRevision::live))
 }
 
 #[hdk_extern]
@@ -63,21 +91,13 @@ pub fn get_original_proposal(original_proposal_hash: ActionHash) -> ExternResult
     Ok(Some(record))
 }
 
+/// All the revisions of the proposal, from the oldest to the most recent one
 #[hdk_extern]
 pub fn get_all_proposal_revisions(original_proposal_hash: ActionHash) -> ExternResult<Vec<Record>> {
-    let Some(Details::Record(details)) = get_details(original_proposal_hash, GetOptions::default())? else {
-        return Ok(vec![]);
-    };
-
-    let mut records = vec![details.record];
-
-    for update in details.updates {
-        let mut update_records = get_all_proposal_revisions(update.action_address().clone())?;
-
-        records.append(&mut update_records);
-    }
-
-    Ok(records)
+    get_proposal_revisions(GetRevisionsInput {
+        original_hash: original_proposal_hash,
+        limit: None,
+    })
 }
 
 #[derive(Serialize, Deserialize, Debug)]
@@ -88,6 +108,68 @@ pub struct UpdateProposalInput {
 }
 #[hdk_extern]
 pub fn update_proposal(input: UpdateProposalInput) -> ExternResult<Record> {
+    update_proposal_revision(input, vec![])
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct MergeProposalRevisionsInput {
+    pub original_proposal_hash: ActionHash,
+    pub merged_proposal: Proposal,
+}
+
+/// Resolves the concurrent revisions of the proposal with an update of its latest revision
+/// that also merges all the other heads
+#[hdk_extern]
+pub fn merge_proposal_revisions(input: MergeProposalRevisionsInput) -> ExternResult<Record> {
+    let heads = get_revision_heads(&input.original_proposal_hash)?;
+    let Some((latest, other_heads)) = heads.split_first() else {
+        return Err(wasm_error!(WasmErrorInner::Guest(String::from(
+            "Could not find the proposal"
+        ))));
+    };
+    if other_heads.is_empty() {
+        return Err(wasm_error!(WasmErrorInner::Guest(String::from(
+            "The proposal has no concurrent revisions to merge"
+        ))));
+    }
+    update_proposal_revision(
+        UpdateProposalInput {
+            original_proposal_hash: input.original_proposal_hash,
+            previous_proposal_hash: latest.action_address().clone(),
+            updated_proposal: input.merged_proposal,
+        },
+        other_heads
+            .iter()
+            .map(|head| head.action_address().clone())
+            .collect(),
+    )
+}
+
+/// The revisions of the proposal that no other revision updates or merges, the first one
+/// being its latest revision
+#[hdk_extern]
+pub fn get_proposal_revision_heads(
+    original_proposal_hash: ActionHash,
+) -> ExternResult<Vec<Record>> {
+    get_revision_heads(&original_proposal_hash)
+}
+
+fn update_proposal_revision(
+    input: UpdateProposalInput,
+    merged_heads: Vec<ActionHash>,
+) -> ExternResult<Record> {
+    if let Some(LatestRevision::Deleted { .. }) =
+        get_latest_revision(input.original_proposal_hash.clone())?
+    {
+        return Err(wasm_error!(WasmErrorInner::Guest(String::from(
+            "Cannot update a deleted proposal"
+        ))));
+    }
+    let previous_record =
+        get(input.previous_proposal_hash.clone(), GetOptions::default())?.ok_or(wasm_error!(
+            WasmErrorInner::Guest(String::from("Could not find the previous proposal"))
+        ))?;
+    let previous_proposal = entry_of::<Proposal>(&previous_record)?;
     let updated_proposal_hash = update_entry(
         input.previous_proposal_hash.clone(),
         &input.updated_proposal,
@@ -96,11 +178,19 @@ pub fn update_proposal(input: UpdateProposalInput) -> ExternResult<Record> {
         input.original_proposal_hash.clone(),
         updated_proposal_hash.clone(),
         LinkTypes::Updates,
-        (),
+        serialize_link_tag(UpdatesTag { merged_heads })?,
     )?;
     let record = get(updated_proposal_hash.clone(), GetOptions::default())?.ok_or(wasm_error!(
         WasmErrorInner::Guest(String::from("Could not find the newly updated proposal"))
     ))?;
+    reschedule_proposal(input.original_proposal_hash.clone(), record.clone())?;
+    update_proposal_tags(&input.original_proposal_hash, &record)?;
+    index_proposal_for_search(&input.original_proposal_hash, &record)?;
+    invite_proposal_hosts(
+        &input.original_proposal_hash,
+        &previous_proposal.hosts,
+        &input.updated_proposal.hosts,
+    )?;
     Ok(record)
 }
 
@@ -108,3 +198,40 @@ pub fn update_proposal(input: UpdateProposalInput) -> ExternResult<Record> {
 pub fn get_events_for_proposal(proposal_hash: ActionHash) -> ExternResult<Vec<Link>> {
     get_links(proposal_hash, LinkTypes::ProposalToEvent, None)
 }
+
+/// Deletes the latest revision of the proposal, which only its hosts can do, and removes the
+/// proposal from every collection and index, leaving the links to the events created from it
+#[hdk_extern]
+pub fn delete_proposal(original_proposal_hash: ActionHash) -> ExternResult<ActionHash> {
+    let Some(LatestRevision::Live { record, .. }) =
+        get_latest_revision(original_proposal_hash.clone())?
+    else {
+        return Err(wasm_error!(WasmErrorInner::Guest(String::from(
+            "Could not find the proposal, or it was already deleted"
+        ))));
+    };
+    let start_times = proposal_start_times(&original_proposal_hash)?;
+    let mut hosts: Vec<AgentPubKey> = vec![];
+    for revision in get_all_revisions(&original_proposal_hash)? {
+        hosts.extend(entry_of::<Proposal>(&revision)?.hosts);
+    }
+
+    let deletion_hash = delete_entry(record.action_address().clone())?;
+
+    for status in [
+        ProposalStatus::Open,
+        ProposalStatus::Expired,
+        ProposalStatus::Cancelled,
+    ] {
+        remove_from_collection(
+            &original_proposal_hash,
+            status.path(),
+            status.link_type(),
+            start_times.clone(),
+        )?;
+    }
+    remove_proposal_tags(&original_proposal_hash)?;
+    remove_proposal_from_search(&original_proposal_hash)?;
+    remove_deleted_from_my_events(&original_proposal_hash, hosts)?;
+    Ok(deletion_hash)
+}
@@ -0,0 +1,373 @@
+use std::collections::BTreeSet;
+
+use gather_integrity::*;
+use hdk::prelude::*;
+
+use crate::{
+    entry_of, event::get_all_event_revisions, get_entry_for_action,
+    proposal::get_all_proposal_revisions,
+};
+
+/// The revisions of the event or proposal that are neither updated nor merged by any other
+/// revision, which are more than one when its hosts update it concurrently
+///
+/// Every agent sorts them the same way, by the timestamp of their action and then by their
+/// hash, so that every agent agrees on the first one being the latest revision
+pub(crate) fn get_revision_heads(original_hash: &ActionHash) -> ExternResult<Vec<Record>> {
+    let mut all_heads = get_all_revision_heads(vec![original_hash.clone()])?;
+    Ok(all_heads.pop().unwrap_or_default())
+}
+
+/// The revision heads of each of the given events or proposals, in the same order,
+/// fetching the revisions of all of them in a single call
+pub(crate) fn get_all_revision_heads(
+    original_hashes: Vec<ActionHash>,
+) -> ExternResult<Vec<Vec<Record>>> {
+    let mut all_revision_hashes: Vec<Vec<ActionHash>> = vec![];
+    let mut all_merged_heads: Vec<BTreeSet<ActionHash>> = vec![];
+    for original_hash in original_hashes {
+        let mut revision_hashes = vec![original_hash.clone()];
+        let mut merged_heads: BTreeSet<ActionHash> = BTreeSet::new();
+        for link in get_links(original_hash, LinkTypes::Updates, None)? {
+            if let Some(updates_tag) = deserialize_link_tag::<UpdatesTag>(link.tag) {
+                merged_heads.extend(updates_tag.merged_heads);
+            }
+            if let Some(update_hash) = link.target.into_action_hash() {
+                if !revision_hashes.contains(&update_hash) {
+                    revision_hashes.push(update_hash);
+                }
+            }
+        }
+        all_revision_hashes.push(revision_hashes);
+        all_merged_heads.push(merged_heads);
+    }
+
+    let inputs: Vec<GetInput> = all_revision_hashes
+        .iter()
+        .flatten()
+        .map(|hash| GetInput::new(hash.clone().into(), GetOptions::default()))
+        .collect();
+    let mut all_records = HDK.with(|h| h.borrow().get(inputs))?.into_iter();
+
+    let mut all_heads = vec![];
+    for (revision_hashes, mut superseded) in all_revision_hashes.into_iter().zip(all_merged_heads) {
+        let records: Vec<Option<Record>> =
+            all_records.by_ref().take(revision_hashes.len()).collect();
+        // Without its original action, the event or proposal can't be found
+        if !matches!(records.first(), Some(Some(_))) {
+            all_heads.push(vec![]);
+            continue;
+        }
+        let revisions: Vec<Record> = records.into_iter().flatten().collect();
+        for revision in &revisions {
+            if let Action::Update(update) = revision.action() {
+                superseded.insert(update.original_action_address.clone());
+            }
+        }
+
+        let mut heads: Vec<Record> = revisions
+            .into_iter()
+            .filter(|revision| !superseded.contains(revision.action_address()))
+            .collect();
+        heads.sort_by(|a, b| {
+            b.action()
+                .timestamp()
+                .cmp(&a.action().timestamp())
+                .then_with(|| b.action_address().cmp(a.action_address()))
+        });
+        all_heads.push(heads);
+    }
+    Ok(all_heads)
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct GetRevisionsInput {
+    pub original_hash: ActionHash,
+    /// Return only this many of the most recent revisions
+    pub limit: Option<usize>,
+}
+
+/// The revisions of the event, from the oldest to the most recent one
+#[hdk_extern]
+pub fn get_event_revisions(input: GetRevisionsInput) -> ExternResult<Vec<Record>> {
+    let Some(EntryTypes::Event(_)) = get_entry_for_action(&input.original_hash)? else {
+        return Err(wasm_error!(WasmErrorInner::Guest(String::from(
+            "The given hash is not the original action of an event"
+        ))));
+    };
+    get_revisions(&input.original_hash, input.limit)
+}
+
+/// The revisions of the proposal, from the oldest to the most recent one
+#[hdk_extern]
+pub fn get_proposal_revisions(input: GetRevisionsInput) -> ExternResult<Vec<Record>> {
+    let Some(EntryTypes::Proposal(_)) = get_entry_for_action(&input.original_hash)? else {
+        return Err(wasm_error!(WasmErrorInner::Guest(String::from(
+            "The given hash is not the original action of a proposal"
+        ))));
+    };
+    get_revisions(&input.original_hash, input.limit)
+}
+
+/// Every revision of the event or proposal, sorted by the time of their action
+pub(crate) fn get_all_revisions(original_hash: &ActionHash) -> ExternResult<Vec<Record>> {
+    get_revisions(original_hash, None)
+}
+
+/// The revisions of the event or proposal sorted by the time of their action, keeping only
+/// the `limit` most recent ones if given
+///
+/// Every update is linked from the original action, so the revisions are found with a single
+/// `get_links` and fetched all at once, fetching only the most recent ones when limited
+pub(crate) fn get_revisions(
+    original_hash: &ActionHash,
+    limit: Option<usize>,
+) -> ExternResult<Vec<Record>> {
+    let mut links = get_links(original_hash.clone(), LinkTypes::Updates, None)?;
+    links.sort_by_key(|link| link.timestamp);
+    // The original action comes before any of its updates
+    let mut revision_hashes = vec![original_hash.clone()];
+    for link in links {
+        if let Some(update_hash) = link.target.into_action_hash() {
+            if !revision_hashes.contains(&update_hash) {
+                revision_hashes.push(update_hash);
+            }
+        }
+    }
+    if let Some(limit) = limit {
+        revision_hashes.drain(..revision_hashes.len().saturating_sub(limit));
+    }
+
+    let inputs: Vec<GetInput> = revision_hashes
+        .into_iter()
+        .map(|hash| GetInput::new(hash.into(), GetOptions::default()))
+        .collect();
+    let mut revisions: Vec<Record> = HDK
+        .with(|h| h.borrow().get(inputs))?
+        .into_iter()
+        .flatten()
+        .collect();
+    revisions.sort_by(|a, b| {
+        a.action()
+            .timestamp()
+            .cmp(&b.action().timestamp())
+            .then_with(|| a.action_address().cmp(b.action_address()))
+    });
+    Ok(revisions)
+}
+
+/// A field of an event or a proposal that changed from one revision to the next
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(tag = "field")]
+pub enum FieldChange {
+    Title {
+        from: String,
+        to: String,
+    },
+    Description {
+        from: String,
+        to: String,
+    },
+    Image {
+        from: EntryHash,
+        to: EntryHash,
+    },
+    Location {
+        from: Location,
+        to: Location,
+    },
+    Time {
+        from: Option<EventTime>,
+        to: Option<EventTime>,
+    },
+    Cost {
+        from: Option<Cost>,
+        to: Option<Cost>,
+    },
+    Tags {
+        from: Vec<String>,
+        to: Vec<String>,
+    },
+    Hosts {
+        from: Vec<AgentPubKey>,
+        to: Vec<AgentPubKey>,
+    },
+    Capacity {
+        from: Option<u32>,
+        to: Option<u32>,
+    },
+}
+
+/// The fields that an update changed from the revision it updates
+#[derive(Serialize, Deserialize, Debug)]
+pub struct RevisionChanges {
+    pub revision_hash: ActionHash,
+    pub previous_revision_hash: ActionHash,
+    pub author: AgentPubKey,
+    pub timestamp: Timestamp,
+    pub changes: Vec<FieldChange>,
+}
+
+/// What each update of the event changed, from the oldest update to the most recent one
+#[hdk_extern]
+pub fn get_event_changelog(original_event_hash: ActionHash) -> ExternResult<Vec<RevisionChanges>> {
+    changelog::<Event>(get_all_event_revisions(original_event_hash)?)
+}
+
+/// What each update of the proposal changed, from the oldest update to the most recent one
+#[hdk_extern]
+pub fn get_proposal_changelog(
+    original_proposal_hash: ActionHash,
+) -> ExternResult<Vec<RevisionChanges>> {
+    changelog::<Proposal>(get_all_proposal_revisions(original_proposal_hash)?)
+}
+
+/// The changes of the given update of an event or a proposal, `None` if it's not an update
+pub(crate) fn get_revision_changes<T: Revisable>(
+    revision_hash: &ActionHash,
+) -> ExternResult<Option<RevisionChanges>> {
+    let Some(revision) = get(revision_hash.clone(), GetOptions::default())? else {
+        return Ok(None);
+    };
+    let Action::Update(update) = revision.action() else {
+        return Ok(None);
+    };
+    let previous_hash = update.original_action_address.clone();
+    let Some(previous) = get(previous_hash, GetOptions::default())? else {
+        return Ok(None);
+    };
+    Ok(Some(revision_changes::<T>(&previous, &revision)?))
+}
+
+pub(crate) trait Revisable: TryFrom<SerializedBytes, Error = SerializedBytesError> {
+    fn changes_from(previous: Self, revision: Self) -> Vec<FieldChange>;
+}
+
+impl Revisable for Event {
+    fn changes_from(previous: Event, event: Event) -> Vec<FieldChange> {
+        let mut changes = vec![];
+        push_if_changed(&mut changes, previous.title, event.title, |from, to| {
+            FieldChange::Title { from, to }
+        });
+        push_if_changed(
+            &mut changes,
+            previous.description,
+            event.description,
+            |from, to| FieldChange::Description { from, to },
+        );
+        push_if_changed(&mut changes, previous.image, event.image, |from, to| {
+            FieldChange::Image { from, to }
+        });
+        push_if_changed(
+            &mut changes,
+            previous.location,
+            event.location,
+            |from, to| FieldChange::Location { from, to },
+        );
+        push_if_changed(
+            &mut changes,
+            Some(previous.time),
+            Some(event.time),
+            |from, to| FieldChange::Time { from, to },
+        );
+        push_if_changed(&mut changes, previous.cost, event.cost, |from, to| {
+            FieldChange::Cost { from, to }
+        });
+        push_if_changed(&mut changes, previous.tags, event.tags, |from, to| {
+            FieldChange::Tags { from, to }
+        });
+        push_if_changed(&mut changes, previous.hosts, event.hosts, |from, to| {
+            FieldChange::Hosts { from, to }
+        });
+        push_if_changed(
+            &mut changes,
+            previous.capacity,
+            event.capacity,
+            |from, to| FieldChange::Capacity { from, to },
+        );
+        changes
+    }
+}
+
+impl Revisable for Proposal {
+    fn changes_from(previous: Proposal, proposal: Proposal) -> Vec<FieldChange> {
+        let mut changes = vec![];
+        push_if_changed(&mut changes, previous.title, proposal.title, |from, to| {
+            FieldChange::Title { from, to }
+        });
+        push_if_changed(
+            &mut changes,
+            previous.description,
+            proposal.description,
+            |from, to| FieldChange::Description { from, to },
+        );
+        push_if_changed(&mut changes, previous.image, proposal.image, |from, to| {
+            FieldChange::Image { from, to }
+        });
+        push_if_changed(
+            &mut changes,
+            previous.location,
+            proposal.location,
+            |from, to| FieldChange::Location { from, to },
+        );
+        push_if_changed(&mut changes, previous.time, proposal.time, |from, to| {
+            FieldChange::Time { from, to }
+        });
+        push_if_changed(&mut changes, previous.cost, proposal.cost, |from, to| {
+            FieldChange::Cost { from, to }
+        });
+        push_if_changed(&mut changes, previous.tags, proposal.tags, |from, to| {
+            FieldChange::Tags { from, to }
+        });
+        push_if_changed(&mut changes, previous.hosts, proposal.hosts, |from, to| {
+            FieldChange::Hosts { from, to }
+        });
+        changes
+    }
+}
+
+fn changelog<T: Revisable>(revisions: Vec<Record>) -> ExternResult<Vec<RevisionChanges>> {
+    let mut changelog = vec![];
+    for revision in &revisions {
+        let Action::Update(update) = revision.action() else {
+            continue;
+        };
+        let previous_hash = update.original_action_address.clone();
+        let previous = match revisions
+            .iter()
+            .find(|previous| previous.action_address() == &previous_hash)
+        {
+            Some(previous) => previous.clone(),
+            None => get(previous_hash, GetOptions::default())?.ok_or(wasm_error!(
+                WasmErrorInner::Guest(String::from("Could not find the previous revision"))
+            ))?,
+        };
+        changelog.push(revision_changes::<T>(&previous, revision)?);
+    }
+    changelog.sort_by_key(|changes| changes.timestamp);
+    Ok(changelog)
+}
+
+fn revision_changes<T: Revisable>(
+    previous: &Record,
+    revision: &Record,
+) -> ExternResult<RevisionChanges> {
+    Ok(RevisionChanges {
+        revision_hash: revision.action_address().clone(),
+        previous_revision_hash: previous.action_address().clone(),
+        author: revision.action().author().clone(),
+        timestamp: revision.action().timestamp(),
+        changes: T::changes_from(entry_of::<T>(previous)?, entry_of::<T>(revision)?),
+    })
+}
+
+fn push_if_changed<T: PartialEq>(
+    changes: &mut Vec<FieldChange>,
+    from: T,
+    to: T,
+    change: fn(T, T) -> FieldChange,
+) {
+    if from != to {
+        changes.push(change(from, to));
+    }
+}
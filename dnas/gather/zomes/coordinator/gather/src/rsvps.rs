@@ -0,0 +1,336 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use gather_integrity::*;
+use hdk::prelude::*;
+
+use crate::{
+    entry_of,
+    event::get_live_event,
+    global_collections::serialize_link_tag,
+    notifications::{notify_alert, GatherAction, GatherNotification},
+};
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct RsvpInput {
+    pub event_hash: ActionHash,
+    pub status: RsvpStatus,
+    #[serde(default)]
+    pub guests: u32,
+    #[serde(default)]
+    pub guest_names: Vec<String>,
+}
+
+/// The latest RSVP link of each agent to an event, grouped by status
+#[derive(Serialize, Deserialize, Debug)]
+pub struct EventRsvps {
+    /// The agents going whose seats are confirmed, in the order they RSVP'd
+    pub going: Vec<Link>,
+    /// The agents going that are waiting for seats, in the order they'll be promoted
+    pub waitlist: Vec<Link>,
+    pub maybe: Vec<Link>,
+    pub not_going: Vec<Link>,
+}
+
+/// The headcounts of an event, which count the guests of each agent
+#[derive(Serialize, Deserialize, Debug)]
+pub struct AttendanceSummary {
+    pub capacity: Option<u32>,
+    /// People with a confirmed seat
+    pub going: u32,
+    /// People waiting for a seat to be freed
+    pub waitlist: u32,
+    pub maybe: u32,
+    /// Agents that won't attend
+    pub not_going: u32,
+    /// Agents that marked themselves as interested, whether they RSVP'd or not
+    pub interested: u32,
+}
+
+/// The latest RSVP link of an agent to an event
+#[derive(Clone)]
+struct AgentRsvp {
+    agent: AgentPubKey,
+    tag: RsvpTag,
+    link: Link,
+}
+
+impl AgentRsvp {
+    fn party_size(&self) -> u32 {
+        1 + self.tag.guests
+    }
+}
+
+/// Sets our RSVP to the event, alerting the agents of the waitlist that get a seat
+/// if we free some
+///
+/// Changing the number of guests while going moves us to the end of the queue,
+/// so that parties can't grow past the agents that were already waiting, while changing
+/// only the names of the guests updates the RSVP and keeps our place
+#[hdk_extern]
+pub fn rsvp(input: RsvpInput) -> ExternResult<Record> {
+    let my_pub_key = agent_info()?.agent_initial_pubkey;
+    let links = get_links(input.event_hash.clone(), LinkTypes::EventRsvps, None)?;
+    let (my_links, others_links): (Vec<Link>, Vec<Link>) = links
+        .into_iter()
+        .partition(|link| link.target.clone().into_agent_pub_key().as_ref() == Some(&my_pub_key));
+
+    let rsvp = Rsvp {
+        event_hash: input.event_hash.clone(),
+        status: input.status,
+        guests: input.guests,
+        guest_names: input.guest_names,
+    };
+    let my_latest_rsvp = latest_rsvps(my_links.clone()).into_iter().next();
+    let mut my_previous_rsvp_hash = None;
+    if let Some(my_latest_rsvp) = &my_latest_rsvp {
+        let previous_rsvp_hash = latest_rsvp_hash(my_latest_rsvp.tag.rsvp_hash.clone())?;
+        let record = get(previous_rsvp_hash.clone(), GetOptions::default())?.ok_or(wasm_error!(
+            WasmErrorInner::Guest(String::from("Could not find the Rsvp"))
+        ))?;
+        if entry_of::<Rsvp>(&record)? == rsvp {
+            return Ok(record);
+        }
+        if my_latest_rsvp.tag.status == rsvp.status && my_latest_rsvp.tag.guests == rsvp.guests {
+            let rsvp_hash = update_entry(previous_rsvp_hash, &rsvp)?;
+            return get(rsvp_hash, GetOptions::default())?.ok_or(wasm_error!(
+                WasmErrorInner::Guest(String::from("Could not find the updated Rsvp"))
+            ));
+        }
+        my_previous_rsvp_hash = Some(previous_rsvp_hash);
+    }
+
+    let capacity = get_event_capacity(&input.event_hash)?;
+    let mut others_rsvps = latest_rsvps(others_links);
+    let mut all_rsvps = others_rsvps.clone();
+    all_rsvps.extend(my_latest_rsvp.clone());
+    sort_rsvps(&mut all_rsvps);
+    let confirmed_before = confirmed_agents(&going_parties(&all_rsvps), capacity);
+
+    let tag = RsvpTag {
+        status: rsvp.status,
+        rsvp_hash: match my_previous_rsvp_hash {
+            Some(my_previous_rsvp_hash) => update_entry(my_previous_rsvp_hash, &rsvp)?,
+            None => create_entry(&EntryTypes::Rsvp(rsvp.clone()))?,
+        },
+        guests: rsvp.guests,
+    };
+    for link in my_links {
+        delete_link(link.create_link_hash)?;
+    }
+    create_link(
+        input.event_hash.clone(),
+        my_pub_key.clone(),
+        LinkTypes::EventRsvps,
+        serialize_link_tag(tag.clone())?,
+    )?;
+    let record = get(tag.rsvp_hash.clone(), GetOptions::default())?.ok_or(wasm_error!(
+        WasmErrorInner::Guest(String::from("Could not find the newly created Rsvp"))
+    ))?;
+
+    // Our new link is the newest one, so we are at the end of the queue
+    let mut parties_after = going_parties(&others_rsvps);
+    if rsvp.status == RsvpStatus::Going {
+        parties_after.push((my_pub_key, rsvp.party_size()));
+    }
+    let confirmed_after = confirmed_agents(&parties_after, capacity);
+    others_rsvps.retain(|rsvp| {
+        confirmed_after.contains(&rsvp.agent) && !confirmed_before.contains(&rsvp.agent)
+    });
+    notify_promoted(&input.event_hash, others_rsvps)?;
+
+    Ok(record)
+}
+
+/// The latest revision of the RSVP, which can be newer than the one in the tag of its link
+/// when only the names of the guests changed
+#[hdk_extern]
+pub fn get_rsvp(rsvp_hash: ActionHash) -> ExternResult<Option<Record>> {
+    get(latest_rsvp_hash(rsvp_hash)?, GetOptions::default())
+}
+
+/// Follows the updates of the RSVP, which only its author makes one after the other
+fn latest_rsvp_hash(rsvp_hash: ActionHash) -> ExternResult<ActionHash> {
+    let mut latest_hash = rsvp_hash;
+    loop {
+        let Some(Details::Record(details)) =
+            get_details(latest_hash.clone(), GetOptions::default())?
+        else {
+            return Ok(latest_hash);
+        };
+        let Some(update) = details
+            .updates
+            .into_iter()
+            .max_by_key(|update| update.action().timestamp())
+        else {
+            return Ok(latest_hash);
+        };
+        latest_hash = update.action_address().clone();
+    }
+}
+
+/// The RSVPs to the event, with the agents going split between the confirmed ones
+/// and the waitlist according to its capacity
+#[hdk_extern]
+pub fn get_event_rsvps(event_hash: ActionHash) -> ExternResult<EventRsvps> {
+    let capacity = get_event_capacity(&event_hash)?;
+    let rsvps = latest_rsvps(get_links(event_hash, LinkTypes::EventRsvps, None)?);
+    let confirmed = confirmed_agents(&going_parties(&rsvps), capacity);
+
+    let mut event_rsvps = EventRsvps {
+        going: vec![],
+        waitlist: vec![],
+        maybe: vec![],
+        not_going: vec![],
+    };
+    for rsvp in rsvps {
+        match rsvp.tag.status {
+            RsvpStatus::Going if confirmed.contains(&rsvp.agent) => {
+                event_rsvps.going.push(rsvp.link)
+            }
+            RsvpStatus::Going => event_rsvps.waitlist.push(rsvp.link),
+            RsvpStatus::Maybe => event_rsvps.maybe.push(rsvp.link),
+            RsvpStatus::NotGoing => event_rsvps.not_going.push(rsvp.link),
+        }
+    }
+    Ok(event_rsvps)
+}
+
+/// The totals of people going, waiting, maybe going and interested in the event
+#[hdk_extern]
+pub fn get_attendance_summary(event_hash: ActionHash) -> ExternResult<AttendanceSummary> {
+    let capacity = get_event_capacity(&event_hash)?;
+    let rsvps = latest_rsvps(get_links(event_hash.clone(), LinkTypes::EventRsvps, None)?);
+    let confirmed = confirmed_agents(&going_parties(&rsvps), capacity);
+    let interested: BTreeSet<AnyLinkableHash> = get_links(event_hash, LinkTypes::Interested, None)?
+        .into_iter()
+        .map(|link| link.target)
+        .collect();
+
+    let mut summary = AttendanceSummary {
+        capacity,
+        going: 0,
+        waitlist: 0,
+        maybe: 0,
+        not_going: 0,
+        interested: interested.len() as u32,
+    };
+    for rsvp in rsvps {
+        match rsvp.tag.status {
+            RsvpStatus::Going if confirmed.contains(&rsvp.agent) => {
+                summary.going += rsvp.party_size()
+            }
+            RsvpStatus::Going => summary.waitlist += rsvp.party_size(),
+            RsvpStatus::Maybe => summary.maybe += rsvp.party_size(),
+            RsvpStatus::NotGoing => summary.not_going += 1,
+        }
+    }
+    Ok(summary)
+}
+
+/// Alerts the agents of the waitlist that got a seat because the capacity of the event grew
+pub fn notify_promoted_by_capacity(
+    event_hash: &ActionHash,
+    previous_capacity: Option<u32>,
+    capacity: Option<u32>,
+) -> ExternResult<()> {
+    let grew = match (previous_capacity, capacity) {
+        (Some(previous_capacity), Some(capacity)) => capacity > previous_capacity,
+        (Some(_), None) => true,
+        (None, _) => false,
+    };
+    if !grew {
+        return Ok(());
+    }
+    let mut rsvps = latest_rsvps(get_links(event_hash.clone(), LinkTypes::EventRsvps, None)?);
+    let parties = going_parties(&rsvps);
+    let confirmed_before = confirmed_agents(&parties, previous_capacity);
+    let confirmed_after = confirmed_agents(&parties, capacity);
+    rsvps.retain(|rsvp| {
+        confirmed_after.contains(&rsvp.agent) && !confirmed_before.contains(&rsvp.agent)
+    });
+    notify_promoted(event_hash, rsvps)
+}
+
+fn get_event_capacity(event_hash: &ActionHash) -> ExternResult<Option<u32>> {
+    let record = get_live_event(event_hash.clone())?.ok_or(wasm_error!(WasmErrorInner::Guest(
+        String::from("Could not find the Event")
+    )))?;
+    Ok(entry_of::<Event>(&record)?.capacity)
+}
+
+/// The latest RSVP link of each agent, in the order in which the agents going get a seat
+fn latest_rsvps(links: Vec<Link>) -> Vec<AgentRsvp> {
+    let mut latest: BTreeMap<AgentPubKey, (RsvpTag, Link)> = BTreeMap::new();
+    for link in links {
+        let Some(agent) = link.target.clone().into_agent_pub_key() else {
+            continue;
+        };
+        let Some(tag) = deserialize_link_tag::<RsvpTag>(link.tag.clone()) else {
+            continue;
+        };
+        let is_newer = latest
+            .get(&agent)
+            .map(|(_, existing)| {
+                (existing.timestamp, &existing.create_link_hash)
+                    < (link.timestamp, &link.create_link_hash)
+            })
+            .unwrap_or(true);
+        if is_newer {
+            latest.insert(agent, (tag, link));
+        }
+    }
+
+    let mut rsvps: Vec<AgentRsvp> = latest
+        .into_iter()
+        .map(|(agent, (tag, link))| AgentRsvp { agent, tag, link })
+        .collect();
+    sort_rsvps(&mut rsvps);
+    rsvps
+}
+
+/// Orders the RSVPs by the time of their links and then by agent, which every agent
+/// computes in the same way
+fn sort_rsvps(rsvps: &mut [AgentRsvp]) {
+    rsvps.sort_by(|a, b| (a.link.timestamp, &a.agent).cmp(&(b.link.timestamp, &b.agent)));
+}
+
+/// The agents going and the size of their parties, in order
+fn going_parties(rsvps: &[AgentRsvp]) -> Vec<(AgentPubKey, u32)> {
+    rsvps
+        .iter()
+        .filter(|rsvp| rsvp.tag.status == RsvpStatus::Going)
+        .map(|rsvp| (rsvp.agent.clone(), rsvp.party_size()))
+        .collect()
+}
+
+/// Seats the parties in order until the first one that doesn't fit, so that smaller parties
+/// can't jump the queue
+fn confirmed_agents(parties: &[(AgentPubKey, u32)], capacity: Option<u32>) -> Vec<AgentPubKey> {
+    let mut seats = 0;
+    let mut confirmed = vec![];
+    for (agent, party_size) in parties {
+        if let Some(capacity) = capacity {
+            if seats + party_size > capacity {
+                break;
+            }
+        }
+        seats += party_size;
+        confirmed.push(agent.clone());
+    }
+    confirmed
+}
+
+fn notify_promoted(event_hash: &ActionHash, promoted: Vec<AgentRsvp>) -> ExternResult<()> {
+    for rsvp in promoted {
+        notify_alert(
+            vec![rsvp.agent],
+            GatherNotification::EventAlert {
+                event_hash: event_hash.clone(),
+                action: GatherAction::RsvpConfirmed {
+                    action_hash: rsvp.tag.rsvp_hash,
+                },
+            },
+        )?;
+    }
+    Ok(())
+}
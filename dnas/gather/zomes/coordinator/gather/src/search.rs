@@ -0,0 +1,242 @@
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+use gather_integrity::*;
+use hdk::prelude::*;
+
+use crate::{
+    entry_of,
+    global_collections::{event_has_status, proposal_has_status, serialize_link_tag},
+    revisions::get_all_revisions,
+};
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct SearchEventsInput {
+    pub query: String,
+    /// Only the events that currently have this status are returned
+    pub status_filter: Option<EventStatus>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct SearchProposalsInput {
+    pub query: String,
+    /// Only the proposals that currently have this status are returned
+    pub status_filter: Option<ProposalStatus>,
+}
+
+/// The events whose title or description have words starting with every word of the query,
+/// most recently indexed first
+///
+/// Words shorter than `SEARCH_PREFIX_LENGTH` are ignored, so "repair caf" finds
+/// "The Repair Café" but "re ca" finds nothing
+#[hdk_extern]
+pub fn search_events(input: SearchEventsInput) -> ExternResult<Vec<Link>> {
+    let links = search(
+        EVENTS_SEARCH_INDEX,
+        LinkTypes::EventsSearchIndex,
+        &input.query,
+    )?;
+    let Some(status) = input.status_filter else {
+        return Ok(links);
+    };
+    let mut filtered_links = vec![];
+    for link in links {
+        let event_hash =
+            ActionHash::try_from(link.target.clone()).map_err(|err| wasm_error!(err))?;
+        if event_has_status(&event_hash, status)? {
+            filtered_links.push(link);
+        }
+    }
+    Ok(filtered_links)
+}
+
+/// The proposals whose title or description have words starting with every word of the query,
+/// most recently indexed first
+#[hdk_extern]
+pub fn search_proposals(input: SearchProposalsInput) -> ExternResult<Vec<Link>> {
+    let links = search(
+        PROPOSALS_SEARCH_INDEX,
+        LinkTypes::ProposalsSearchIndex,
+        &input.query,
+    )?;
+    let Some(status) = input.status_filter else {
+        return Ok(links);
+    };
+    let mut filtered_links = vec![];
+    for link in links {
+        let proposal_hash =
+            ActionHash::try_from(link.target.clone()).map_err(|err| wasm_error!(err))?;
+        if proposal_has_status(&proposal_hash, status)? {
+            filtered_links.push(link);
+        }
+    }
+    Ok(filtered_links)
+}
+
+/// Files the event under the words of the title and description of the given revision,
+/// and removes the links to the words that the revision no longer has
+pub fn index_event_for_search(event_hash: &ActionHash, revision: &Record) -> ExternResult<()> {
+    let mut all_words: BTreeSet<String> = BTreeSet::new();
+    for event_revision in get_all_revisions(event_hash)? {
+        let event = entry_of::<Event>(&event_revision)?;
+        all_words.extend(searchable_words(&event.title, &event.description));
+    }
+    let event = entry_of::<Event>(revision)?;
+    update_index(
+        EVENTS_SEARCH_INDEX,
+        LinkTypes::EventsSearchIndex,
+        event_hash,
+        revision.action_address(),
+        searchable_words(&event.title, &event.description),
+        all_words,
+    )
+}
+
+/// Files the proposal under the words of the title and description of the given revision,
+/// and removes the links to the words that the revision no longer has
+pub fn index_proposal_for_search(
+    proposal_hash: &ActionHash,
+    revision: &Record,
+) -> ExternResult<()> {
+    let mut all_words: BTreeSet<String> = BTreeSet::new();
+    for proposal_revision in get_all_revisions(proposal_hash)? {
+        let proposal = entry_of::<Proposal>(&proposal_revision)?;
+        all_words.extend(searchable_words(&proposal.title, &proposal.description));
+    }
+    let proposal = entry_of::<Proposal>(revision)?;
+    update_index(
+        PROPOSALS_SEARCH_INDEX,
+        LinkTypes::ProposalsSearchIndex,
+        proposal_hash,
+        revision.action_address(),
+        searchable_words(&proposal.title, &proposal.description),
+        all_words,
+    )
+}
+
+/// Removes the deleted event from the paths of the words of all its revisions
+pub fn remove_event_from_search(event_hash: &ActionHash) -> ExternResult<()> {
+    let mut all_words: BTreeSet<String> = BTreeSet::new();
+    for event_revision in get_all_revisions(event_hash)? {
+        let event = entry_of::<Event>(&event_revision)?;
+        all_words.extend(searchable_words(&event.title, &event.description));
+    }
+    update_index(
+        EVENTS_SEARCH_INDEX,
+        LinkTypes::EventsSearchIndex,
+        event_hash,
+        event_hash,
+        BTreeSet::new(),
+        all_words,
+    )
+}
+
+/// Removes the deleted proposal from the paths of the words of all its revisions
+pub fn remove_proposal_from_search(proposal_hash: &ActionHash) -> ExternResult<()> {
+    let mut all_words: BTreeSet<String> = BTreeSet::new();
+    for proposal_revision in get_all_revisions(proposal_hash)? {
+        let proposal = entry_of::<Proposal>(&proposal_revision)?;
+        all_words.extend(searchable_words(&proposal.title, &proposal.description));
+    }
+    update_index(
+        PROPOSALS_SEARCH_INDEX,
+        LinkTypes::ProposalsSearchIndex,
+        proposal_hash,
+        proposal_hash,
+        BTreeSet::new(),
+        all_words,
+    )
+}
+
+/// `previous_words` are the words of all the revisions, whose prefixes are all the paths
+/// the event or proposal may be filed under
+fn update_index(
+    index: &str,
+    link_type: LinkTypes,
+    hash: &ActionHash,
+    revision_hash: &ActionHash,
+    words: BTreeSet<String>,
+    previous_words: BTreeSet<String>,
+) -> ExternResult<()> {
+    let paths: BTreeSet<String> = previous_words
+        .iter()
+        .map(|word| search_path(index, word))
+        .collect();
+    let mut indexed_words: BTreeSet<String> = BTreeSet::new();
+    for path in paths {
+        for link in get_links(Path::from(path).path_entry_hash()?, link_type, None)? {
+            if link.target.clone().into_action_hash().as_ref() != Some(hash) {
+                continue;
+            }
+            let word = deserialize_link_tag::<SearchIndexTag>(link.tag.clone())
+                .map(|search_tag| search_tag.word);
+            match word {
+                Some(word) if words.contains(&word) && !indexed_words.contains(&word) => {
+                    indexed_words.insert(word);
+                }
+                _ => {
+                    delete_link(link.create_link_hash)?;
+                }
+            }
+        }
+    }
+
+    for word in words.difference(&indexed_words) {
+        create_link(
+            Path::from(search_path(index, word)).path_entry_hash()?,
+            hash.clone(),
+            link_type,
+            serialize_link_tag(SearchIndexTag {
+                word: word.clone(),
+                revision_hash: revision_hash.clone(),
+            })?,
+        )?;
+    }
+    Ok(())
+}
+
+/// Every word of the query must be the beginning of a word of the result
+fn search(index: &str, link_type: LinkTypes, query: &str) -> ExternResult<Vec<Link>> {
+    let query_words = search_words(query);
+    if query_words.is_empty() {
+        return Ok(vec![]);
+    }
+
+    let mut links_by_path: BTreeMap<String, Vec<Link>> = BTreeMap::new();
+    let mut results: Option<HashMap<AnyLinkableHash, Link>> = None;
+    for query_word in query_words {
+        let path = search_path(index, &query_word);
+        if !links_by_path.contains_key(&path) {
+            let links = get_links(Path::from(path.clone()).path_entry_hash()?, link_type, None)?;
+            links_by_path.insert(path.clone(), links);
+        }
+
+        let mut matches: HashMap<AnyLinkableHash, Link> = HashMap::new();
+        for link in links_by_path[&path].iter() {
+            let Some(search_tag) = deserialize_link_tag::<SearchIndexTag>(link.tag.clone()) else {
+                continue;
+            };
+            if !search_tag.word.starts_with(&query_word) {
+                continue;
+            }
+            let is_newer = matches
+                .get(&link.target)
+                .map(|existing| existing.timestamp < link.timestamp)
+                .unwrap_or(true);
+            if is_newer {
+                matches.insert(link.target.clone(), link.clone());
+            }
+        }
+
+        results = Some(match results {
+            None => matches,
+            Some(results) => results
+                .into_iter()
+                .filter(|(target, _)| matches.contains_key(target))
+                .collect(),
+        });
+    }
+
+    let mut links: Vec<Link> = results.unwrap_or_default().into_values().collect();
+    links.sort_by(|a, b| b.timestamp.cmp(&a.timestamp));
+    Ok(links)
+}
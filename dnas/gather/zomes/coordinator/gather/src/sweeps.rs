@@ -0,0 +1,155 @@
+use gather_integrity::*;
+use hdk::prelude::*;
+
+use crate::{
+    co_hosts::get_accepted_hosts,
+    event::get_live_event,
+    get_entry_for_action,
+    global_collections::{
+        event_has_status, proposal_has_status, transition_event, transition_proposal,
+    },
+    notifications::{call_assemble, notify_alert, GatherAction, GatherNotification},
+    proposal::{get_events_for_proposal, get_live_proposal},
+};
+
+/// Every 10 minutes
+pub const SWEEP_SCHEDULE: &str = "0 */10 * * * *";
+
+/// Periodically moves the events and proposals hosted by this agent to the past events and
+/// expired proposals, so that they don't linger as upcoming or open if no client marks them
+#[hdk_extern(infallible)]
+pub fn sweep_my_events(_: Option<Schedule>) -> Option<Schedule> {
+    if let Err(err) = sweep() {
+        error!("Error sweeping my events: {:?}", err);
+    }
+    Some(Schedule::Persisted(SWEEP_SCHEDULE.into()))
+}
+
+fn sweep() -> ExternResult<()> {
+    let my_pub_key = agent_info()?.agent_initial_pubkey;
+    let now = sys_time()?;
+
+    for link in get_links(my_pub_key.clone(), LinkTypes::MyEvents, None)? {
+        let Some(hash) = link.target.into_action_hash() else {
+            continue;
+        };
+        let result = match get_entry_for_action(&hash)? {
+            Some(EntryTypes::Event(_)) => sweep_event(&my_pub_key, hash.clone(), now),
+            Some(EntryTypes::Proposal(_)) => sweep_proposal(&my_pub_key, hash.clone(), now),
+            _ => Ok(()),
+        };
+        // Keep sweeping the rest even if one of them fails
+        if let Err(err) = result {
+            warn!("Error sweeping {:?}: {:?}", hash, err);
+        }
+    }
+
+    Ok(())
+}
+
+/// Only the first of the hosts that accepted sweeps each event or proposal, so that
+/// co-hosts don't race to make the same transition or send the same alerts
+fn is_sweeping_host(
+    my_pub_key: &AgentPubKey,
+    event_or_proposal_hash: &ActionHash,
+) -> ExternResult<bool> {
+    let accepted_hosts = get_accepted_hosts(event_or_proposal_hash.clone())?;
+    Ok(accepted_hosts.first() == Some(my_pub_key))
+}
+
+fn sweep_event(
+    my_pub_key: &AgentPubKey,
+    event_hash: ActionHash,
+    now: Timestamp,
+) -> ExternResult<()> {
+    let Some(latest_event) = get_live_event(event_hash.clone())? else {
+        return Ok(());
+    };
+    let Some(event) = latest_event
+        .entry()
+        .to_app_option::<Event>()
+        .map_err(|err| wasm_error!(err))?
+    else {
+        return Ok(());
+    };
+    if !is_sweeping_host(my_pub_key, &event_hash)? {
+        return Ok(());
+    }
+    let ended = matches!(event.time.end_time(), Some(end_time) if end_time <= now);
+    if !ended || !event_has_status(&event_hash, EventStatus::Upcoming)? {
+        return Ok(());
+    }
+
+    transition_event(event_hash, latest_event, EventTransition::Passed)
+}
+
+fn sweep_proposal(
+    my_pub_key: &AgentPubKey,
+    proposal_hash: ActionHash,
+    now: Timestamp,
+) -> ExternResult<()> {
+    let Some(latest_proposal) = get_live_proposal(proposal_hash.clone())? else {
+        return Ok(());
+    };
+    let Some(proposal) = latest_proposal
+        .entry()
+        .to_app_option::<Proposal>()
+        .map_err(|err| wasm_error!(err))?
+    else {
+        return Ok(());
+    };
+    if !is_sweeping_host(my_pub_key, &proposal_hash)? {
+        return Ok(());
+    }
+    let Some(call_to_action_record) =
+        get(proposal.call_to_action_hash.clone(), GetOptions::default())?
+    else {
+        return Ok(());
+    };
+    let Some(CallToAction {
+        expiration_time: Some(expiration_time),
+    }) = call_to_action_record
+        .entry()
+        .to_app_option::<CallToAction>()
+        .map_err(|err| wasm_error!(err))?
+    else {
+        return Ok(());
+    };
+    if expiration_time > now || !proposal_has_status(&proposal_hash, ProposalStatus::Open)? {
+        return Ok(());
+    }
+
+    // Proposals that met their needs or already became an event don't expire
+    let assemblies: Vec<Link> = call_assemble(
+        "get_assemblies_for_call_to_action".into(),
+        proposal.call_to_action_hash.clone(),
+    )?;
+    if !assemblies.is_empty() || !get_events_for_proposal(proposal_hash.clone())?.is_empty() {
+        return Ok(());
+    }
+
+    transition_proposal(
+        proposal_hash.clone(),
+        latest_proposal,
+        ProposalTransition::Expired,
+    )?;
+
+    let mut agents = proposal.hosts.clone();
+    for link in get_links(proposal_hash.clone(), LinkTypes::Interested, None)? {
+        if let Some(agent) = link.target.into_agent_pub_key() {
+            if !agents.contains(&agent) {
+                agents.push(agent);
+            }
+        }
+    }
+    notify_alert(
+        agents,
+        GatherNotification::ProposalAlert {
+            proposal_hash: proposal_hash.clone(),
+            action: GatherAction::ProposalExpired {
+                action_hash: proposal_hash,
+                timestamp: expiration_time.as_micros() as u64,
+            },
+        },
+    )
+}
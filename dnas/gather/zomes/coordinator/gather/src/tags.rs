@@ -0,0 +1,148 @@
+use std::collections::BTreeSet;
+
+use gather_integrity::*;
+use hdk::prelude::*;
+
+use crate::{entry_of, global_collections::serialize_link_tag, revisions::get_all_revisions};
+
+/// All the events that have the tag, which is normalized first
+#[hdk_extern]
+pub fn get_events_by_tag(tag: String) -> ExternResult<Vec<Link>> {
+    get_by_tag(EVENT_TAGS, LinkTypes::EventsByTag, &tag)
+}
+
+/// All the proposals that have the tag, which is normalized first
+#[hdk_extern]
+pub fn get_proposals_by_tag(tag: String) -> ExternResult<Vec<Link>> {
+    get_by_tag(PROPOSAL_TAGS, LinkTypes::ProposalsByTag, &tag)
+}
+
+/// Links the event from the anchors of the tags of the given revision,
+/// and unlinks it from the anchors of the tags that the revision removed
+pub fn update_event_tags(event_hash: &ActionHash, revision: &Record) -> ExternResult<()> {
+    let mut all_tags: BTreeSet<String> = BTreeSet::new();
+    for event_revision in get_all_revisions(event_hash)? {
+        all_tags.extend(entry_of::<Event>(&event_revision)?.tags);
+    }
+    let tags = entry_of::<Event>(revision)?.tags;
+    update_tags(
+        EVENT_TAGS,
+        LinkTypes::EventsByTag,
+        event_hash,
+        revision.action_address(),
+        tags,
+        all_tags,
+    )
+}
+
+/// Links the proposal from the anchors of the tags of the given revision,
+/// and unlinks it from the anchors of the tags that the revision removed
+pub fn update_proposal_tags(proposal_hash: &ActionHash, revision: &Record) -> ExternResult<()> {
+    let mut all_tags: BTreeSet<String> = BTreeSet::new();
+    for proposal_revision in get_all_revisions(proposal_hash)? {
+        all_tags.extend(entry_of::<Proposal>(&proposal_revision)?.tags);
+    }
+    let tags = entry_of::<Proposal>(revision)?.tags;
+    update_tags(
+        PROPOSAL_TAGS,
+        LinkTypes::ProposalsByTag,
+        proposal_hash,
+        revision.action_address(),
+        tags,
+        all_tags,
+    )
+}
+
+/// Unlinks the deleted event from the anchors of the tags of all its revisions
+pub fn remove_event_tags(event_hash: &ActionHash) -> ExternResult<()> {
+    let mut all_tags: BTreeSet<String> = BTreeSet::new();
+    for event_revision in get_all_revisions(event_hash)? {
+        all_tags.extend(entry_of::<Event>(&event_revision)?.tags);
+    }
+    update_tags(
+        EVENT_TAGS,
+        LinkTypes::EventsByTag,
+        event_hash,
+        event_hash,
+        vec![],
+        all_tags,
+    )
+}
+
+/// Unlinks the deleted proposal from the anchors of the tags of all its revisions
+pub fn remove_proposal_tags(proposal_hash: &ActionHash) -> ExternResult<()> {
+    let mut all_tags: BTreeSet<String> = BTreeSet::new();
+    for proposal_revision in get_all_revisions(proposal_hash)? {
+        all_tags.extend(entry_of::<Proposal>(&proposal_revision)?.tags);
+    }
+    update_tags(
+        PROPOSAL_TAGS,
+        LinkTypes::ProposalsByTag,
+        proposal_hash,
+        proposal_hash,
+        vec![],
+        all_tags,
+    )
+}
+
+/// `previous_tags` are the tags of all the revisions, which are all the anchors
+/// the event or proposal may be linked from
+fn update_tags(
+    tags_root: &str,
+    link_type: LinkTypes,
+    hash: &ActionHash,
+    revision_hash: &ActionHash,
+    tags: Vec<String>,
+    previous_tags: BTreeSet<String>,
+) -> ExternResult<()> {
+    let mut linked_tags: BTreeSet<String> = BTreeSet::new();
+    for tag in previous_tags {
+        let links = get_links(
+            Path::from(tag_path(tags_root, &tag)).path_entry_hash()?,
+            link_type,
+            None,
+        )?;
+        for link in links {
+            if link.target.clone().into_action_hash().as_ref() != Some(hash) {
+                continue;
+            }
+            if tags.contains(&tag) && !linked_tags.contains(&tag) {
+                linked_tags.insert(tag.clone());
+            } else {
+                delete_link(link.create_link_hash)?;
+            }
+        }
+    }
+
+    for tag in tags {
+        if linked_tags.contains(&tag) {
+            continue;
+        }
+        let path = Path::from(tag_path(tags_root, &tag)).typed(LinkTypes::TagIndex)?;
+        path.ensure()?;
+        create_link(
+            path.path_entry_hash()?,
+            hash.clone(),
+            link_type,
+            serialize_link_tag(TaggedTag {
+                tag,
+                revision_hash: revision_hash.clone(),
+            })?,
+        )?;
+    }
+    Ok(())
+}
+
+fn get_by_tag(tags_root: &str, link_type: LinkTypes, tag: &str) -> ExternResult<Vec<Link>> {
+    let tag = normalize_tag(tag);
+    if tag.is_empty() {
+        return Ok(vec![]);
+    }
+    let mut links = get_links(
+        Path::from(tag_path(tags_root, &tag)).path_entry_hash()?,
+        link_type,
+        None,
+    )?;
+    links.sort_by(|a, b| b.timestamp.cmp(&a.timestamp));
+    Ok(links)
+}
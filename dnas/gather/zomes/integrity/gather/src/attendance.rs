@@ -0,0 +1,211 @@
+use std::time::Duration;
+
+use hdi::prelude::*;
+
+use crate::{
+    deserialize_link_tag, follows_deletion_of, must_get_original_action_hash,
+    must_get_original_app_entry, must_get_valid_app_entry, validate_host_has_accepted, EntryTypes,
+};
+
+/// How long the check-in codes issued by the hosts can be used for
+pub const CHECK_IN_CODE_VALIDITY: Duration = Duration::from_secs(10 * 60);
+
+/// What the host signs when issuing a check-in code
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct CheckInCodePayload {
+    pub event_hash: ActionHash,
+    pub issued_at: Timestamp,
+    pub expires_at: Timestamp,
+}
+
+/// A short-lived code that a host shows at the event, so that attendees can check themselves in
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct CheckInCode {
+    pub host: AgentPubKey,
+    /// The revision of the event that has the host among its hosts
+    pub revision_hash: ActionHash,
+    pub issued_at: Timestamp,
+    pub expires_at: Timestamp,
+    pub signature: Signature,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(tag = "type")]
+pub enum CheckIn {
+    /// One of the hosts of the given revision of the event marked the attendee as present
+    ByHost { revision_hash: ActionHash },
+    /// The attendee checked in with a code issued by one of the hosts
+    WithCode { code: CheckInCode },
+}
+
+/// The record that an agent attended an event
+#[hdk_entry_helper]
+#[derive(Clone)]
+pub struct Attendance {
+    pub event_hash: ActionHash,
+    pub attendee: AgentPubKey,
+    pub check_in: CheckIn,
+}
+
+/// The tag of the links from an event to its attendees
+#[derive(Serialize, Deserialize, Debug, Clone, SerializedBytes)]
+pub struct AttendanceTag {
+    pub attendance_hash: ActionHash,
+}
+
+pub fn validate_create_attendance(
+    action: EntryCreationAction,
+    attendance: Attendance,
+) -> ExternResult<ValidateCallbackResult> {
+    let Some(EntryTypes::Event(_)) = must_get_original_app_entry(attendance.event_hash.clone())?
+    else {
+        return Ok(ValidateCallbackResult::Invalid(String::from(
+            "An attendance must be for the original action of an event",
+        )));
+    };
+
+    let (host, revision_hash) = match &attendance.check_in {
+        CheckIn::ByHost { revision_hash } => (action.author().clone(), revision_hash.clone()),
+        CheckIn::WithCode { code } => {
+            if action.author() != &attendance.attendee {
+                return Ok(ValidateCallbackResult::Invalid(String::from(
+                    "Agents can only check themselves in with a code",
+                )));
+            }
+            let result = validate_check_in_code(&attendance.event_hash, code, *action.timestamp())?;
+            let ValidateCallbackResult::Valid = result else {
+                return Ok(result);
+            };
+            (code.host.clone(), code.revision_hash.clone())
+        }
+    };
+
+    if must_get_original_action_hash(revision_hash.clone())? != Some(attendance.event_hash) {
+        return Ok(ValidateCallbackResult::Invalid(String::from(
+            "The revision of a check-in must be a revision of its event",
+        )));
+    }
+    let Some(EntryTypes::Event(revision)) = must_get_valid_app_entry(revision_hash)? else {
+        return Ok(ValidateCallbackResult::Invalid(String::from(
+            "The revision of a check-in must be an event",
+        )));
+    };
+    if !revision.hosts.contains(&host) {
+        return Ok(ValidateCallbackResult::Invalid(String::from(
+            "Only the hosts of an event can check attendees in",
+        )));
+    }
+    validate_host_has_accepted(attendance.event_hash, &host)
+}
+
+/// The code must be signed by its host and be used while it's valid
+fn validate_check_in_code(
+    event_hash: &ActionHash,
+    code: &CheckInCode,
+    used_at: Timestamp,
+) -> ExternResult<ValidateCallbackResult> {
+    let validity = code
+        .expires_at
+        .as_micros()
+        .checked_sub(code.issued_at.as_micros());
+    let max_validity = CHECK_IN_CODE_VALIDITY.as_micros() as i64;
+    if !matches!(validity, Some(validity) if validity > 0 && validity <= max_validity) {
+        return Ok(ValidateCallbackResult::Invalid(format!(
+            "Check-in codes can't be valid for more than {} minutes",
+            CHECK_IN_CODE_VALIDITY.as_secs() / 60
+        )));
+    }
+    if used_at < code.issued_at || used_at >= code.expires_at {
+        return Ok(ValidateCallbackResult::Invalid(String::from(
+            "The check-in code has expired",
+        )));
+    }
+    let payload = CheckInCodePayload {
+        event_hash: event_hash.clone(),
+        issued_at: code.issued_at,
+        expires_at: code.expires_at,
+    };
+    if !verify_signature(code.host.clone(), code.signature.clone(), payload)? {
+        return Ok(ValidateCallbackResult::Invalid(String::from(
+            "The check-in code must be signed by its host",
+        )));
+    }
+    Ok(ValidateCallbackResult::Valid)
+}
+
+pub fn validate_update_attendance(
+    _action: Update,
+    _attendance: Attendance,
+    _original_action: EntryCreationAction,
+    _original_attendance: Attendance,
+) -> ExternResult<ValidateCallbackResult> {
+    Ok(ValidateCallbackResult::Invalid(String::from(
+        "Attendances can't be updated",
+    )))
+}
+
+pub fn validate_delete_attendance(
+    action: Delete,
+    original_action: EntryCreationAction,
+    _original_attendance: Attendance,
+) -> ExternResult<ValidateCallbackResult> {
+    if &action.author != original_action.author() {
+        return Ok(ValidateCallbackResult::Invalid(String::from(
+            "Only the author of an attendance can delete it",
+        )));
+    }
+    Ok(ValidateCallbackResult::Valid)
+}
+
+/// The link must be from the event to the attendee of the attendance in its tag,
+/// and be created by whoever checked them in
+pub fn validate_create_link_event_attendees(
+    action: CreateLink,
+    base_address: AnyLinkableHash,
+    target_address: AnyLinkableHash,
+    tag: LinkTag,
+) -> ExternResult<ValidateCallbackResult> {
+    let Some(attendance_tag) = deserialize_link_tag::<AttendanceTag>(tag) else {
+        return Ok(ValidateCallbackResult::Invalid(String::from(
+            "The tag of an EventAttendees link must be an AttendanceTag",
+        )));
+    };
+    let record = must_get_valid_record(attendance_tag.attendance_hash.clone())?;
+    if record.action().author() != &action.author {
+        return Ok(ValidateCallbackResult::Invalid(String::from(
+            "Only the author of an attendance can link it",
+        )));
+    }
+    let Some(EntryTypes::Attendance(attendance)) =
+        must_get_valid_app_entry(attendance_tag.attendance_hash)?
+    else {
+        return Ok(ValidateCallbackResult::Invalid(String::from(
+            "The tag of an EventAttendees link must reference an attendance",
+        )));
+    };
+    if base_address != AnyLinkableHash::from(attendance.event_hash)
+        || target_address != AnyLinkableHash::from(attendance.attendee)
+    {
+        return Ok(ValidateCallbackResult::Invalid(String::from(
+            "An EventAttendees link must go from the event to the attendee of its attendance",
+        )));
+    }
+    Ok(ValidateCallbackResult::Valid)
+}
+
+pub fn validate_delete_link_event_attendees(
+    action: DeleteLink,
+    original_action: CreateLink,
+    _base: AnyLinkableHash,
+    _target: AnyLinkableHash,
+    _tag: LinkTag,
+) -> ExternResult<ValidateCallbackResult> {
+    if action.author != original_action.author
+        && !follows_deletion_of(&action, &original_action.base_address)?
+    {
+        return Ok(ValidateCallbackResult::Invalid(String::from(
+            "Only the author of an attendance can remove it",
+        )));
+    }
+    Ok(ValidateCallbackResult::Valid)
+}
@@ -0,0 +1,174 @@
+use hdi::prelude::*;
+
+use crate::{
+    follows_deletion_of, must_get_original_action_hash, must_get_original_app_entry,
+    must_get_valid_app_entry, EntryTypes, Event,
+};
+
+/// The consent of an agent to host an event or a proposal that lists them as one of its hosts
+///
+/// Its entry hash only depends on the event or proposal and the host, so validation can
+/// look it up without any link
+#[hdk_entry_helper]
+#[derive(Clone)]
+pub struct HostAcceptance {
+    pub event_or_proposal_hash: ActionHash,
+    pub host: AgentPubKey,
+}
+
+/// The creator of an event or a proposal hosts it implicitly, while the rest of its hosts
+/// must have accepted to host it
+///
+/// The hosts of an event created from a proposal accept to host it by accepting to host
+/// the proposal, and the absence of an acceptance can't be proven, so validation waits
+/// until it can be found
+pub fn validate_host_has_accepted(
+    revision_hash: ActionHash,
+    host: &AgentPubKey,
+) -> ExternResult<ValidateCallbackResult> {
+    let Some(original_hash) = must_get_original_action_hash(revision_hash)? else {
+        return Ok(ValidateCallbackResult::Invalid(String::from(
+            "Hosts can only act on revisions of an event or a proposal",
+        )));
+    };
+    if must_get_action(original_hash.clone())?.action().author() == host {
+        return Ok(ValidateCallbackResult::Valid);
+    }
+    let accepted_hash = match must_get_valid_app_entry(original_hash.clone())? {
+        Some(EntryTypes::Event(Event {
+            from_proposal: Some(from_proposal),
+            ..
+        })) => from_proposal.proposal_hash,
+        _ => original_hash,
+    };
+    if must_get_action(accepted_hash.clone())?.action().author() == host {
+        return Ok(ValidateCallbackResult::Valid);
+    }
+    let acceptance_hash = hash_entry(HostAcceptance {
+        event_or_proposal_hash: accepted_hash,
+        host: host.clone(),
+    })?;
+    must_get_entry(acceptance_hash)?;
+    Ok(ValidateCallbackResult::Valid)
+}
+
+/// The author acts as one of the hosts of the given revision of an event or a proposal,
+/// which requires them to be listed among its hosts and to have accepted to host it
+pub fn validate_acting_host(
+    revision_hash: ActionHash,
+    hosts: &[AgentPubKey],
+    author: &AgentPubKey,
+) -> ExternResult<ValidateCallbackResult> {
+    if !hosts.contains(author) {
+        return Ok(ValidateCallbackResult::Invalid(String::from(
+            "The author must be one of the hosts",
+        )));
+    }
+    validate_host_has_accepted(revision_hash, author)
+}
+
+/// The author files the given revision of an event or a proposal in an index, which its
+/// acting hosts can do, and so can the author of the revision itself, who isn't a host when
+/// they created the event by fulfilling the needs of a proposal
+pub fn validate_author_can_index(
+    revision_hash: ActionHash,
+    hosts: &[AgentPubKey],
+    author: &AgentPubKey,
+) -> ExternResult<ValidateCallbackResult> {
+    if must_get_action(revision_hash.clone())?.action().author() == author {
+        return Ok(ValidateCallbackResult::Valid);
+    }
+    validate_acting_host(revision_hash, hosts, author)
+}
+
+pub fn validate_create_host_acceptance(
+    action: EntryCreationAction,
+    host_acceptance: HostAcceptance,
+) -> ExternResult<ValidateCallbackResult> {
+    if action.author() != &host_acceptance.host {
+        return Ok(ValidateCallbackResult::Invalid(String::from(
+            "Agents can only accept to host for themselves",
+        )));
+    }
+    match must_get_original_app_entry(host_acceptance.event_or_proposal_hash)? {
+        Some(EntryTypes::Event(_))
+        | Some(EntryTypes::Proposal(_))
+        | Some(EntryTypes::PrivateEvent(_)) => Ok(ValidateCallbackResult::Valid),
+        _ => Ok(ValidateCallbackResult::Invalid(String::from(
+            "Agents can only accept to host the original action of an event or a proposal",
+        ))),
+    }
+}
+
+pub fn validate_update_host_acceptance(
+    _action: Update,
+    _host_acceptance: HostAcceptance,
+    _original_action: EntryCreationAction,
+    _original_host_acceptance: HostAcceptance,
+) -> ExternResult<ValidateCallbackResult> {
+    Ok(ValidateCallbackResult::Invalid(String::from(
+        "Host acceptances can't be updated",
+    )))
+}
+
+pub fn validate_delete_host_acceptance(
+    _action: Delete,
+    _original_action: EntryCreationAction,
+    _original_host_acceptance: HostAcceptance,
+) -> ExternResult<ValidateCallbackResult> {
+    Ok(ValidateCallbackResult::Invalid(String::from(
+        "Host acceptances can't be deleted",
+    )))
+}
+
+/// Links go from the hosts that have accepted to host the event to the event
+///
+/// Hosts link the event to themselves when they accept to host it, and its creator links it
+/// to the hosts that had already accepted
+pub fn validate_create_link_hosted_events(
+    action: CreateLink,
+    base_address: AnyLinkableHash,
+    target_address: AnyLinkableHash,
+    _tag: LinkTag,
+) -> ExternResult<ValidateCallbackResult> {
+    let Some(host) = base_address.into_agent_pub_key() else {
+        return Ok(ValidateCallbackResult::Invalid(String::from(
+            "The base of a HostedEvents link must be an agent",
+        )));
+    };
+    let Some(event_hash) = target_address.into_action_hash() else {
+        return Ok(ValidateCallbackResult::Invalid(String::from(
+            "The target of a HostedEvents link must be an action hash",
+        )));
+    };
+    let Some(EntryTypes::Event(_)) = must_get_original_app_entry(event_hash.clone())? else {
+        return Ok(ValidateCallbackResult::Invalid(String::from(
+            "The target of a HostedEvents link must be the original action of an event",
+        )));
+    };
+    if action.author != host
+        && must_get_action(event_hash.clone())?.action().author() != &action.author
+    {
+        return Ok(ValidateCallbackResult::Invalid(String::from(
+            "Only the host or the creator of the event can create a HostedEvents link",
+        )));
+    }
+    validate_host_has_accepted(event_hash, &host)
+}
+
+pub fn validate_delete_link_hosted_events(
+    action: DeleteLink,
+    original_action: CreateLink,
+    _base: AnyLinkableHash,
+    _target: AnyLinkableHash,
+    _tag: LinkTag,
+) -> ExternResult<ValidateCallbackResult> {
+    if action.author != original_action.author
+        && !follows_deletion_of(&action, &original_action.target_address)?
+    {
+        return Ok(ValidateCallbackResult::Invalid(String::from(
+            "Only the author of a HostedEvents link can delete it",
+        )));
+    }
+    Ok(ValidateCallbackResult::Valid)
+}
@@ -0,0 +1,272 @@
+use std::collections::HashSet;
+use std::fmt;
+
+use hdi::prelude::*;
+use serde::Deserializer;
+
+use crate::validate_not_empty;
+
+/// An amount of money in the minor unit of its currency, like cents for EUR
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct Price {
+    pub amount: u64,
+    /// ISO 4217 code of the currency, like "EUR"
+    pub currency: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct CostTier {
+    pub name: String,
+    pub price: Price,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(tag = "type")]
+pub enum Cost {
+    Free,
+    Fixed {
+        price: Price,
+    },
+    /// Amounts in the minor unit of the currency
+    PayWhatYouCan {
+        currency: String,
+        suggested_min: Option<u64>,
+        suggested_max: Option<u64>,
+    },
+    Tiered {
+        tiers: Vec<CostTier>,
+    },
+    /// A free-text cost from before costs were structured, which couldn't be parsed
+    Other {
+        description: String,
+    },
+}
+
+impl Cost {
+    pub fn is_free(&self) -> bool {
+        matches!(self, Cost::Free)
+    }
+}
+
+/// The number of decimals of the amounts in the given currency, 2 for most of them
+pub fn currency_decimals(currency: &str) -> u32 {
+    match currency {
+        "BIF" | "CLP" | "DJF" | "GNF" | "ISK" | "JPY" | "KMF" | "KRW" | "PYG" | "RWF" | "UGX"
+        | "UYI" | "VND" | "VUV" | "XAF" | "XOF" | "XPF" => 0,
+        "BHD" | "IQD" | "JOD" | "KWD" | "LYD" | "OMR" | "TND" => 3,
+        _ => 2,
+    }
+}
+
+impl fmt::Display for Price {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let decimals = currency_decimals(&self.currency);
+        if decimals == 0 {
+            return write!(f, "{} {}", self.amount, self.currency);
+        }
+        let unit = 10u64.pow(decimals);
+        write!(
+            f,
+            "{}.{:0width$} {}",
+            self.amount / unit,
+            self.amount % unit,
+            self.currency,
+            width = decimals as usize
+        )
+    }
+}
+
+impl fmt::Display for Cost {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Cost::Free => write!(f, "Free"),
+            Cost::Fixed { price } => write!(f, "{price}"),
+            Cost::PayWhatYouCan {
+                currency,
+                suggested_min,
+                suggested_max,
+            } => {
+                let price = |amount: u64| Price {
+                    amount,
+                    currency: currency.clone(),
+                };
+                match (suggested_min, suggested_max) {
+                    (Some(min), Some(max)) => {
+                        write!(
+                            f,
+                            "Pay what you can, from {} to {}",
+                            price(*min),
+                            price(*max)
+                        )
+                    }
+                    (Some(min), None) => write!(f, "Pay what you can, from {}", price(*min)),
+                    (None, Some(max)) => write!(f, "Pay what you can, up to {}", price(*max)),
+                    (None, None) => write!(f, "Pay what you can, in {currency}"),
+                }
+            }
+            Cost::Tiered { tiers } => {
+                let tiers: Vec<String> = tiers
+                    .iter()
+                    .map(|tier| format!("{}: {}", tier.name, tier.price))
+                    .collect();
+                write!(f, "{}", tiers.join(", "))
+            }
+            Cost::Other { description } => write!(f, "{description}"),
+        }
+    }
+}
+
+/// Before `Cost` existed, events and proposals stored their cost as an optional free-text string
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum CostOrLegacy {
+    Cost(Cost),
+    Legacy(Option<String>),
+}
+
+/// Deserializes an optional `Cost`, migrating the legacy string form on read with
+/// `parse_legacy_cost`, and treating empty strings as no cost
+pub fn deserialize_cost<'de, D>(deserializer: D) -> Result<Option<Cost>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    Ok(match CostOrLegacy::deserialize(deserializer)? {
+        CostOrLegacy::Cost(cost) => Some(cost),
+        CostOrLegacy::Legacy(Some(text)) if !text.trim().is_empty() => {
+            Some(parse_legacy_cost(&text))
+        }
+        CostOrLegacy::Legacy(_) => None,
+    })
+}
+
+/// Recognizes the most common ways in which costs were written as free text,
+/// like "Free", "10 EUR", "EUR 10.50" or "5€", and keeps any other text as `Cost::Other`
+pub fn parse_legacy_cost(text: &str) -> Cost {
+    let text = text.trim();
+    if ["free", "gratis", "0"].contains(&text.to_lowercase().as_str()) {
+        return Cost::Free;
+    }
+    match parse_legacy_price(text) {
+        Some(price) if price.amount == 0 => Cost::Free,
+        Some(price) => Cost::Fixed { price },
+        None => Cost::Other {
+            description: text.to_string(),
+        },
+    }
+}
+
+fn parse_legacy_price(text: &str) -> Option<Price> {
+    for (symbol, currency) in [("€", "EUR"), ("$", "USD"), ("£", "GBP")] {
+        if let Some(amount) = text.strip_prefix(symbol).or(text.strip_suffix(symbol)) {
+            return Some(Price {
+                amount: parse_amount(amount.trim(), currency_decimals(currency))?,
+                currency: currency.to_string(),
+            });
+        }
+    }
+
+    let parts: Vec<&str> = text.split_whitespace().collect();
+    let [first, second] = parts.as_slice() else {
+        return None;
+    };
+    let (amount, currency) = if is_currency_code(&first.to_uppercase()) {
+        (second, first.to_uppercase())
+    } else {
+        (first, second.to_uppercase())
+    };
+    if !is_currency_code(&currency) {
+        return None;
+    }
+    Some(Price {
+        amount: parse_amount(amount, currency_decimals(&currency))?,
+        currency,
+    })
+}
+
+/// Parses a decimal amount like "10", "10.5" or "10,50" into the minor unit of its currency
+fn parse_amount(text: &str, decimals: u32) -> Option<u64> {
+    let text = text.replace(',', ".");
+    let (whole, fraction) = text.split_once('.').unwrap_or((&text, ""));
+    let is_number = |s: &str| s.chars().all(|c| c.is_ascii_digit());
+    if whole.is_empty() || !is_number(whole) || !is_number(fraction) {
+        return None;
+    }
+    if fraction.len() > decimals as usize {
+        return None;
+    }
+    let unit = 10u64.pow(decimals);
+    let fraction = if fraction.is_empty() {
+        0
+    } else {
+        fraction.parse::<u64>().ok()? * 10u64.pow(decimals - fraction.len() as u32)
+    };
+    whole
+        .parse::<u64>()
+        .ok()?
+        .checked_mul(unit)?
+        .checked_add(fraction)
+}
+
+fn is_currency_code(currency: &str) -> bool {
+    currency.len() == 3 && currency.chars().all(|c| c.is_ascii_uppercase())
+}
+
+fn validate_currency(currency: &str) -> ValidateCallbackResult {
+    if !is_currency_code(currency) {
+        return ValidateCallbackResult::Invalid(format!(
+            "\"{currency}\" is not an ISO 4217 currency code"
+        ));
+    }
+    ValidateCallbackResult::Valid
+}
+
+pub fn validate_cost(cost: &Cost) -> ValidateCallbackResult {
+    match cost {
+        Cost::Free => ValidateCallbackResult::Valid,
+        Cost::Fixed { price } => {
+            if price.amount == 0 {
+                return ValidateCallbackResult::Invalid(String::from(
+                    "A fixed cost must be greater than zero, otherwise the event is free",
+                ));
+            }
+            validate_currency(&price.currency)
+        }
+        Cost::PayWhatYouCan {
+            currency,
+            suggested_min,
+            suggested_max,
+        } => {
+            if matches!((suggested_min, suggested_max), (Some(min), Some(max)) if min > max) {
+                return ValidateCallbackResult::Invalid(String::from(
+                    "The suggested minimum can't be greater than the suggested maximum",
+                ));
+            }
+            validate_currency(currency)
+        }
+        Cost::Tiered { tiers } => {
+            if tiers.is_empty() {
+                return ValidateCallbackResult::Invalid(String::from(
+                    "A tiered cost must have at least one tier",
+                ));
+            }
+            let names: HashSet<&str> = tiers.iter().map(|tier| tier.name.trim()).collect();
+            if names.len() != tiers.len() {
+                return ValidateCallbackResult::Invalid(String::from(
+                    "The tiers of a cost can't have the same name",
+                ));
+            }
+            for tier in tiers {
+                let result = validate_not_empty("name of the tier", &tier.name);
+                let ValidateCallbackResult::Valid = result else {
+                    return result;
+                };
+                let result = validate_currency(&tier.price.currency);
+                let ValidateCallbackResult::Valid = result else {
+                    return result;
+                };
+            }
+            ValidateCallbackResult::Valid
+        }
+        Cost::Other { description } => validate_not_empty("cost", description),
+    }
+}
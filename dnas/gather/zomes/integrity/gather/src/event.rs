@@ -2,19 +2,41 @@ use std::time::Duration;
 
 use hdi::prelude::*;
 
-#[derive(Serialize, Deserialize, Debug, Clone)]
+use crate::{
+    deserialize_cost, deserialize_location, must_get_valid_app_entry, recurrence_start_times,
+    validate_author_is_host, validate_cost, validate_host_has_accepted, validate_hosts,
+    validate_location, validate_not_empty, validate_recurrence, validate_references, validate_tags,
+    Cost, EntryTypes, Location, Recurrence, MAX_OCCURRENCES,
+};
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
 #[serde(tag = "type")]
 pub enum EventTime {
     Periodic {
         start_time: Timestamp,
         event_duration: Duration,
         period_duration: Duration,
+        /// Misspelled like in the first version of the entry, to keep the same serialization
         ocurrences: Option<u64>,
     },
     Unique {
         start_time: Timestamp,
         end_time: Timestamp,
     },
+    Recurring {
+        start_time: Timestamp,
+        event_duration: Duration,
+        recurrence: Recurrence,
+    },
+}
+
+/// A single occurrence of an event
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct Occurrence {
+    /// Position of the occurrence among all the occurrences of the event, starting at 0
+    pub index: u64,
+    pub start_time: Timestamp,
+    pub end_time: Timestamp,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
@@ -30,38 +52,352 @@ pub struct Event {
     pub title: String,
     pub description: String,
     pub image: EntryHash,
-    pub location: String,
+    #[serde(deserialize_with = "deserialize_location")]
+    pub location: Location,
     pub time: EventTime,
-    pub cost: Option<String>,
+    #[serde(default, deserialize_with = "deserialize_cost")]
+    pub cost: Option<Cost>,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    /// Maximum number of confirmed attendees, guests included, the rest of the agents going
+    /// are waitlisted
+    ///
+    /// Validation can't see all the RSVPs to the event, so every agent derives who's confirmed
+    /// in the same way: by ordering the `Going` RSVPs by the time of their links and then by agent,
+    /// and seating them in that order until the first party that doesn't fit
+    #[serde(default)]
+    pub capacity: Option<u32>,
     pub call_to_action_hash: ActionHash,
     pub from_proposal: Option<FromProposal>,
 }
 
-pub fn validate_create_link_events_by_author(
-    _action: CreateLink,
-    _base_address: AnyLinkableHash,
-    target_address: AnyLinkableHash,
-    _tag: LinkTag,
+impl EventTime {
+    /// The time at which the first occurrence of the event starts
+    pub fn start_time(&self) -> Timestamp {
+        match self {
+            EventTime::Unique { start_time, .. } => *start_time,
+            EventTime::Periodic { start_time, .. } => *start_time,
+            EventTime::Recurring { start_time, .. } => *start_time,
+        }
+    }
+
+    /// The time at which the last occurrence of the event ends,
+    /// or `None` if the event repeats forever
+    pub fn end_time(&self) -> Option<Timestamp> {
+        match self {
+            EventTime::Unique { end_time, .. } => Some(*end_time),
+            EventTime::Periodic {
+                start_time,
+                event_duration,
+                period_duration,
+                ocurrences: occurrences,
+            } => {
+                let occurrences = (*occurrences)?;
+                let last_start = duration_micros(period_duration)?
+                    .checked_mul(occurrences.saturating_sub(1) as i64)?
+                    .checked_add(start_time.as_micros())?;
+                let end = last_start.checked_add(duration_micros(event_duration)?)?;
+                Some(Timestamp::from_micros(end))
+            }
+            EventTime::Recurring {
+                start_time,
+                event_duration,
+                recurrence,
+            } => {
+                if recurrence.count.is_none() && recurrence.until.is_none() {
+                    return None;
+                }
+                let start_times =
+                    recurrence_start_times(*start_time, recurrence, *start_time, Timestamp::MAX);
+                let (_, last_start) = *start_times.last()?;
+                let end = last_start
+                    .as_micros()
+                    .checked_add(duration_micros(event_duration)?)?;
+                Some(Timestamp::from_micros(end))
+            }
+        }
+    }
+
+    /// The occurrence of the event with the given index, if the event has that many occurrences
+    pub fn occurrence(&self, index: u64) -> Option<Occurrence> {
+        match self {
+            EventTime::Unique { .. } => self.occurrences_between(Timestamp::MIN, Timestamp::MAX),
+            EventTime::Periodic {
+                start_time,
+                period_duration,
+                ..
+            } => {
+                let period = duration_micros(period_duration)?;
+                let start_time = (index as i64)
+                    .checked_mul(period)?
+                    .checked_add(start_time.as_micros())?;
+                self.occurrences_between(
+                    Timestamp::from_micros(start_time),
+                    Timestamp::from_micros(start_time.checked_add(1)?),
+                )
+            }
+            EventTime::Recurring { start_time, .. } => {
+                self.occurrences_between(*start_time, Timestamp::MAX)
+            }
+        }
+        .into_iter()
+        .find(|occurrence| occurrence.index == index)
+    }
+
+    /// The occurrences of the event that overlap with the window from `from` to `to`,
+    /// in chronological order and capped at `MAX_OCCURRENCES`
+    pub fn occurrences_between(&self, from: Timestamp, to: Timestamp) -> Vec<Occurrence> {
+        let (start_times, duration) = match self {
+            EventTime::Unique {
+                start_time,
+                end_time,
+            } => {
+                if *start_time >= to || *end_time <= from {
+                    return vec![];
+                }
+                return vec![Occurrence {
+                    index: 0,
+                    start_time: *start_time,
+                    end_time: *end_time,
+                }];
+            }
+            EventTime::Periodic {
+                start_time,
+                event_duration,
+                period_duration,
+                ocurrences: occurrences,
+            } => {
+                let start = start_time.as_micros();
+                let (Some(period), Some(duration)) = (
+                    duration_micros(period_duration),
+                    duration_micros(event_duration),
+                ) else {
+                    return vec![];
+                };
+                let period = period.max(1);
+                let Some(first_end) = start.checked_add(duration) else {
+                    return vec![];
+                };
+                // First occurrence that hasn't ended by `from`
+                let ended_before = from.as_micros().saturating_sub(first_end);
+                let first = (ended_before.div_euclid(period) + 1).max(0);
+                let last = occurrences.map(|o| o as i64).unwrap_or(i64::MAX);
+                let start_times: Vec<(u64, Timestamp)> = (first..last)
+                    .map_while(|n| Some((n as u64, n.checked_mul(period)?.checked_add(start)?)))
+                    .take_while(|(_, start_time)| *start_time < to.as_micros())
+                    .take(MAX_OCCURRENCES as usize)
+                    .map(|(index, start_time)| (index, Timestamp::from_micros(start_time)))
+                    .collect();
+                (start_times, duration)
+            }
+            EventTime::Recurring {
+                start_time,
+                event_duration,
+                recurrence,
+            } => {
+                let Some(duration) = duration_micros(event_duration) else {
+                    return vec![];
+                };
+                let ended_before = from.as_micros().saturating_sub(duration);
+                let start_times = recurrence_start_times(
+                    *start_time,
+                    recurrence,
+                    Timestamp::from_micros(ended_before + 1),
+                    to,
+                );
+                (start_times, duration)
+            }
+        };
+
+        start_times
+            .into_iter()
+            .map(|(index, start_time)| Occurrence {
+                index,
+                start_time,
+                end_time: Timestamp::from_micros(start_time.as_micros().saturating_add(duration)),
+            })
+            .filter(|occurrence| occurrence.end_time > from)
+            .collect()
+    }
+}
+
+/// The duration in microseconds, `None` if it doesn't fit in a timestamp
+fn duration_micros(duration: &Duration) -> Option<i64> {
+    i64::try_from(duration.as_micros()).ok()
+}
+
+pub fn validate_event_time(time: &EventTime) -> ValidateCallbackResult {
+    match time {
+        EventTime::Unique {
+            start_time,
+            end_time,
+        } => {
+            if end_time < start_time {
+                return ValidateCallbackResult::Invalid(String::from(
+                    "The end time of an event can't be before its start time",
+                ));
+            }
+        }
+        EventTime::Periodic {
+            event_duration,
+            period_duration,
+            ocurrences: occurrences,
+            ..
+        } => {
+            if event_duration.is_zero() {
+                return ValidateCallbackResult::Invalid(String::from(
+                    "The duration of a periodic event can't be zero",
+                ));
+            }
+            if period_duration < event_duration {
+                return ValidateCallbackResult::Invalid(String::from(
+                    "The period of a periodic event can't be shorter than its duration",
+                ));
+            }
+            if duration_micros(period_duration).is_none() {
+                return ValidateCallbackResult::Invalid(String::from(
+                    "The period of a periodic event is too long",
+                ));
+            }
+            if let Some(0) = occurrences {
+                return ValidateCallbackResult::Invalid(String::from(
+                    "A periodic event must have at least one occurrence",
+                ));
+            }
+        }
+        EventTime::Recurring {
+            start_time,
+            event_duration,
+            recurrence,
+        } => {
+            if event_duration.is_zero() {
+                return ValidateCallbackResult::Invalid(String::from(
+                    "The duration of a recurring event can't be zero",
+                ));
+            }
+            if duration_micros(event_duration).is_none() {
+                return ValidateCallbackResult::Invalid(String::from(
+                    "The duration of a recurring event is too long",
+                ));
+            }
+            return validate_recurrence(*start_time, recurrence);
+        }
+    }
+    ValidateCallbackResult::Valid
+}
+
+pub fn validate_create_event(
+    action: EntryCreationAction,
+    event: Event,
+) -> ExternResult<ValidateCallbackResult> {
+    for (field, value) in [("title", &event.title), ("description", &event.description)] {
+        let result = validate_not_empty(field, value);
+        let ValidateCallbackResult::Valid = result else {
+            return Ok(result);
+        };
+    }
+    if let Location::ToBeAnnounced = event.location {
+        return Ok(ValidateCallbackResult::Invalid(String::from(
+            "The location of an event must be announced",
+        )));
+    }
+    let result = validate_location(&event.location);
+    let ValidateCallbackResult::Valid = result else {
+        return Ok(result);
+    };
+
+    let result = validate_event_time(&event.time);
+    let ValidateCallbackResult::Valid = result else {
+        return Ok(result);
+    };
+
+    if let Some(cost) = &event.cost {
+        let result = validate_cost(cost);
+        let ValidateCallbackResult::Valid = result else {
+            return Ok(result);
+        };
+    }
+
+    let result = validate_tags(&event.tags);
+    let ValidateCallbackResult::Valid = result else {
+        return Ok(result);
+    };
+
+    if let Some(0) = event.capacity {
+        return Ok(ValidateCallbackResult::Invalid(String::from(
+            "The capacity of an event must be at least one",
+        )));
+    }
+
+    let result = validate_references(&event.call_to_action_hash, &event.image)?;
+    let ValidateCallbackResult::Valid = result else {
+        return Ok(result);
+    };
+
+    let result = validate_hosts(&event.hosts);
+    let ValidateCallbackResult::Valid = result else {
+        return Ok(result);
+    };
+
+    // Who is allowed to update an event is checked against the revision being updated
+    if let EntryCreationAction::Update(_) = action {
+        return Ok(ValidateCallbackResult::Valid);
+    }
+
+    let Some(from_proposal) = &event.from_proposal else {
+        return Ok(validate_author_is_host(action.author(), &event.hosts));
+    };
+
+    let Some(EntryTypes::Proposal(proposal)) =
+        must_get_valid_app_entry(from_proposal.proposal_hash.clone())?
+    else {
+        return Ok(ValidateCallbackResult::Invalid(String::from(
+            "The proposal hash must reference a proposal",
+        )));
+    };
+    if proposal.call_to_action_hash != event.call_to_action_hash {
+        return Ok(ValidateCallbackResult::Invalid(String::from(
+            "An event created from a proposal must have the same call to action as the proposal",
+        )));
+    }
+
+    // Whoever creates the assembly that fulfills the needs of the proposal creates the event,
+    // and they may not be one of the hosts
+    if let Some(assembly_hash) = &from_proposal.assembly_hash {
+        let assembly_record = must_get_valid_record(assembly_hash.clone())?;
+        if assembly_record.action().author().eq(action.author()) {
+            return Ok(ValidateCallbackResult::Valid);
+        }
+    }
+
+    Ok(validate_author_is_host(action.author(), &event.hosts))
+}
+
+/// Only the hosts of the revision being updated can update an event, once they have
+/// accepted to host it, so co-hosts added in a previous update can also update it
+pub fn validate_update_event(
+    action: Update,
+    _event: Event,
+    _original_action: EntryCreationAction,
+    original_event: Event,
 ) -> ExternResult<ValidateCallbackResult> {
-    let action_hash = ActionHash::try_from(target_address).map_err(|err| wasm_error!(err))?;
-    let record = must_get_valid_record(action_hash)?;
-    let _event: crate::Event = record
-        .entry()
-        .to_app_option()
-        .map_err(|e| wasm_error!(e))?
-        .ok_or(wasm_error!(WasmErrorInner::Guest(String::from(
-            "Linked action must reference an entry"
-        ))))?;
-    Ok(ValidateCallbackResult::Valid)
+    if !original_event.hosts.contains(&action.author) {
+        return Ok(ValidateCallbackResult::Invalid(String::from(
+            "Only the hosts of an event can update it",
+        )));
+    }
+    validate_host_has_accepted(action.original_action_address, &action.author)
 }
-pub fn validate_delete_link_events_by_author(
-    _action: DeleteLink,
-    _original_action: CreateLink,
-    _base: AnyLinkableHash,
-    _target: AnyLinkableHash,
-    _tag: LinkTag,
+
+pub fn validate_delete_event(
+    action: Delete,
+    _original_action: EntryCreationAction,
+    original_event: Event,
 ) -> ExternResult<ValidateCallbackResult> {
-    Ok(ValidateCallbackResult::Invalid(String::from(
-        "EventsByAuthor links cannot be deleted",
-    )))
+    if !original_event.hosts.contains(&action.author) {
+        return Ok(ValidateCallbackResult::Invalid(String::from(
+            "Only the hosts of an event can delete it",
+        )));
+    }
+    validate_host_has_accepted(action.deletes_address, &action.author)
 }
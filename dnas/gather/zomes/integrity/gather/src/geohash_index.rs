@@ -0,0 +1,233 @@
+use hdi::prelude::*;
+
+use crate::{
+    deserialize_link_tag, follows_deletion_of, must_get_original_action_hash,
+    must_get_original_app_entry, must_get_valid_app_entry, path_entry_hash, validate_acting_host,
+    validate_author_can_index, Coordinates, EntryTypes,
+};
+
+pub const UPCOMING_EVENTS_BY_LOCATION: &str = "upcoming_events_by_location";
+
+/// Number of characters of the geohashes that events are filed under,
+/// which makes cells of about 5km by 5km
+pub const GEOHASH_PRECISION: usize = 5;
+
+const GEOHASH_ALPHABET: &[u8; 32] = b"0123456789bcdefghjkmnpqrstuvwxyz";
+
+pub const EARTH_RADIUS_KM: f64 = 6_371.0;
+
+/// The area covered by all the coordinates whose geohash starts with a given prefix
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GeohashCell {
+    pub min_latitude: f64,
+    pub max_latitude: f64,
+    pub min_longitude: f64,
+    pub max_longitude: f64,
+}
+
+/// The tag of the links from the geohash paths to the upcoming events located in them
+#[derive(Serialize, Deserialize, Debug, Clone, SerializedBytes)]
+pub struct EventLocationTag {
+    /// The revision of the event whose location and time are indexed
+    pub revision_hash: ActionHash,
+    pub coordinates: Coordinates,
+    pub start_time: Timestamp,
+    pub end_time: Option<Timestamp>,
+}
+
+/// The geohash of the given coordinates, with the given number of characters
+pub fn geohash(coordinates: Coordinates, precision: usize) -> String {
+    let mut latitude_range = (-90.0, 90.0);
+    let mut longitude_range = (-180.0, 180.0);
+    let mut geohash = String::with_capacity(precision);
+    let mut is_longitude_bit = true;
+    let mut character = 0;
+    let mut bits = 0;
+
+    while geohash.len() < precision {
+        let (range, value) = if is_longitude_bit {
+            (&mut longitude_range, coordinates.longitude)
+        } else {
+            (&mut latitude_range, coordinates.latitude)
+        };
+        let middle = (range.0 + range.1) / 2.0;
+        character <<= 1;
+        if value >= middle {
+            character |= 1;
+            range.0 = middle;
+        } else {
+            range.1 = middle;
+        }
+        is_longitude_bit = !is_longitude_bit;
+        bits += 1;
+
+        if bits == 5 {
+            geohash.push(GEOHASH_ALPHABET[character] as char);
+            character = 0;
+            bits = 0;
+        }
+    }
+    geohash
+}
+
+/// The cell covered by the given geohash prefix, `None` if it isn't a valid geohash
+pub fn geohash_cell(geohash: &str) -> Option<GeohashCell> {
+    let mut cell = GeohashCell {
+        min_latitude: -90.0,
+        max_latitude: 90.0,
+        min_longitude: -180.0,
+        max_longitude: 180.0,
+    };
+    let mut is_longitude_bit = true;
+    for c in geohash.bytes() {
+        let value = GEOHASH_ALPHABET.iter().position(|a| *a == c)?;
+        for bit in (0..5).rev() {
+            let (min, max) = if is_longitude_bit {
+                (&mut cell.min_longitude, &mut cell.max_longitude)
+            } else {
+                (&mut cell.min_latitude, &mut cell.max_latitude)
+            };
+            let middle = (*min + *max) / 2.0;
+            if value & (1 << bit) != 0 {
+                *min = middle;
+            } else {
+                *max = middle;
+            }
+            is_longitude_bit = !is_longitude_bit;
+        }
+    }
+    Some(cell)
+}
+
+/// The path of the geohash cell in which the given coordinates fall, with one component
+/// per character of their geohash, like "upcoming_events_by_location.s.p.3.e.9"
+///
+/// Each character narrows down the cell, so that nearby events can be found by walking
+/// only the prefixes that overlap with the searched area
+pub fn geohash_path(coordinates: Coordinates) -> String {
+    let mut path = String::from(UPCOMING_EVENTS_BY_LOCATION);
+    for c in geohash(coordinates, GEOHASH_PRECISION).chars() {
+        path.push('.');
+        path.push(c);
+    }
+    path
+}
+
+/// The great-circle distance between the given coordinates, in kilometers
+pub fn distance_km(a: Coordinates, b: Coordinates) -> f64 {
+    let (latitude_a, latitude_b) = (a.latitude.to_radians(), b.latitude.to_radians());
+    let delta_latitude = latitude_b - latitude_a;
+    let delta_longitude = (b.longitude - a.longitude).to_radians();
+    let haversine = (delta_latitude / 2.0).sin().powi(2)
+        + latitude_a.cos() * latitude_b.cos() * (delta_longitude / 2.0).sin().powi(2);
+    2.0 * EARTH_RADIUS_KM * haversine.sqrt().min(1.0).asin()
+}
+
+pub fn validate_create_link_upcoming_events_by_location(
+    action: CreateLink,
+    base_address: AnyLinkableHash,
+    target_address: AnyLinkableHash,
+    tag: LinkTag,
+) -> ExternResult<ValidateCallbackResult> {
+    let Some(event_hash) = target_address.into_action_hash() else {
+        return Ok(ValidateCallbackResult::Invalid(String::from(
+            "The target of an UpcomingEventsByLocation link must be an action hash",
+        )));
+    };
+    let Some(EntryTypes::Event(_)) = must_get_original_app_entry(event_hash.clone())? else {
+        return Ok(ValidateCallbackResult::Invalid(String::from(
+            "The target of an UpcomingEventsByLocation link must be the original action of an event",
+        )));
+    };
+    let Some(tag) = deserialize_link_tag::<EventLocationTag>(tag) else {
+        return Ok(ValidateCallbackResult::Invalid(String::from(
+            "The tag of an UpcomingEventsByLocation link must be an event location tag",
+        )));
+    };
+    if must_get_original_action_hash(tag.revision_hash.clone())? != Some(event_hash) {
+        return Ok(ValidateCallbackResult::Invalid(String::from(
+            "The revision in the tag of an UpcomingEventsByLocation link must be a revision of its target",
+        )));
+    }
+    let Some(EntryTypes::Event(revision)) = must_get_valid_app_entry(tag.revision_hash.clone())?
+    else {
+        return Ok(ValidateCallbackResult::Invalid(String::from(
+            "The revision in the tag of an UpcomingEventsByLocation link must be an event",
+        )));
+    };
+    if revision.location.coordinates() != Some(tag.coordinates)
+        || revision.time.start_time() != tag.start_time
+        || revision.time.end_time() != tag.end_time
+    {
+        return Ok(ValidateCallbackResult::Invalid(String::from(
+            "The tag of an UpcomingEventsByLocation link must have the coordinates and the time of its revision",
+        )));
+    }
+    if base_address != AnyLinkableHash::from(path_entry_hash(&geohash_path(tag.coordinates))?) {
+        return Ok(ValidateCallbackResult::Invalid(String::from(
+            "The base of an UpcomingEventsByLocation link must be the path of the geohash of its coordinates",
+        )));
+    }
+    validate_author_can_index(tag.revision_hash, &revision.hosts, &action.author)
+}
+
+/// The acting hosts can remove their events from the index at any time, and anyone can remove
+/// them once they have ended
+pub fn validate_delete_link_upcoming_events_by_location(
+    action: DeleteLink,
+    original_action: CreateLink,
+    _base: AnyLinkableHash,
+    _target: AnyLinkableHash,
+    _tag: LinkTag,
+) -> ExternResult<ValidateCallbackResult> {
+    let Some(tag) = deserialize_link_tag::<EventLocationTag>(original_action.tag) else {
+        return Ok(ValidateCallbackResult::Invalid(String::from(
+            "The tag of an UpcomingEventsByLocation link must be an event location tag",
+        )));
+    };
+    if matches!(tag.end_time, Some(end_time) if end_time <= action.timestamp)
+        || follows_deletion_of(&action, &original_action.target_address)?
+    {
+        return Ok(ValidateCallbackResult::Valid);
+    }
+    let Some(EntryTypes::Event(revision)) = must_get_valid_app_entry(tag.revision_hash.clone())?
+    else {
+        return Ok(ValidateCallbackResult::Invalid(String::from(
+            "The revision in the tag of an UpcomingEventsByLocation link must be an event",
+        )));
+    };
+    validate_acting_host(tag.revision_hash, &revision.hosts, &action.author)
+}
+
+/// GeohashIndex links are created by `TypedPath::ensure()` to make the geohash cells
+/// with events discoverable
+pub fn validate_create_link_geohash_index(
+    _action: CreateLink,
+    _base_address: AnyLinkableHash,
+    target_address: AnyLinkableHash,
+    tag: LinkTag,
+) -> ExternResult<ValidateCallbackResult> {
+    if target_address.into_entry_hash().is_none() {
+        return Ok(ValidateCallbackResult::Invalid(String::from(
+            "The target of a GeohashIndex link must be the entry hash of a path",
+        )));
+    }
+    if tag.into_inner().is_empty() {
+        return Ok(ValidateCallbackResult::Invalid(String::from(
+            "The tag of a GeohashIndex link must be the component of its target path",
+        )));
+    }
+    Ok(ValidateCallbackResult::Valid)
+}
+
+pub fn validate_delete_link_geohash_index(
+    _action: DeleteLink,
+    _original_action: CreateLink,
+    _base: AnyLinkableHash,
+    _target: AnyLinkableHash,
+    _tag: LinkTag,
+) -> ExternResult<ValidateCallbackResult> {
+    Ok(ValidateCallbackResult::Invalid(String::from(
+        "GeohashIndex links cannot be deleted",
+    )))
+}
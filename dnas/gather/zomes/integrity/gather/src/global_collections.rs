@@ -0,0 +1,483 @@
+use hdi::prelude::*;
+
+use crate::{
+    deserialize_link_tag, follows_deletion_of, must_get_original_action_hash,
+    must_get_original_app_entry, must_get_valid_app_entry, proposal_start_time, time_bucket_path,
+    validate_acting_host, validate_host_has_accepted, EntryTypes, EventCollectionTag, EventStatus,
+    EventTransition, LinkTypes, ProposalCollectionTag, ProposalStatus, ProposalTransition,
+};
+
+pub const ALL_UPCOMING_EVENTS: &str = "all_upcoming_events";
+pub const ALL_PAST_EVENTS: &str = "all_past_events";
+pub const ALL_CANCELLED_EVENTS: &str = "all_cancelled_events";
+pub const ALL_OPEN_PROPOSALS: &str = "all_open_proposals";
+pub const ALL_EXPIRED_PROPOSALS: &str = "all_expired_proposals";
+pub const ALL_CANCELLED_PROPOSALS: &str = "all_cancelled_proposals";
+
+/// Computes the same entry hash as `Path::from(path).path_entry_hash()` in the hdk,
+/// which is not available in integrity zomes
+///
+/// A path is serialized as a messagepack array of binary components, and each
+/// component is the UTF-32 encoding of one of the "." separated parts of the path
+pub fn path_entry_hash(path: &str) -> ExternResult<EntryHash> {
+    let components: Vec<Vec<u8>> = path
+        .split('.')
+        .filter(|component| !component.is_empty())
+        .map(|component| {
+            component
+                .chars()
+                .flat_map(|c| (c as u32).to_le_bytes())
+                .collect()
+        })
+        .collect();
+
+    let mut bytes: Vec<u8> = vec![];
+    match components.len() {
+        len if len < 16 => bytes.push(0x90 | len as u8),
+        len => {
+            bytes.push(0xdc);
+            bytes.extend((len as u16).to_be_bytes());
+        }
+    }
+    for component in components {
+        match component.len() {
+            len if len < 1 << 8 => {
+                bytes.push(0xc4);
+                bytes.push(len as u8);
+            }
+            len if len < 1 << 16 => {
+                bytes.push(0xc5);
+                bytes.extend((len as u16).to_be_bytes());
+            }
+            len => {
+                bytes.push(0xc6);
+                bytes.extend((len as u32).to_be_bytes());
+            }
+        }
+        bytes.extend(component);
+    }
+
+    hash_entry(Entry::App(AppEntryBytes(SerializedBytes::from(
+        UnsafeBytes::from(bytes),
+    ))))
+}
+
+/// Mirror of the entry of the cancellations zome, with only the fields we need
+#[derive(Serialize, Deserialize, Debug, Clone, SerializedBytes)]
+struct Cancellation {
+    cancelled_hash: ActionHash,
+}
+
+/// Mirror of the entry of the assemble zome, with only the fields we need
+#[derive(Serialize, Deserialize, Debug, Clone, SerializedBytes)]
+pub struct CallToAction {
+    pub expiration_time: Option<Timestamp>,
+}
+
+pub fn validate_create_link_events_collection(
+    action: CreateLink,
+    status: EventStatus,
+    base_address: AnyLinkableHash,
+    target_address: AnyLinkableHash,
+    tag: LinkTag,
+) -> ExternResult<ValidateCallbackResult> {
+    let path = status.path();
+    let Some(event_hash) = target_address.into_action_hash() else {
+        return Ok(ValidateCallbackResult::Invalid(format!(
+            "The target of a link in the {path} collection must be an action hash"
+        )));
+    };
+    let Some(EntryTypes::Event(event)) = must_get_original_app_entry(event_hash.clone())? else {
+        return Ok(ValidateCallbackResult::Invalid(format!(
+            "The target of a link in the {path} collection must be the original action of an event"
+        )));
+    };
+    let Some(tag) = deserialize_link_tag::<EventCollectionTag>(tag) else {
+        return Ok(ValidateCallbackResult::Invalid(format!(
+            "The tag of a link in the {path} collection must be an event collection tag"
+        )));
+    };
+    if tag.transition.next_status() != status {
+        return Ok(ValidateCallbackResult::Invalid(format!(
+            "A {:?} transition can't add an event to the {path} collection",
+            tag.transition
+        )));
+    }
+    let previous_status = match &tag.previous_transition {
+        Some(previous_transition) => Some(
+            must_get_previous_transition_type(&action, previous_transition)?
+                .and_then(|link_type| EventStatus::from_link_type(&link_type)),
+        ),
+        None => None,
+    };
+    if previous_status != tag.transition.previous_status().map(Some) {
+        return Ok(ValidateCallbackResult::Invalid(format!(
+            "A {:?} transition must come right after removing the event from the collection of the status it starts from",
+            tag.transition
+        )));
+    }
+
+    if must_get_original_action_hash(tag.revision_hash.clone())? != Some(event_hash.clone()) {
+        return Ok(ValidateCallbackResult::Invalid(format!(
+            "The revision in the tag of a link in the {path} collection must be a revision of its target"
+        )));
+    }
+    let Some(EntryTypes::Event(revision)) = must_get_valid_app_entry(tag.revision_hash.clone())?
+    else {
+        return Ok(ValidateCallbackResult::Invalid(format!(
+            "The revision in the tag of a link in the {path} collection must be an event"
+        )));
+    };
+    if revision.time.start_time() != tag.start_time {
+        return Ok(ValidateCallbackResult::Invalid(format!(
+            "The start time in the tag of a link in the {path} collection must be the start time of its revision"
+        )));
+    }
+    let result = validate_collection_base(path, tag.start_time, base_address)?;
+    let ValidateCallbackResult::Valid = result else {
+        return Ok(result);
+    };
+
+    match tag.transition {
+        EventTransition::Created => {
+            if tag.revision_hash != event_hash {
+                return Ok(ValidateCallbackResult::Invalid(String::from(
+                    "A Created transition must be filed under the original event",
+                )));
+            }
+            let create_action = must_get_action(event_hash)?;
+            if create_action.action().author().ne(&action.author) {
+                return Ok(ValidateCallbackResult::Invalid(String::from(
+                    "Only the creator of an event can add it to the upcoming events",
+                )));
+            }
+            Ok(ValidateCallbackResult::Valid)
+        }
+        EventTransition::Passed => {
+            // The latest revision can't be known during validation, so a revision
+            // rescheduled after this one could still be marked as past by its old time
+            match revision.time.end_time() {
+                Some(end_time) if end_time <= action.timestamp => Ok(ValidateCallbackResult::Valid),
+                _ => Ok(ValidateCallbackResult::Invalid(String::from(
+                    "An event can't be marked as past before it has ended",
+                ))),
+            }
+        }
+        EventTransition::Rescheduled => {
+            validate_acting_host(tag.revision_hash, &revision.hosts, &action.author)
+        }
+        EventTransition::Cancelled { cancellation_hash } => {
+            validate_cancellation(cancellation_hash, &event_hash, &event.hosts)
+        }
+        EventTransition::Uncancelled {
+            cancellation_deletion_hash,
+        } => validate_cancellation_deletion(cancellation_deletion_hash, &event_hash, &event.hosts),
+    }
+}
+
+pub fn validate_create_link_proposals_collection(
+    action: CreateLink,
+    status: ProposalStatus,
+    base_address: AnyLinkableHash,
+    target_address: AnyLinkableHash,
+    tag: LinkTag,
+) -> ExternResult<ValidateCallbackResult> {
+    let path = status.path();
+    let Some(proposal_hash) = target_address.into_action_hash() else {
+        return Ok(ValidateCallbackResult::Invalid(format!(
+            "The target of a link in the {path} collection must be an action hash"
+        )));
+    };
+    let Some(EntryTypes::Proposal(proposal)) = must_get_original_app_entry(proposal_hash.clone())?
+    else {
+        return Ok(ValidateCallbackResult::Invalid(format!(
+            "The target of a link in the {path} collection must be the original action of a proposal"
+        )));
+    };
+    let Some(tag) = deserialize_link_tag::<ProposalCollectionTag>(tag) else {
+        return Ok(ValidateCallbackResult::Invalid(format!(
+            "The tag of a link in the {path} collection must be a proposal collection tag"
+        )));
+    };
+    if tag.transition.next_status() != status {
+        return Ok(ValidateCallbackResult::Invalid(format!(
+            "A {:?} transition can't add a proposal to the {path} collection",
+            tag.transition
+        )));
+    }
+    let previous_status = match &tag.previous_transition {
+        Some(previous_transition) => Some(
+            must_get_previous_transition_type(&action, previous_transition)?
+                .and_then(|link_type| ProposalStatus::from_link_type(&link_type)),
+        ),
+        None => None,
+    };
+    if previous_status != tag.transition.previous_status().map(Some) {
+        return Ok(ValidateCallbackResult::Invalid(format!(
+            "A {:?} transition must come right after removing the proposal from the collection of the status it starts from",
+            tag.transition
+        )));
+    }
+
+    if must_get_original_action_hash(tag.revision_hash.clone())? != Some(proposal_hash.clone()) {
+        return Ok(ValidateCallbackResult::Invalid(format!(
+            "The revision in the tag of a link in the {path} collection must be a revision of its target"
+        )));
+    }
+    let Some(EntryTypes::Proposal(revision)) = must_get_valid_app_entry(tag.revision_hash.clone())?
+    else {
+        return Ok(ValidateCallbackResult::Invalid(format!(
+            "The revision in the tag of a link in the {path} collection must be a proposal"
+        )));
+    };
+    let create_action = must_get_action(proposal_hash.clone())?;
+    if proposal_start_time(&revision, create_action.action().timestamp()) != tag.start_time {
+        return Ok(ValidateCallbackResult::Invalid(format!(
+            "The start time in the tag of a link in the {path} collection must be the start time of its revision"
+        )));
+    }
+    let result = validate_collection_base(path, tag.start_time, base_address)?;
+    let ValidateCallbackResult::Valid = result else {
+        return Ok(result);
+    };
+
+    match tag.transition {
+        ProposalTransition::Created => {
+            if tag.revision_hash != proposal_hash {
+                return Ok(ValidateCallbackResult::Invalid(String::from(
+                    "A Created transition must be filed under the original proposal",
+                )));
+            }
+            if create_action.action().author().ne(&action.author) {
+                return Ok(ValidateCallbackResult::Invalid(String::from(
+                    "Only the creator of a proposal can add it to the open proposals",
+                )));
+            }
+            Ok(ValidateCallbackResult::Valid)
+        }
+        ProposalTransition::Expired => {
+            match call_to_action_has_expired(proposal.call_to_action_hash, action.timestamp)? {
+                Some(true) => Ok(ValidateCallbackResult::Valid),
+                Some(false) => Ok(ValidateCallbackResult::Invalid(String::from(
+                    "A proposal can't expire before the expiration time of its call to action",
+                ))),
+                None => Ok(ValidateCallbackResult::Invalid(String::from(
+                    "The call to action of the proposal is malformed",
+                ))),
+            }
+        }
+        ProposalTransition::Rescheduled => {
+            validate_acting_host(tag.revision_hash, &revision.hosts, &action.author)
+        }
+        ProposalTransition::Cancelled { cancellation_hash } => {
+            validate_cancellation(cancellation_hash, &proposal_hash, &proposal.hosts)
+        }
+        ProposalTransition::Uncancelled {
+            cancellation_deletion_hash,
+        } => validate_cancellation_deletion(
+            cancellation_deletion_hash,
+            &proposal_hash,
+            &proposal.hosts,
+        ),
+    }
+}
+
+/// Removing an event from a collection is only valid for the agents who can transition it
+/// out of that status, or right after deleting it
+///
+/// Validating the transition that must come right after checks that the removed link was
+/// the one that filed the event in the collection of the status it starts from
+pub fn validate_delete_link_events_collection(
+    action: DeleteLink,
+    status: EventStatus,
+    original_action: CreateLink,
+) -> ExternResult<ValidateCallbackResult> {
+    if follows_deletion_of(&action, &original_action.target_address)? {
+        return Ok(ValidateCallbackResult::Valid);
+    }
+    let Some(tag) = deserialize_link_tag::<EventCollectionTag>(original_action.tag) else {
+        return Ok(ValidateCallbackResult::Invalid(format!(
+            "The tag of a link in the {} collection must be an event collection tag",
+            status.path()
+        )));
+    };
+    let Some(EntryTypes::Event(revision)) = must_get_valid_app_entry(tag.revision_hash.clone())?
+    else {
+        return Ok(ValidateCallbackResult::Invalid(format!(
+            "The revision in the tag of a link in the {} collection must be an event",
+            status.path()
+        )));
+    };
+    // Anyone can mark an event as past once it has ended
+    let has_ended =
+        matches!(revision.time.end_time(), Some(end_time) if end_time <= action.timestamp);
+    match status {
+        EventStatus::Upcoming if has_ended => Ok(ValidateCallbackResult::Valid),
+        EventStatus::Upcoming | EventStatus::Cancelled => {
+            validate_acting_host(tag.revision_hash, &revision.hosts, &action.author)
+        }
+        EventStatus::Past => Ok(ValidateCallbackResult::Invalid(String::from(
+            "Past events can't be removed from the past events",
+        ))),
+    }
+}
+
+/// Removing a proposal from a collection is only valid for the agents who can transition it
+/// out of that status, right after deleting it, or for open proposals, right after linking
+/// them to the event they became
+pub fn validate_delete_link_proposals_collection(
+    action: DeleteLink,
+    status: ProposalStatus,
+    original_action: CreateLink,
+) -> ExternResult<ValidateCallbackResult> {
+    if follows_deletion_of(&action, &original_action.target_address)? {
+        return Ok(ValidateCallbackResult::Valid);
+    }
+    if status == ProposalStatus::Open {
+        if let Some((_, previous_link)) = must_get_previous_create_link(&action)? {
+            if let Some(LinkTypes::ProposalToEvent) =
+                LinkTypes::from_type(previous_link.zome_index, previous_link.link_type)?
+            {
+                if previous_link.base_address == original_action.target_address {
+                    return Ok(ValidateCallbackResult::Valid);
+                }
+            }
+        }
+    }
+    let Some(tag) = deserialize_link_tag::<ProposalCollectionTag>(original_action.tag) else {
+        return Ok(ValidateCallbackResult::Invalid(format!(
+            "The tag of a link in the {} collection must be a proposal collection tag",
+            status.path()
+        )));
+    };
+    let Some(EntryTypes::Proposal(revision)) = must_get_valid_app_entry(tag.revision_hash.clone())?
+    else {
+        return Ok(ValidateCallbackResult::Invalid(format!(
+            "The revision in the tag of a link in the {} collection must be a proposal",
+            status.path()
+        )));
+    };
+    // Anyone can mark a proposal as expired once its call to action has expired
+    let has_expired = status == ProposalStatus::Open
+        && call_to_action_has_expired(revision.call_to_action_hash, action.timestamp)?
+            == Some(true);
+    match status {
+        ProposalStatus::Open if has_expired => Ok(ValidateCallbackResult::Valid),
+        ProposalStatus::Open | ProposalStatus::Cancelled => {
+            validate_acting_host(tag.revision_hash, &revision.hosts, &action.author)
+        }
+        ProposalStatus::Expired => Ok(ValidateCallbackResult::Invalid(String::from(
+            "Expired proposals can't be removed from the expired proposals",
+        ))),
+    }
+}
+
+fn validate_collection_base(
+    path: &str,
+    start_time: Timestamp,
+    base_address: AnyLinkableHash,
+) -> ExternResult<ValidateCallbackResult> {
+    if base_address != AnyLinkableHash::from(path_entry_hash(&time_bucket_path(path, start_time))?)
+    {
+        return Ok(ValidateCallbackResult::Invalid(format!(
+            "The base of a link in the {path} collection must be the path of the day of its start time"
+        )));
+    }
+    Ok(ValidateCallbackResult::Valid)
+}
+
+fn validate_cancellation(
+    cancellation_hash: ActionHash,
+    cancelled_hash: &ActionHash,
+    hosts: &Vec<AgentPubKey>,
+) -> ExternResult<ValidateCallbackResult> {
+    let record = must_get_valid_record(cancellation_hash)?;
+    let Ok(Some(cancellation)) = record.entry().to_app_option::<Cancellation>() else {
+        return Ok(ValidateCallbackResult::Invalid(String::from(
+            "The cancellation hash must reference a cancellation",
+        )));
+    };
+    if cancellation.cancelled_hash.ne(cancelled_hash) {
+        return Ok(ValidateCallbackResult::Invalid(String::from(
+            "The cancellation must cancel the linked event or proposal",
+        )));
+    }
+    if !hosts.contains(record.action().author()) {
+        return Ok(ValidateCallbackResult::Invalid(String::from(
+            "Only the hosts can cancel an event or a proposal",
+        )));
+    }
+    validate_host_has_accepted(cancelled_hash.clone(), record.action().author())
+}
+
+fn validate_cancellation_deletion(
+    cancellation_deletion_hash: ActionHash,
+    cancelled_hash: &ActionHash,
+    hosts: &Vec<AgentPubKey>,
+) -> ExternResult<ValidateCallbackResult> {
+    let deletion = must_get_action(cancellation_deletion_hash)?;
+    let Action::Delete(delete) = deletion.action() else {
+        return Ok(ValidateCallbackResult::Invalid(String::from(
+            "The cancellation deletion hash must reference a delete action",
+        )));
+    };
+    validate_cancellation(delete.deletes_address.clone(), cancelled_hash, hosts)
+}
+
+/// Walks back the source chain of the author of the given delete, skipping other deletes
+/// of links, and returns the create link that precedes them if there is one
+fn must_get_previous_create_link(
+    action: &DeleteLink,
+) -> ExternResult<Option<(ActionHash, CreateLink)>> {
+    let mut previous_hash = action.prev_action.clone();
+    loop {
+        let previous_action = must_get_action(previous_hash.clone())?;
+        match previous_action.action() {
+            Action::DeleteLink(delete_link) => {
+                previous_hash = delete_link.prev_action.clone();
+            }
+            Action::CreateLink(create_link) => {
+                return Ok(Some((previous_hash, create_link.clone())))
+            }
+            _ => return Ok(None),
+        }
+    }
+}
+
+/// The type of the link of the previous transition, if it's the link that the author
+/// deleted right before creating the given link and it has the same target
+fn must_get_previous_transition_type(
+    action: &CreateLink,
+    previous_transition: &ActionHash,
+) -> ExternResult<Option<LinkTypes>> {
+    let previous_action = must_get_action(action.prev_action.clone())?;
+    let Action::DeleteLink(delete_link) = previous_action.action() else {
+        return Ok(None);
+    };
+    if &delete_link.link_add_address != previous_transition {
+        return Ok(None);
+    }
+    let previous_transition_action = must_get_action(previous_transition.clone())?;
+    let Action::CreateLink(previous_link) = previous_transition_action.action() else {
+        return Ok(None);
+    };
+    if previous_link.target_address != action.target_address {
+        return Ok(None);
+    }
+    LinkTypes::from_type(previous_link.zome_index, previous_link.link_type)
+}
+
+/// Whether the call to action has expired by the given time, `None` if it's malformed
+fn call_to_action_has_expired(
+    call_to_action_hash: ActionHash,
+    timestamp: Timestamp,
+) -> ExternResult<Option<bool>> {
+    let record = must_get_valid_record(call_to_action_hash)?;
+    let Ok(Some(call_to_action)) = record.entry().to_app_option::<CallToAction>() else {
+        return Ok(None);
+    };
+    Ok(Some(matches!(
+        call_to_action.expiration_time,
+        Some(expiration_time) if expiration_time <= timestamp
+    )))
+}
@@ -0,0 +1,51 @@
+use hdi::prelude::*;
+
+use crate::{follows_deletion_of, must_get_original_app_entry, EntryTypes};
+
+pub fn validate_create_link_interested(
+    action: CreateLink,
+    base_address: AnyLinkableHash,
+    target_address: AnyLinkableHash,
+    _tag: LinkTag,
+) -> ExternResult<ValidateCallbackResult> {
+    let Some(agent) = target_address.into_agent_pub_key() else {
+        return Ok(ValidateCallbackResult::Invalid(String::from(
+            "The target of an Interested link must be an agent",
+        )));
+    };
+    if agent != action.author {
+        return Ok(ValidateCallbackResult::Invalid(String::from(
+            "Agents can only mark themselves as interested",
+        )));
+    }
+    let Some(event_or_proposal_hash) = base_address.into_action_hash() else {
+        return Ok(ValidateCallbackResult::Invalid(String::from(
+            "The base of an Interested link must be an action hash",
+        )));
+    };
+    match must_get_original_app_entry(event_or_proposal_hash)? {
+        Some(EntryTypes::Event(_)) | Some(EntryTypes::Proposal(_)) => {
+            Ok(ValidateCallbackResult::Valid)
+        }
+        _ => Ok(ValidateCallbackResult::Invalid(String::from(
+            "The base of an Interested link must be the original action of an event or a proposal",
+        ))),
+    }
+}
+
+pub fn validate_delete_link_interested(
+    action: DeleteLink,
+    original_action: CreateLink,
+    _base: AnyLinkableHash,
+    _target: AnyLinkableHash,
+    _tag: LinkTag,
+) -> ExternResult<ValidateCallbackResult> {
+    if action.author != original_action.author
+        && !follows_deletion_of(&action, &original_action.base_address)?
+    {
+        return Ok(ValidateCallbackResult::Invalid(String::from(
+            "Agents can only remove their own interest",
+        )));
+    }
+    Ok(ValidateCallbackResult::Valid)
+}
@@ -1,14 +1,45 @@
+use std::collections::HashSet;
+
 use hdi::prelude::*;
 
+pub mod attendance;
+pub use attendance::*;
+pub mod co_hosts;
+pub use co_hosts::*;
+pub mod cost;
+pub use cost::*;
 pub mod event;
 pub use event::*;
 pub mod proposal;
 pub use proposal::*;
-
-#[hdk_extern]
-pub fn validate(_op: Op) -> ExternResult<ValidateCallbackResult> {
-    Ok(ValidateCallbackResult::Valid)
-}
+pub mod geohash_index;
+pub use geohash_index::*;
+pub mod global_collections;
+pub use global_collections::*;
+pub mod interested;
+pub use interested::*;
+pub mod location;
+pub use location::*;
+pub mod my_events;
+pub use my_events::*;
+pub mod occurrence_override;
+pub use occurrence_override::*;
+pub mod private_event;
+pub use private_event::*;
+pub mod recurrence;
+pub use recurrence::*;
+pub mod rsvp;
+pub use rsvp::*;
+pub mod search_index;
+pub use search_index::*;
+pub mod status;
+pub use status::*;
+pub mod tags;
+pub use tags::*;
+pub mod time_index;
+pub use time_index::*;
+pub mod updates;
+pub use updates::*;
 
 #[derive(Serialize, Deserialize)]
 #[serde(tag = "type")]
@@ -17,6 +48,11 @@ pub fn validate(_op: Op) -> ExternResult<ValidateCallbackResult> {
 pub enum EntryTypes {
     Event(Event),
     Proposal(Proposal),
+    OccurrenceOverride(OccurrenceOverride),
+    PrivateEvent(PrivateEvent),
+    Rsvp(Rsvp),
+    Attendance(Attendance),
+    HostAcceptance(HostAcceptance),
 }
 
 #[derive(Serialize, Deserialize)]
@@ -32,4 +68,854 @@ pub enum LinkTypes {
     MyEvents,
     Interested,
     ProposalToEvent,
+    TimeIndex,
+    OccurrenceOverrides,
+    InterestedInOccurrence,
+    UpcomingEventsByLocation,
+    GeohashIndex,
+    EventsByTag,
+    ProposalsByTag,
+    TagIndex,
+    EventsSearchIndex,
+    ProposalsSearchIndex,
+    EncryptionKeys,
+    Invitations,
+    EventRsvps,
+    EventAttendees,
+    HostedEvents,
+}
+
+#[hdk_extern]
+pub fn validate(op: Op) -> ExternResult<ValidateCallbackResult> {
+    match op.flattened::<EntryTypes, LinkTypes>()? {
+        FlatOp::StoreEntry(store_entry) => match store_entry {
+            OpEntry::CreateEntry { app_entry, action } => match app_entry {
+                EntryTypes::Event(event) => {
+                    validate_create_event(EntryCreationAction::Create(action), event)
+                }
+                EntryTypes::Proposal(proposal) => {
+                    validate_create_proposal(EntryCreationAction::Create(action), proposal)
+                }
+                EntryTypes::OccurrenceOverride(occurrence_override) => {
+                    validate_create_occurrence_override(
+                        EntryCreationAction::Create(action),
+                        occurrence_override,
+                    )
+                }
+                EntryTypes::PrivateEvent(private_event) => validate_create_private_event(
+                    EntryCreationAction::Create(action),
+                    private_event,
+                ),
+                EntryTypes::Rsvp(rsvp) => {
+                    validate_create_rsvp(EntryCreationAction::Create(action), rsvp)
+                }
+                EntryTypes::Attendance(attendance) => {
+                    validate_create_attendance(EntryCreationAction::Create(action), attendance)
+                }
+                EntryTypes::HostAcceptance(host_acceptance) => validate_create_host_acceptance(
+                    EntryCreationAction::Create(action),
+                    host_acceptance,
+                ),
+            },
+            OpEntry::UpdateEntry {
+                app_entry, action, ..
+            } => match app_entry {
+                EntryTypes::Event(event) => {
+                    validate_create_event(EntryCreationAction::Update(action), event)
+                }
+                EntryTypes::Proposal(proposal) => {
+                    validate_create_proposal(EntryCreationAction::Update(action), proposal)
+                }
+                EntryTypes::OccurrenceOverride(occurrence_override) => {
+                    validate_create_occurrence_override(
+                        EntryCreationAction::Update(action),
+                        occurrence_override,
+                    )
+                }
+                EntryTypes::PrivateEvent(private_event) => validate_create_private_event(
+                    EntryCreationAction::Update(action),
+                    private_event,
+                ),
+                EntryTypes::Rsvp(rsvp) => {
+                    validate_create_rsvp(EntryCreationAction::Update(action), rsvp)
+                }
+                EntryTypes::Attendance(attendance) => {
+                    validate_create_attendance(EntryCreationAction::Update(action), attendance)
+                }
+                EntryTypes::HostAcceptance(host_acceptance) => validate_create_host_acceptance(
+                    EntryCreationAction::Update(action),
+                    host_acceptance,
+                ),
+            },
+            _ => Ok(ValidateCallbackResult::Valid),
+        },
+        FlatOp::RegisterUpdate(update_entry) => match update_entry {
+            OpUpdate::Entry {
+                original_action,
+                original_app_entry,
+                app_entry,
+                action,
+            } => match (app_entry, original_app_entry) {
+                (EntryTypes::Event(event), EntryTypes::Event(original_event)) => {
+                    validate_update_event(action, event, original_action, original_event)
+                }
+                (EntryTypes::Proposal(proposal), EntryTypes::Proposal(original_proposal)) => {
+                    validate_update_proposal(action, proposal, original_action, original_proposal)
+                }
+                (
+                    EntryTypes::OccurrenceOverride(occurrence_override),
+                    EntryTypes::OccurrenceOverride(original_occurrence_override),
+                ) => validate_update_occurrence_override(
+                    action,
+                    occurrence_override,
+                    original_action,
+                    original_occurrence_override,
+                ),
+                (
+                    EntryTypes::PrivateEvent(private_event),
+                    EntryTypes::PrivateEvent(original_private_event),
+                ) => validate_update_private_event(
+                    action,
+                    private_event,
+                    original_action,
+                    original_private_event,
+                ),
+                (EntryTypes::Rsvp(rsvp), EntryTypes::Rsvp(original_rsvp)) => {
+                    validate_update_rsvp(action, rsvp, original_action, original_rsvp)
+                }
+                (
+                    EntryTypes::Attendance(attendance),
+                    EntryTypes::Attendance(original_attendance),
+                ) => validate_update_attendance(
+                    action,
+                    attendance,
+                    original_action,
+                    original_attendance,
+                ),
+                (
+                    EntryTypes::HostAcceptance(host_acceptance),
+                    EntryTypes::HostAcceptance(original_host_acceptance),
+                ) => validate_update_host_acceptance(
+                    action,
+                    host_acceptance,
+                    original_action,
+                    original_host_acceptance,
+                ),
+                _ => Ok(ValidateCallbackResult::Invalid(
+                    "Original and updated entry types must be the same".to_string(),
+                )),
+            },
+            _ => Ok(ValidateCallbackResult::Valid),
+        },
+        FlatOp::RegisterDelete(delete_entry) => match delete_entry {
+            OpDelete::Entry {
+                original_action,
+                original_app_entry,
+                action,
+            } => match original_app_entry {
+                EntryTypes::Event(event) => validate_delete_event(action, original_action, event),
+                EntryTypes::Proposal(proposal) => {
+                    validate_delete_proposal(action, original_action, proposal)
+                }
+                EntryTypes::OccurrenceOverride(occurrence_override) => {
+                    validate_delete_occurrence_override(
+                        action,
+                        original_action,
+                        occurrence_override,
+                    )
+                }
+                EntryTypes::PrivateEvent(private_event) => {
+                    validate_delete_private_event(action, original_action, private_event)
+                }
+                EntryTypes::Rsvp(rsvp) => validate_delete_rsvp(action, original_action, rsvp),
+                EntryTypes::Attendance(attendance) => {
+                    validate_delete_attendance(action, original_action, attendance)
+                }
+                EntryTypes::HostAcceptance(host_acceptance) => {
+                    validate_delete_host_acceptance(action, original_action, host_acceptance)
+                }
+            },
+            _ => Ok(ValidateCallbackResult::Valid),
+        },
+        FlatOp::RegisterCreateLink {
+            link_type,
+            base_address,
+            target_address,
+            tag,
+            action,
+        } => validate_create_link(action, link_type, base_address, target_address, tag),
+        FlatOp::RegisterDeleteLink {
+            link_type,
+            base_address,
+            target_address,
+            tag,
+            original_action,
+            action,
+        } => validate_delete_link(
+            action,
+            original_action,
+            link_type,
+            base_address,
+            target_address,
+            tag,
+        ),
+        FlatOp::StoreRecord(store_record) => match store_record {
+            OpRecord::CreateEntry { app_entry, action } => match app_entry {
+                EntryTypes::Event(event) => {
+                    validate_create_event(EntryCreationAction::Create(action), event)
+                }
+                EntryTypes::Proposal(proposal) => {
+                    validate_create_proposal(EntryCreationAction::Create(action), proposal)
+                }
+                EntryTypes::OccurrenceOverride(occurrence_override) => {
+                    validate_create_occurrence_override(
+                        EntryCreationAction::Create(action),
+                        occurrence_override,
+                    )
+                }
+                EntryTypes::PrivateEvent(private_event) => validate_create_private_event(
+                    EntryCreationAction::Create(action),
+                    private_event,
+                ),
+                EntryTypes::Rsvp(rsvp) => {
+                    validate_create_rsvp(EntryCreationAction::Create(action), rsvp)
+                }
+                EntryTypes::Attendance(attendance) => {
+                    validate_create_attendance(EntryCreationAction::Create(action), attendance)
+                }
+                EntryTypes::HostAcceptance(host_acceptance) => validate_create_host_acceptance(
+                    EntryCreationAction::Create(action),
+                    host_acceptance,
+                ),
+            },
+            OpRecord::UpdateEntry {
+                original_action_hash,
+                app_entry,
+                action,
+                ..
+            } => {
+                let original_record = must_get_valid_record(original_action_hash)?;
+                let original_action = match original_record.action().clone() {
+                    Action::Create(create) => EntryCreationAction::Create(create),
+                    Action::Update(update) => EntryCreationAction::Update(update),
+                    _ => {
+                        return Ok(ValidateCallbackResult::Invalid(
+                            "Original action for an update must be a Create or Update action"
+                                .to_string(),
+                        ));
+                    }
+                };
+                match app_entry {
+                    EntryTypes::Event(event) => {
+                        let result = validate_create_event(
+                            EntryCreationAction::Update(action.clone()),
+                            event.clone(),
+                        )?;
+                        let ValidateCallbackResult::Valid = result else {
+                            return Ok(result);
+                        };
+                        let Some(original_event) = original_record
+                            .entry()
+                            .to_app_option::<Event>()
+                            .map_err(|e| wasm_error!(e))?
+                        else {
+                            return Ok(ValidateCallbackResult::Invalid(
+                                "The updated entry type must be the same as the original entry type"
+                                    .to_string(),
+                            ));
+                        };
+                        validate_update_event(action, event, original_action, original_event)
+                    }
+                    EntryTypes::Proposal(proposal) => {
+                        let result = validate_create_proposal(
+                            EntryCreationAction::Update(action.clone()),
+                            proposal.clone(),
+                        )?;
+                        let ValidateCallbackResult::Valid = result else {
+                            return Ok(result);
+                        };
+                        let Some(original_proposal) = original_record
+                            .entry()
+                            .to_app_option::<Proposal>()
+                            .map_err(|e| wasm_error!(e))?
+                        else {
+                            return Ok(ValidateCallbackResult::Invalid(
+                                "The updated entry type must be the same as the original entry type"
+                                    .to_string(),
+                            ));
+                        };
+                        validate_update_proposal(
+                            action,
+                            proposal,
+                            original_action,
+                            original_proposal,
+                        )
+                    }
+                    EntryTypes::OccurrenceOverride(occurrence_override) => {
+                        let result = validate_create_occurrence_override(
+                            EntryCreationAction::Update(action.clone()),
+                            occurrence_override.clone(),
+                        )?;
+                        let ValidateCallbackResult::Valid = result else {
+                            return Ok(result);
+                        };
+                        let Some(original_occurrence_override) = original_record
+                            .entry()
+                            .to_app_option::<OccurrenceOverride>()
+                            .map_err(|e| wasm_error!(e))?
+                        else {
+                            return Ok(ValidateCallbackResult::Invalid(
+                                "The updated entry type must be the same as the original entry type"
+                                    .to_string(),
+                            ));
+                        };
+                        validate_update_occurrence_override(
+                            action,
+                            occurrence_override,
+                            original_action,
+                            original_occurrence_override,
+                        )
+                    }
+                    EntryTypes::PrivateEvent(private_event) => {
+                        let result = validate_create_private_event(
+                            EntryCreationAction::Update(action.clone()),
+                            private_event.clone(),
+                        )?;
+                        let ValidateCallbackResult::Valid = result else {
+                            return Ok(result);
+                        };
+                        let Some(original_private_event) = original_record
+                            .entry()
+                            .to_app_option::<PrivateEvent>()
+                            .map_err(|e| wasm_error!(e))?
+                        else {
+                            return Ok(ValidateCallbackResult::Invalid(
+                                "The updated entry type must be the same as the original entry type"
+                                    .to_string(),
+                            ));
+                        };
+                        validate_update_private_event(
+                            action,
+                            private_event,
+                            original_action,
+                            original_private_event,
+                        )
+                    }
+                    EntryTypes::Rsvp(rsvp) => {
+                        let result = validate_create_rsvp(
+                            EntryCreationAction::Update(action.clone()),
+                            rsvp.clone(),
+                        )?;
+                        let ValidateCallbackResult::Valid = result else {
+                            return Ok(result);
+                        };
+                        let Some(original_rsvp) = original_record
+                            .entry()
+                            .to_app_option::<Rsvp>()
+                            .map_err(|e| wasm_error!(e))?
+                        else {
+                            return Ok(ValidateCallbackResult::Invalid(
+                                "The updated entry type must be the same as the original entry type"
+                                    .to_string(),
+                            ));
+                        };
+                        validate_update_rsvp(action, rsvp, original_action, original_rsvp)
+                    }
+                    EntryTypes::Attendance(attendance) => {
+                        let result = validate_create_attendance(
+                            EntryCreationAction::Update(action.clone()),
+                            attendance.clone(),
+                        )?;
+                        let ValidateCallbackResult::Valid = result else {
+                            return Ok(result);
+                        };
+                        let Some(original_attendance) = original_record
+                            .entry()
+                            .to_app_option::<Attendance>()
+                            .map_err(|e| wasm_error!(e))?
+                        else {
+                            return Ok(ValidateCallbackResult::Invalid(
+                                "The updated entry type must be the same as the original entry type"
+                                    .to_string(),
+                            ));
+                        };
+                        validate_update_attendance(
+                            action,
+                            attendance,
+                            original_action,
+                            original_attendance,
+                        )
+                    }
+                    EntryTypes::HostAcceptance(host_acceptance) => {
+                        let result = validate_create_host_acceptance(
+                            EntryCreationAction::Update(action.clone()),
+                            host_acceptance.clone(),
+                        )?;
+                        let ValidateCallbackResult::Valid = result else {
+                            return Ok(result);
+                        };
+                        let Some(original_host_acceptance) = original_record
+                            .entry()
+                            .to_app_option::<HostAcceptance>()
+                            .map_err(|e| wasm_error!(e))?
+                        else {
+                            return Ok(ValidateCallbackResult::Invalid(
+                                "The updated entry type must be the same as the original entry type"
+                                    .to_string(),
+                            ));
+                        };
+                        validate_update_host_acceptance(
+                            action,
+                            host_acceptance,
+                            original_action,
+                            original_host_acceptance,
+                        )
+                    }
+                }
+            }
+            OpRecord::DeleteEntry {
+                original_action_hash,
+                action,
+                ..
+            } => {
+                let original_record = must_get_valid_record(original_action_hash)?;
+                let original_action = match original_record.action().clone() {
+                    Action::Create(create) => EntryCreationAction::Create(create),
+                    Action::Update(update) => EntryCreationAction::Update(update),
+                    _ => {
+                        return Ok(ValidateCallbackResult::Invalid(
+                            "Original action for a delete must be a Create or Update action"
+                                .to_string(),
+                        ));
+                    }
+                };
+                let app_entry_type = match original_action.entry_type() {
+                    EntryType::App(app_entry_type) => app_entry_type,
+                    _ => {
+                        return Ok(ValidateCallbackResult::Valid);
+                    }
+                };
+                let Some(entry) = original_record.entry().as_option() else {
+                    if original_action.entry_type().visibility().is_public() {
+                        return Ok(ValidateCallbackResult::Invalid(
+                            "Original record for a delete of a public entry must contain an entry"
+                                .to_string(),
+                        ));
+                    }
+                    return Ok(ValidateCallbackResult::Valid);
+                };
+                let Some(original_app_entry) = EntryTypes::deserialize_from_type(
+                    app_entry_type.zome_index,
+                    app_entry_type.entry_index,
+                    entry,
+                )?
+                else {
+                    return Ok(ValidateCallbackResult::Valid);
+                };
+                match original_app_entry {
+                    EntryTypes::Event(original_event) => {
+                        validate_delete_event(action, original_action, original_event)
+                    }
+                    EntryTypes::Proposal(original_proposal) => {
+                        validate_delete_proposal(action, original_action, original_proposal)
+                    }
+                    EntryTypes::OccurrenceOverride(original_occurrence_override) => {
+                        validate_delete_occurrence_override(
+                            action,
+                            original_action,
+                            original_occurrence_override,
+                        )
+                    }
+                    EntryTypes::PrivateEvent(original_private_event) => {
+                        validate_delete_private_event(
+                            action,
+                            original_action,
+                            original_private_event,
+                        )
+                    }
+                    EntryTypes::Rsvp(original_rsvp) => {
+                        validate_delete_rsvp(action, original_action, original_rsvp)
+                    }
+                    EntryTypes::Attendance(original_attendance) => {
+                        validate_delete_attendance(action, original_action, original_attendance)
+                    }
+                    EntryTypes::HostAcceptance(original_host_acceptance) => {
+                        validate_delete_host_acceptance(
+                            action,
+                            original_action,
+                            original_host_acceptance,
+                        )
+                    }
+                }
+            }
+            OpRecord::CreateLink {
+                base_address,
+                target_address,
+                tag,
+                link_type,
+                action,
+            } => validate_create_link(action, link_type, base_address, target_address, tag),
+            OpRecord::DeleteLink {
+                original_action_hash,
+                base_address,
+                action,
+            } => {
+                let record = must_get_valid_record(original_action_hash)?;
+                let Action::CreateLink(create_link) = record.action().clone() else {
+                    return Ok(ValidateCallbackResult::Invalid(
+                        "The action that a DeleteLink deletes must be a CreateLink".to_string(),
+                    ));
+                };
+                let Some(link_type) =
+                    LinkTypes::from_type(create_link.zome_index, create_link.link_type)?
+                else {
+                    return Ok(ValidateCallbackResult::Valid);
+                };
+                validate_delete_link(
+                    action,
+                    create_link.clone(),
+                    link_type,
+                    base_address,
+                    create_link.target_address,
+                    create_link.tag,
+                )
+            }
+            _ => Ok(ValidateCallbackResult::Valid),
+        },
+        _ => Ok(ValidateCallbackResult::Valid),
+    }
+}
+
+fn validate_create_link(
+    action: CreateLink,
+    link_type: LinkTypes,
+    base_address: AnyLinkableHash,
+    target_address: AnyLinkableHash,
+    tag: LinkTag,
+) -> ExternResult<ValidateCallbackResult> {
+    match link_type {
+        LinkTypes::Updates => {
+            validate_create_link_updates(action, base_address, target_address, tag)
+        }
+        LinkTypes::UpcomingEvents => validate_create_link_events_collection(
+            action,
+            EventStatus::Upcoming,
+            base_address,
+            target_address,
+            tag,
+        ),
+        LinkTypes::PastEvents => validate_create_link_events_collection(
+            action,
+            EventStatus::Past,
+            base_address,
+            target_address,
+            tag,
+        ),
+        LinkTypes::CancelledEvents => validate_create_link_events_collection(
+            action,
+            EventStatus::Cancelled,
+            base_address,
+            target_address,
+            tag,
+        ),
+        LinkTypes::OpenProposals => validate_create_link_proposals_collection(
+            action,
+            ProposalStatus::Open,
+            base_address,
+            target_address,
+            tag,
+        ),
+        LinkTypes::ExpiredProposals => validate_create_link_proposals_collection(
+            action,
+            ProposalStatus::Expired,
+            base_address,
+            target_address,
+            tag,
+        ),
+        LinkTypes::CancelledProposals => validate_create_link_proposals_collection(
+            action,
+            ProposalStatus::Cancelled,
+            base_address,
+            target_address,
+            tag,
+        ),
+        LinkTypes::MyEvents => {
+            validate_create_link_my_events(action, base_address, target_address, tag)
+        }
+        LinkTypes::Interested => {
+            validate_create_link_interested(action, base_address, target_address, tag)
+        }
+        LinkTypes::ProposalToEvent => {
+            validate_create_link_proposal_to_event(action, base_address, target_address, tag)
+        }
+        LinkTypes::TimeIndex => {
+            validate_create_link_time_index(action, base_address, target_address, tag)
+        }
+        LinkTypes::OccurrenceOverrides => {
+            validate_create_link_occurrence_overrides(action, base_address, target_address, tag)
+        }
+        LinkTypes::InterestedInOccurrence => {
+            validate_create_link_interested_in_occurrence(action, base_address, target_address, tag)
+        }
+        LinkTypes::UpcomingEventsByLocation => validate_create_link_upcoming_events_by_location(
+            action,
+            base_address,
+            target_address,
+            tag,
+        ),
+        LinkTypes::GeohashIndex => {
+            validate_create_link_geohash_index(action, base_address, target_address, tag)
+        }
+        LinkTypes::EventsByTag => {
+            validate_create_link_events_by_tag(action, base_address, target_address, tag)
+        }
+        LinkTypes::ProposalsByTag => {
+            validate_create_link_proposals_by_tag(action, base_address, target_address, tag)
+        }
+        LinkTypes::TagIndex => {
+            validate_create_link_tag_index(action, base_address, target_address, tag)
+        }
+        LinkTypes::EventsSearchIndex => {
+            validate_create_link_events_search_index(action, base_address, target_address, tag)
+        }
+        LinkTypes::ProposalsSearchIndex => {
+            validate_create_link_proposals_search_index(action, base_address, target_address, tag)
+        }
+        LinkTypes::EncryptionKeys => {
+            validate_create_link_encryption_keys(action, base_address, target_address, tag)
+        }
+        LinkTypes::Invitations => {
+            validate_create_link_invitations(action, base_address, target_address, tag)
+        }
+        LinkTypes::EventRsvps => {
+            validate_create_link_event_rsvps(action, base_address, target_address, tag)
+        }
+        LinkTypes::EventAttendees => {
+            validate_create_link_event_attendees(action, base_address, target_address, tag)
+        }
+        LinkTypes::HostedEvents => {
+            validate_create_link_hosted_events(action, base_address, target_address, tag)
+        }
+    }
+}
+
+fn validate_delete_link(
+    action: DeleteLink,
+    original_action: CreateLink,
+    link_type: LinkTypes,
+    base_address: AnyLinkableHash,
+    target_address: AnyLinkableHash,
+    tag: LinkTag,
+) -> ExternResult<ValidateCallbackResult> {
+    match link_type {
+        LinkTypes::Updates => {
+            validate_delete_link_updates(action, original_action, base_address, target_address, tag)
+        }
+        LinkTypes::UpcomingEvents => {
+            validate_delete_link_events_collection(action, EventStatus::Upcoming, original_action)
+        }
+        LinkTypes::PastEvents => {
+            validate_delete_link_events_collection(action, EventStatus::Past, original_action)
+        }
+        LinkTypes::CancelledEvents => {
+            validate_delete_link_events_collection(action, EventStatus::Cancelled, original_action)
+        }
+        LinkTypes::OpenProposals => {
+            validate_delete_link_proposals_collection(action, ProposalStatus::Open, original_action)
+        }
+        LinkTypes::ExpiredProposals => validate_delete_link_proposals_collection(
+            action,
+            ProposalStatus::Expired,
+            original_action,
+        ),
+        LinkTypes::CancelledProposals => validate_delete_link_proposals_collection(
+            action,
+            ProposalStatus::Cancelled,
+            original_action,
+        ),
+        LinkTypes::MyEvents => validate_delete_link_my_events(
+            action,
+            original_action,
+            base_address,
+            target_address,
+            tag,
+        ),
+        LinkTypes::Interested => validate_delete_link_interested(
+            action,
+            original_action,
+            base_address,
+            target_address,
+            tag,
+        ),
+        LinkTypes::ProposalToEvent => validate_delete_link_proposal_to_event(
+            action,
+            original_action,
+            base_address,
+            target_address,
+            tag,
+        ),
+        LinkTypes::TimeIndex => validate_delete_link_time_index(
+            action,
+            original_action,
+            base_address,
+            target_address,
+            tag,
+        ),
+        LinkTypes::OccurrenceOverrides => validate_delete_link_occurrence_overrides(
+            action,
+            original_action,
+            base_address,
+            target_address,
+            tag,
+        ),
+        LinkTypes::InterestedInOccurrence => validate_delete_link_interested_in_occurrence(
+            action,
+            original_action,
+            base_address,
+            target_address,
+            tag,
+        ),
+        LinkTypes::UpcomingEventsByLocation => validate_delete_link_upcoming_events_by_location(
+            action,
+            original_action,
+            base_address,
+            target_address,
+            tag,
+        ),
+        LinkTypes::GeohashIndex => validate_delete_link_geohash_index(
+            action,
+            original_action,
+            base_address,
+            target_address,
+            tag,
+        ),
+        LinkTypes::EventsByTag | LinkTypes::ProposalsByTag => {
+            validate_delete_link_by_tag(action, original_action, base_address, target_address, tag)
+        }
+        LinkTypes::TagIndex => validate_delete_link_tag_index(
+            action,
+            original_action,
+            base_address,
+            target_address,
+            tag,
+        ),
+        LinkTypes::EventsSearchIndex | LinkTypes::ProposalsSearchIndex => {
+            validate_delete_link_search_index(
+                action,
+                original_action,
+                base_address,
+                target_address,
+                tag,
+            )
+        }
+        LinkTypes::EncryptionKeys => validate_delete_link_encryption_keys(
+            action,
+            original_action,
+            base_address,
+            target_address,
+            tag,
+        ),
+        LinkTypes::Invitations => validate_delete_link_invitations(
+            action,
+            original_action,
+            base_address,
+            target_address,
+            tag,
+        ),
+        LinkTypes::EventRsvps => validate_delete_link_event_rsvps(
+            action,
+            original_action,
+            base_address,
+            target_address,
+            tag,
+        ),
+        LinkTypes::EventAttendees => validate_delete_link_event_attendees(
+            action,
+            original_action,
+            base_address,
+            target_address,
+            tag,
+        ),
+        LinkTypes::HostedEvents => validate_delete_link_hosted_events(
+            action,
+            original_action,
+            base_address,
+            target_address,
+            tag,
+        ),
+    }
+}
+
+/** Helpers */
+
+pub(crate) fn validate_hosts(hosts: &Vec<AgentPubKey>) -> ValidateCallbackResult {
+    if hosts.is_empty() {
+        return ValidateCallbackResult::Invalid(String::from("There must be at least one host"));
+    }
+    let unique_hosts: HashSet<&AgentPubKey> = hosts.iter().collect();
+    if unique_hosts.len() != hosts.len() {
+        return ValidateCallbackResult::Invalid(String::from("Hosts can't contain duplicates"));
+    }
+    ValidateCallbackResult::Valid
+}
+
+pub(crate) fn validate_author_is_host(
+    author: &AgentPubKey,
+    hosts: &Vec<AgentPubKey>,
+) -> ValidateCallbackResult {
+    if !hosts.contains(author) {
+        return ValidateCallbackResult::Invalid(String::from(
+            "The author must be one of the hosts",
+        ));
+    }
+    ValidateCallbackResult::Valid
+}
+
+pub(crate) fn validate_not_empty(field: &str, value: &String) -> ValidateCallbackResult {
+    if value.trim().is_empty() {
+        return ValidateCallbackResult::Invalid(format!("The {field} can't be empty"));
+    }
+    ValidateCallbackResult::Valid
+}
+
+/// Checks that the call to action and the image referenced by an event or proposal
+/// exist in the DHT
+pub(crate) fn validate_references(
+    call_to_action_hash: &ActionHash,
+    image: &EntryHash,
+) -> ExternResult<ValidateCallbackResult> {
+    let call_to_action_record = must_get_valid_record(call_to_action_hash.clone())?;
+    if call_to_action_record.action().entry_type().is_none() {
+        return Ok(ValidateCallbackResult::Invalid(String::from(
+            "The call to action hash must reference an entry",
+        )));
+    }
+    must_get_entry(image.clone())?;
+    Ok(ValidateCallbackResult::Valid)
+}
+
+/// Fetches the record for the given action hash and deserializes its entry
+/// as one of the entry types of this zome
+pub(crate) fn must_get_valid_app_entry(
+    action_hash: ActionHash,
+) -> ExternResult<Option<EntryTypes>> {
+    let record = must_get_valid_record(action_hash)?;
+    let Some(EntryType::App(app_entry_def)) = record.action().entry_type() else {
+        return Ok(None);
+    };
+    let Some(entry) = record.entry().as_option() else {
+        return Ok(None);
+    };
+    EntryTypes::deserialize_from_type(app_entry_def.zome_index, app_entry_def.entry_index, entry)
+}
+
+/// Fetches the original create action of an event or a proposal and deserializes its entry
+pub(crate) fn must_get_original_app_entry(
+    action_hash: ActionHash,
+) -> ExternResult<Option<EntryTypes>> {
+    let action = must_get_action(action_hash.clone())?;
+    let Action::Create(_) = action.action() else {
+        return Ok(None);
+    };
+    must_get_valid_app_entry(action_hash)
 }
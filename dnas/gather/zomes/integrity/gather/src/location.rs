@@ -0,0 +1,112 @@
+use hdi::prelude::*;
+use serde::Deserializer;
+
+use crate::validate_not_empty;
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub struct Coordinates {
+    pub latitude: f64,
+    pub longitude: f64,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(tag = "type")]
+pub enum Location {
+    Physical {
+        address: String,
+        coordinates: Option<Coordinates>,
+    },
+    Online {
+        url: String,
+    },
+    Hybrid {
+        address: String,
+        coordinates: Option<Coordinates>,
+        url: String,
+    },
+    ToBeAnnounced,
+}
+
+impl Location {
+    pub fn address(&self) -> Option<&String> {
+        match self {
+            Location::Physical { address, .. } | Location::Hybrid { address, .. } => Some(address),
+            _ => None,
+        }
+    }
+
+    pub fn url(&self) -> Option<&String> {
+        match self {
+            Location::Online { url } | Location::Hybrid { url, .. } => Some(url),
+            _ => None,
+        }
+    }
+
+    pub fn coordinates(&self) -> Option<Coordinates> {
+        match self {
+            Location::Physical { coordinates, .. } | Location::Hybrid { coordinates, .. } => {
+                *coordinates
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Before `Location` existed, events stored their location as a free-text string
+/// and proposals as an optional one, without any location meaning it was to be announced
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum LocationOrLegacy {
+    Location(Location),
+    Legacy(Option<String>),
+}
+
+/// Deserializes a `Location`, migrating the legacy string form on read: legacy strings become
+/// physical addresses, and missing or empty ones become `ToBeAnnounced`
+pub fn deserialize_location<'de, D>(deserializer: D) -> Result<Location, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    Ok(match LocationOrLegacy::deserialize(deserializer)? {
+        LocationOrLegacy::Location(location) => location,
+        LocationOrLegacy::Legacy(Some(address)) if !address.trim().is_empty() => {
+            Location::Physical {
+                address,
+                coordinates: None,
+            }
+        }
+        LocationOrLegacy::Legacy(_) => Location::ToBeAnnounced,
+    })
+}
+
+pub fn validate_location(location: &Location) -> ValidateCallbackResult {
+    if let Some(address) = location.address() {
+        let result = validate_not_empty("address", address);
+        let ValidateCallbackResult::Valid = result else {
+            return result;
+        };
+    }
+    if let Some(url) = location.url() {
+        let result = validate_not_empty("url", url);
+        let ValidateCallbackResult::Valid = result else {
+            return result;
+        };
+    }
+    if let Some(Coordinates {
+        latitude,
+        longitude,
+    }) = location.coordinates()
+    {
+        if !(-90.0..=90.0).contains(&latitude) {
+            return ValidateCallbackResult::Invalid(String::from(
+                "The latitude must be between -90 and 90 degrees",
+            ));
+        }
+        if !(-180.0..=180.0).contains(&longitude) {
+            return ValidateCallbackResult::Invalid(String::from(
+                "The longitude must be between -180 and 180 degrees",
+            ));
+        }
+    }
+    ValidateCallbackResult::Valid
+}
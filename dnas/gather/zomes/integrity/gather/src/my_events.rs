@@ -0,0 +1,51 @@
+use hdi::prelude::*;
+
+use crate::{follows_deletion_of, must_get_original_app_entry, EntryTypes};
+
+pub fn validate_create_link_my_events(
+    action: CreateLink,
+    base_address: AnyLinkableHash,
+    target_address: AnyLinkableHash,
+    _tag: LinkTag,
+) -> ExternResult<ValidateCallbackResult> {
+    let Some(agent) = base_address.into_agent_pub_key() else {
+        return Ok(ValidateCallbackResult::Invalid(String::from(
+            "The base of a MyEvents link must be an agent",
+        )));
+    };
+    if agent != action.author {
+        return Ok(ValidateCallbackResult::Invalid(String::from(
+            "Agents can only add events to their own MyEvents",
+        )));
+    }
+    let Some(event_or_proposal_hash) = target_address.into_action_hash() else {
+        return Ok(ValidateCallbackResult::Invalid(String::from(
+            "The target of a MyEvents link must be an action hash",
+        )));
+    };
+    match must_get_original_app_entry(event_or_proposal_hash)? {
+        Some(EntryTypes::Event(_)) | Some(EntryTypes::Proposal(_)) => {
+            Ok(ValidateCallbackResult::Valid)
+        }
+        _ => Ok(ValidateCallbackResult::Invalid(String::from(
+            "The target of a MyEvents link must be the original action of an event or a proposal",
+        ))),
+    }
+}
+
+pub fn validate_delete_link_my_events(
+    action: DeleteLink,
+    original_action: CreateLink,
+    _base: AnyLinkableHash,
+    _target: AnyLinkableHash,
+    _tag: LinkTag,
+) -> ExternResult<ValidateCallbackResult> {
+    if action.author != original_action.author
+        && !follows_deletion_of(&action, &original_action.target_address)?
+    {
+        return Ok(ValidateCallbackResult::Invalid(String::from(
+            "Agents can only remove events from their own MyEvents",
+        )));
+    }
+    Ok(ValidateCallbackResult::Valid)
+}
@@ -0,0 +1,264 @@
+use hdi::prelude::*;
+
+use crate::{
+    deserialize_link_tag, follows_deletion_of, must_get_original_action_hash,
+    must_get_original_app_entry, must_get_valid_app_entry, validate_acting_host, validate_location,
+    EntryTypes, Event, EventTime, Location,
+};
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(tag = "type")]
+pub enum OccurrenceChange {
+    Cancelled,
+    Rescheduled {
+        start_time: Timestamp,
+        end_time: Timestamp,
+    },
+    Relocated {
+        location: Location,
+    },
+}
+
+/// A change to a single occurrence of a periodic or recurring event,
+/// which the hosts undo by deleting it
+#[hdk_entry_helper]
+#[derive(Clone)]
+pub struct OccurrenceOverride {
+    /// The original action hash of the event
+    pub event_hash: ActionHash,
+    /// The revision of the event that the hosts act on, which must have the occurrence
+    pub revision_hash: ActionHash,
+    pub occurrence_index: u64,
+    pub change: OccurrenceChange,
+}
+
+/// The tag of the links that only concern one occurrence of an event
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, SerializedBytes)]
+pub struct OccurrenceTag {
+    pub occurrence_index: u64,
+}
+
+pub fn validate_create_occurrence_override(
+    action: EntryCreationAction,
+    occurrence_override: OccurrenceOverride,
+) -> ExternResult<ValidateCallbackResult> {
+    if must_get_original_action_hash(occurrence_override.revision_hash.clone())?
+        != Some(occurrence_override.event_hash)
+    {
+        return Ok(ValidateCallbackResult::Invalid(String::from(
+            "The revision of an OccurrenceOverride must be a revision of its event",
+        )));
+    }
+    let Some(EntryTypes::Event(revision)) =
+        must_get_valid_app_entry(occurrence_override.revision_hash.clone())?
+    else {
+        return Ok(ValidateCallbackResult::Invalid(String::from(
+            "An OccurrenceOverride must reference a revision of an event",
+        )));
+    };
+    let result = validate_acting_host(
+        occurrence_override.revision_hash,
+        &revision.hosts,
+        action.author(),
+    )?;
+    let ValidateCallbackResult::Valid = result else {
+        return Ok(result);
+    };
+    let result = validate_occurrence_index(&revision.time, occurrence_override.occurrence_index);
+    let ValidateCallbackResult::Valid = result else {
+        return Ok(result);
+    };
+
+    match occurrence_override.change {
+        OccurrenceChange::Cancelled => {}
+        OccurrenceChange::Rescheduled {
+            start_time,
+            end_time,
+        } => {
+            if end_time < start_time {
+                return Ok(ValidateCallbackResult::Invalid(String::from(
+                    "The end time of an occurrence can't be before its start time",
+                )));
+            }
+        }
+        OccurrenceChange::Relocated { location } => {
+            if let Location::ToBeAnnounced = location {
+                return Ok(ValidateCallbackResult::Invalid(String::from(
+                    "The location of an occurrence must be announced",
+                )));
+            }
+            let result = validate_location(&location);
+            let ValidateCallbackResult::Valid = result else {
+                return Ok(result);
+            };
+        }
+    }
+
+    Ok(ValidateCallbackResult::Valid)
+}
+
+pub fn validate_update_occurrence_override(
+    _action: Update,
+    _occurrence_override: OccurrenceOverride,
+    _original_action: EntryCreationAction,
+    _original_occurrence_override: OccurrenceOverride,
+) -> ExternResult<ValidateCallbackResult> {
+    Ok(ValidateCallbackResult::Invalid(String::from(
+        "Occurrence overrides cannot be updated, delete them and create new ones",
+    )))
+}
+
+pub fn validate_delete_occurrence_override(
+    action: Delete,
+    _original_action: EntryCreationAction,
+    original_occurrence_override: OccurrenceOverride,
+) -> ExternResult<ValidateCallbackResult> {
+    validate_override_host(original_occurrence_override, &action.author)
+}
+
+pub fn validate_create_link_occurrence_overrides(
+    action: CreateLink,
+    base_address: AnyLinkableHash,
+    target_address: AnyLinkableHash,
+    _tag: LinkTag,
+) -> ExternResult<ValidateCallbackResult> {
+    let Some(event_hash) = base_address.into_action_hash() else {
+        return Ok(ValidateCallbackResult::Invalid(String::from(
+            "The base of an OccurrenceOverrides link must be an action hash",
+        )));
+    };
+    let Some(override_hash) = target_address.into_action_hash() else {
+        return Ok(ValidateCallbackResult::Invalid(String::from(
+            "The target of an OccurrenceOverrides link must be an action hash",
+        )));
+    };
+    let Some(EntryTypes::OccurrenceOverride(occurrence_override)) =
+        must_get_valid_app_entry(override_hash.clone())?
+    else {
+        return Ok(ValidateCallbackResult::Invalid(String::from(
+            "The target of an OccurrenceOverrides link must be an OccurrenceOverride",
+        )));
+    };
+    if occurrence_override.event_hash != event_hash {
+        return Ok(ValidateCallbackResult::Invalid(String::from(
+            "The base of an OccurrenceOverrides link must be the event of its target",
+        )));
+    }
+    let override_action = must_get_action(override_hash)?;
+    if override_action.action().author() != &action.author {
+        return Ok(ValidateCallbackResult::Invalid(String::from(
+            "Only the author of an OccurrenceOverride can link to it",
+        )));
+    }
+    Ok(ValidateCallbackResult::Valid)
+}
+
+pub fn validate_delete_link_occurrence_overrides(
+    action: DeleteLink,
+    original_action: CreateLink,
+    _base: AnyLinkableHash,
+    _target: AnyLinkableHash,
+    _tag: LinkTag,
+) -> ExternResult<ValidateCallbackResult> {
+    let Some(override_hash) = original_action.target_address.into_action_hash() else {
+        return Ok(ValidateCallbackResult::Invalid(String::from(
+            "The target of an OccurrenceOverrides link must be an action hash",
+        )));
+    };
+    let Some(EntryTypes::OccurrenceOverride(occurrence_override)) =
+        must_get_valid_app_entry(override_hash)?
+    else {
+        return Ok(ValidateCallbackResult::Invalid(String::from(
+            "The target of an OccurrenceOverrides link must be an OccurrenceOverride",
+        )));
+    };
+    validate_override_host(occurrence_override, &action.author)
+}
+
+pub fn validate_create_link_interested_in_occurrence(
+    action: CreateLink,
+    base_address: AnyLinkableHash,
+    target_address: AnyLinkableHash,
+    tag: LinkTag,
+) -> ExternResult<ValidateCallbackResult> {
+    let Some(agent) = target_address.into_agent_pub_key() else {
+        return Ok(ValidateCallbackResult::Invalid(String::from(
+            "The target of an InterestedInOccurrence link must be an agent",
+        )));
+    };
+    if agent != action.author {
+        return Ok(ValidateCallbackResult::Invalid(String::from(
+            "Agents can only mark themselves as interested",
+        )));
+    }
+    let Some(event_hash) = base_address.into_action_hash() else {
+        return Ok(ValidateCallbackResult::Invalid(String::from(
+            "The base of an InterestedInOccurrence link must be an action hash",
+        )));
+    };
+    let Some(event) = must_get_original_event(event_hash)? else {
+        return Ok(ValidateCallbackResult::Invalid(String::from(
+            "The base of an InterestedInOccurrence link must be the original action of an event",
+        )));
+    };
+    let Some(OccurrenceTag { occurrence_index }) = deserialize_link_tag(tag) else {
+        return Ok(ValidateCallbackResult::Invalid(String::from(
+            "The tag of an InterestedInOccurrence link must be an OccurrenceTag",
+        )));
+    };
+    Ok(validate_occurrence_index(&event.time, occurrence_index))
+}
+
+pub fn validate_delete_link_interested_in_occurrence(
+    action: DeleteLink,
+    original_action: CreateLink,
+    _base: AnyLinkableHash,
+    _target: AnyLinkableHash,
+    _tag: LinkTag,
+) -> ExternResult<ValidateCallbackResult> {
+    if action.author != original_action.author
+        && !follows_deletion_of(&action, &original_action.base_address)?
+    {
+        return Ok(ValidateCallbackResult::Invalid(String::from(
+            "Agents can only remove their own interest",
+        )));
+    }
+    Ok(ValidateCallbackResult::Valid)
+}
+
+/// Only the acting hosts of the revision that the override was made on can undo it
+fn validate_override_host(
+    occurrence_override: OccurrenceOverride,
+    author: &AgentPubKey,
+) -> ExternResult<ValidateCallbackResult> {
+    let Some(EntryTypes::Event(revision)) =
+        must_get_valid_app_entry(occurrence_override.revision_hash.clone())?
+    else {
+        return Ok(ValidateCallbackResult::Invalid(String::from(
+            "An OccurrenceOverride must reference a revision of an event",
+        )));
+    };
+    validate_acting_host(occurrence_override.revision_hash, &revision.hosts, author)
+}
+
+/// Only the occurrences of periodic and recurring events can be addressed on their own
+fn validate_occurrence_index(time: &EventTime, occurrence_index: u64) -> ValidateCallbackResult {
+    if let EventTime::Unique { .. } = time {
+        return ValidateCallbackResult::Invalid(String::from(
+            "Events with a unique time don't have separate occurrences",
+        ));
+    }
+    if time.occurrence(occurrence_index).is_none() {
+        return ValidateCallbackResult::Invalid(format!(
+            "The event doesn't have an occurrence with index {occurrence_index}"
+        ));
+    }
+    ValidateCallbackResult::Valid
+}
+
+fn must_get_original_event(event_hash: ActionHash) -> ExternResult<Option<Event>> {
+    match must_get_original_app_entry(event_hash)? {
+        Some(EntryTypes::Event(event)) => Ok(Some(event)),
+        _ => Ok(None),
+    }
+}
@@ -0,0 +1,237 @@
+use std::collections::HashSet;
+
+use hdi::prelude::*;
+
+use crate::{
+    deserialize_link_tag, must_get_original_action_hash, must_get_original_app_entry,
+    must_get_valid_app_entry, validate_acting_host, validate_author_is_host, validate_hosts,
+    EntryTypes,
+};
+
+/// An invite-only event, whose `Event` is encrypted so that only its hosts and invitees
+/// can read it
+///
+/// Private events are never added to the global collections nor to any of the indexes,
+/// so the rest of the network can't discover them
+#[hdk_entry_helper]
+#[derive(Clone)]
+pub struct PrivateEvent {
+    pub hosts: Vec<AgentPubKey>,
+    pub invitees: Vec<AgentPubKey>,
+    /// The serialized `Event`, encrypted with a random shared secret
+    pub encrypted_event: XSalsa20Poly1305EncryptedData,
+    /// The shared secret, encrypted for each of the hosts and the invitees
+    pub keys: Vec<EncryptedKey>,
+}
+
+/// The shared secret of a private event, encrypted from the X25519 key of the author
+/// of the revision to the X25519 key of one of its hosts or invitees
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct EncryptedKey {
+    pub agent: AgentPubKey,
+    pub sender: X25519PubKey,
+    pub recipient: X25519PubKey,
+    pub encrypted_key: XSalsa20Poly1305EncryptedData,
+}
+
+/// The tag of the link from an agent to itself that publishes the X25519 key
+/// that the shared secrets of private events are encrypted to
+#[derive(Serialize, Deserialize, Debug, Clone, SerializedBytes)]
+pub struct EncryptionKeyTag {
+    pub key: X25519PubKey,
+}
+
+/// The tag of the links from the hosts and the invitees to their private events
+#[derive(Serialize, Deserialize, Debug, Clone, SerializedBytes)]
+pub struct InvitationTag {
+    /// The revision of the private event that invites the agent
+    pub revision_hash: ActionHash,
+}
+
+impl PrivateEvent {
+    /// The hosts and the invitees, which are all the agents that can read the event
+    pub fn participants(&self) -> Vec<AgentPubKey> {
+        let mut participants = self.hosts.clone();
+        participants.extend(self.invitees.clone());
+        participants
+    }
+}
+
+pub fn validate_create_private_event(
+    action: EntryCreationAction,
+    private_event: PrivateEvent,
+) -> ExternResult<ValidateCallbackResult> {
+    let result = validate_hosts(&private_event.hosts);
+    let ValidateCallbackResult::Valid = result else {
+        return Ok(result);
+    };
+
+    if private_event.invitees.is_empty() {
+        return Ok(ValidateCallbackResult::Invalid(String::from(
+            "There must be at least one invitee",
+        )));
+    }
+    let participants = private_event.participants();
+    let unique_participants: HashSet<&AgentPubKey> = participants.iter().collect();
+    if unique_participants.len() != participants.len() {
+        return Ok(ValidateCallbackResult::Invalid(String::from(
+            "Invitees can't contain duplicates nor any of the hosts",
+        )));
+    }
+
+    // The content can't be validated, but every participant must be able to decrypt it
+    let key_agents: HashSet<&AgentPubKey> =
+        private_event.keys.iter().map(|key| &key.agent).collect();
+    if key_agents != unique_participants || private_event.keys.len() != participants.len() {
+        return Ok(ValidateCallbackResult::Invalid(String::from(
+            "There must be exactly one encrypted key for each of the hosts and the invitees",
+        )));
+    }
+
+    // Who is allowed to update a private event is checked against the revision being updated
+    if let EntryCreationAction::Update(_) = action {
+        return Ok(ValidateCallbackResult::Valid);
+    }
+    Ok(validate_author_is_host(
+        action.author(),
+        &private_event.hosts,
+    ))
+}
+
+pub fn validate_update_private_event(
+    action: Update,
+    _private_event: PrivateEvent,
+    _original_action: EntryCreationAction,
+    original_private_event: PrivateEvent,
+) -> ExternResult<ValidateCallbackResult> {
+    validate_acting_host(
+        action.original_action_address,
+        &original_private_event.hosts,
+        &action.author,
+    )
+}
+
+pub fn validate_delete_private_event(
+    action: Delete,
+    _original_action: EntryCreationAction,
+    original_private_event: PrivateEvent,
+) -> ExternResult<ValidateCallbackResult> {
+    if !original_private_event.hosts.contains(&action.author) {
+        return Ok(ValidateCallbackResult::Invalid(String::from(
+            "Only the hosts of a private event can delete it",
+        )));
+    }
+    Ok(ValidateCallbackResult::Valid)
+}
+
+/// Agents can only publish their own encryption keys
+pub fn validate_create_link_encryption_keys(
+    action: CreateLink,
+    base_address: AnyLinkableHash,
+    target_address: AnyLinkableHash,
+    tag: LinkTag,
+) -> ExternResult<ValidateCallbackResult> {
+    let author = AnyLinkableHash::from(action.author);
+    if base_address != author || target_address != author {
+        return Ok(ValidateCallbackResult::Invalid(String::from(
+            "The base and the target of an EncryptionKeys link must be its author",
+        )));
+    }
+    if deserialize_link_tag::<EncryptionKeyTag>(tag).is_none() {
+        return Ok(ValidateCallbackResult::Invalid(String::from(
+            "The tag of an EncryptionKeys link must be an EncryptionKeyTag",
+        )));
+    }
+    Ok(ValidateCallbackResult::Valid)
+}
+
+pub fn validate_delete_link_encryption_keys(
+    action: DeleteLink,
+    original_action: CreateLink,
+    _base: AnyLinkableHash,
+    _target: AnyLinkableHash,
+    _tag: LinkTag,
+) -> ExternResult<ValidateCallbackResult> {
+    if action.author != original_action.author {
+        return Ok(ValidateCallbackResult::Invalid(String::from(
+            "Agents can only remove their own encryption keys",
+        )));
+    }
+    Ok(ValidateCallbackResult::Valid)
+}
+
+/// The acting hosts of a revision of a private event can invite its hosts and invitees
+pub fn validate_create_link_invitations(
+    action: CreateLink,
+    base_address: AnyLinkableHash,
+    target_address: AnyLinkableHash,
+    tag: LinkTag,
+) -> ExternResult<ValidateCallbackResult> {
+    let Some(agent) = base_address.into_agent_pub_key() else {
+        return Ok(ValidateCallbackResult::Invalid(String::from(
+            "The base of an Invitations link must be an agent",
+        )));
+    };
+    let Some(private_event_hash) = target_address.into_action_hash() else {
+        return Ok(ValidateCallbackResult::Invalid(String::from(
+            "The target of an Invitations link must be an action hash",
+        )));
+    };
+    let Some(EntryTypes::PrivateEvent(_)) =
+        must_get_original_app_entry(private_event_hash.clone())?
+    else {
+        return Ok(ValidateCallbackResult::Invalid(String::from(
+            "The target of an Invitations link must be the original action of a private event",
+        )));
+    };
+    let Some(invitation) = deserialize_link_tag::<InvitationTag>(tag) else {
+        return Ok(ValidateCallbackResult::Invalid(String::from(
+            "The tag of an Invitations link must be an InvitationTag",
+        )));
+    };
+    if must_get_original_action_hash(invitation.revision_hash.clone())? != Some(private_event_hash)
+    {
+        return Ok(ValidateCallbackResult::Invalid(String::from(
+            "The revision in the tag of an Invitations link must be a revision of its target",
+        )));
+    }
+    let Some(EntryTypes::PrivateEvent(revision)) =
+        must_get_valid_app_entry(invitation.revision_hash.clone())?
+    else {
+        return Ok(ValidateCallbackResult::Invalid(String::from(
+            "The revision in the tag of an Invitations link must be a private event",
+        )));
+    };
+    if !revision.participants().contains(&agent) {
+        return Ok(ValidateCallbackResult::Invalid(String::from(
+            "Only the hosts and the invitees of a private event can be invited to it",
+        )));
+    }
+    validate_acting_host(invitation.revision_hash, &revision.hosts, &action.author)
+}
+
+/// Invitees can dismiss their invitations, and hosts can withdraw them
+pub fn validate_delete_link_invitations(
+    action: DeleteLink,
+    original_action: CreateLink,
+    base: AnyLinkableHash,
+    _target: AnyLinkableHash,
+    _tag: LinkTag,
+) -> ExternResult<ValidateCallbackResult> {
+    if base == AnyLinkableHash::from(action.author.clone()) {
+        return Ok(ValidateCallbackResult::Valid);
+    }
+    let Some(invitation) = deserialize_link_tag::<InvitationTag>(original_action.tag) else {
+        return Ok(ValidateCallbackResult::Invalid(String::from(
+            "The tag of an Invitations link must be an InvitationTag",
+        )));
+    };
+    let Some(EntryTypes::PrivateEvent(revision)) =
+        must_get_valid_app_entry(invitation.revision_hash.clone())?
+    else {
+        return Ok(ValidateCallbackResult::Invalid(String::from(
+            "The revision in the tag of an Invitations link must be a private event",
+        )));
+    };
+    validate_acting_host(invitation.revision_hash, &revision.hosts, &action.author)
+}
@@ -1,6 +1,11 @@
 use hdi::prelude::*;
 
-use crate::EventTime;
+use crate::{
+    deserialize_cost, deserialize_location, must_get_original_app_entry, validate_author_is_host,
+    validate_cost, validate_event_time, validate_host_has_accepted, validate_hosts,
+    validate_location, validate_not_empty, validate_references, validate_tags, Cost, EntryTypes,
+    EventTime, Location,
+};
 
 #[hdk_entry_helper]
 #[derive(Clone)]
@@ -9,8 +14,151 @@ pub struct Proposal {
     pub title: String,
     pub description: String,
     pub image: EntryHash,
-    pub location: Option<String>,
+    #[serde(deserialize_with = "deserialize_location")]
+    pub location: Location,
     pub time: Option<EventTime>,
-    pub cost: Option<String>,
+    #[serde(default, deserialize_with = "deserialize_cost")]
+    pub cost: Option<Cost>,
+    #[serde(default)]
+    pub tags: Vec<String>,
     pub call_to_action_hash: ActionHash,
 }
+
+/// The time under which a revision of a proposal is filed in the proposal collections,
+/// which is its creation time for proposals without time
+pub fn proposal_start_time(proposal: &Proposal, created_at: Timestamp) -> Timestamp {
+    match &proposal.time {
+        Some(time) => time.start_time(),
+        None => created_at,
+    }
+}
+
+pub fn validate_create_proposal(
+    action: EntryCreationAction,
+    proposal: Proposal,
+) -> ExternResult<ValidateCallbackResult> {
+    for (field, value) in [
+        ("title", &proposal.title),
+        ("description", &proposal.description),
+    ] {
+        let result = validate_not_empty(field, value);
+        let ValidateCallbackResult::Valid = result else {
+            return Ok(result);
+        };
+    }
+    let result = validate_location(&proposal.location);
+    let ValidateCallbackResult::Valid = result else {
+        return Ok(result);
+    };
+
+    if let Some(time) = &proposal.time {
+        let result = validate_event_time(time);
+        let ValidateCallbackResult::Valid = result else {
+            return Ok(result);
+        };
+    }
+    if let Some(cost) = &proposal.cost {
+        let result = validate_cost(cost);
+        let ValidateCallbackResult::Valid = result else {
+            return Ok(result);
+        };
+    }
+    let result = validate_tags(&proposal.tags);
+    let ValidateCallbackResult::Valid = result else {
+        return Ok(result);
+    };
+
+    let result = validate_references(&proposal.call_to_action_hash, &proposal.image)?;
+    let ValidateCallbackResult::Valid = result else {
+        return Ok(result);
+    };
+
+    let result = validate_hosts(&proposal.hosts);
+    let ValidateCallbackResult::Valid = result else {
+        return Ok(result);
+    };
+
+    // Who is allowed to update a proposal is checked against the revision being updated
+    if let EntryCreationAction::Update(_) = action {
+        return Ok(ValidateCallbackResult::Valid);
+    }
+
+    Ok(validate_author_is_host(action.author(), &proposal.hosts))
+}
+
+/// Only the hosts of the revision being updated can update a proposal, once they have
+/// accepted to host it, so co-hosts added in a previous update can also update it
+pub fn validate_update_proposal(
+    action: Update,
+    _proposal: Proposal,
+    _original_action: EntryCreationAction,
+    original_proposal: Proposal,
+) -> ExternResult<ValidateCallbackResult> {
+    if !original_proposal.hosts.contains(&action.author) {
+        return Ok(ValidateCallbackResult::Invalid(String::from(
+            "Only the hosts of a proposal can update it",
+        )));
+    }
+    validate_host_has_accepted(action.original_action_address, &action.author)
+}
+
+pub fn validate_delete_proposal(
+    action: Delete,
+    _original_action: EntryCreationAction,
+    original_proposal: Proposal,
+) -> ExternResult<ValidateCallbackResult> {
+    if !original_proposal.hosts.contains(&action.author) {
+        return Ok(ValidateCallbackResult::Invalid(String::from(
+            "Only the hosts of a proposal can delete it",
+        )));
+    }
+    validate_host_has_accepted(action.deletes_address, &action.author)
+}
+
+pub fn validate_create_link_proposal_to_event(
+    _action: CreateLink,
+    base_address: AnyLinkableHash,
+    target_address: AnyLinkableHash,
+    _tag: LinkTag,
+) -> ExternResult<ValidateCallbackResult> {
+    let Some(proposal_hash) = base_address.into_action_hash() else {
+        return Ok(ValidateCallbackResult::Invalid(String::from(
+            "The base of a ProposalToEvent link must be an action hash",
+        )));
+    };
+    let Some(EntryTypes::Proposal(_)) = must_get_original_app_entry(proposal_hash.clone())? else {
+        return Ok(ValidateCallbackResult::Invalid(String::from(
+            "The base of a ProposalToEvent link must be the original action of a proposal",
+        )));
+    };
+    let Some(event_hash) = target_address.into_action_hash() else {
+        return Ok(ValidateCallbackResult::Invalid(String::from(
+            "The target of a ProposalToEvent link must be an action hash",
+        )));
+    };
+    let Some(EntryTypes::Event(event)) = must_get_original_app_entry(event_hash)? else {
+        return Ok(ValidateCallbackResult::Invalid(String::from(
+            "The target of a ProposalToEvent link must be the original action of an event",
+        )));
+    };
+    match event.from_proposal {
+        Some(from_proposal) if from_proposal.proposal_hash == proposal_hash => {
+            Ok(ValidateCallbackResult::Valid)
+        }
+        _ => Ok(ValidateCallbackResult::Invalid(String::from(
+            "The target of a ProposalToEvent link must be an event created from its base proposal",
+        ))),
+    }
+}
+
+pub fn validate_delete_link_proposal_to_event(
+    _action: DeleteLink,
+    _original_action: CreateLink,
+    _base: AnyLinkableHash,
+    _target: AnyLinkableHash,
+    _tag: LinkTag,
+) -> ExternResult<ValidateCallbackResult> {
+    Ok(ValidateCallbackResult::Invalid(String::from(
+        "ProposalToEvent links cannot be deleted",
+    )))
+}
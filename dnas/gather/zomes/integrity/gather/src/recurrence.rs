@@ -0,0 +1,316 @@
+use hdi::prelude::*;
+
+use crate::{civil_from_days, days_from_civil, days_in_month, MICROS_PER_DAY};
+
+/// Upper bound on the number of occurrences a recurrence can have or be expanded into,
+/// so that expanding one always terminates in a reasonable time
+pub const MAX_OCCURRENCES: u32 = 10_000;
+
+/// Upper bound on the number of periods walked while expanding a recurrence, for rules
+/// that rarely or never produce an occurrence (e.g. the 31st of every other February)
+const MAX_PERIODS: i64 = 100_000;
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Frequency {
+    Daily,
+    Weekly,
+    Monthly,
+    Yearly,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Weekday {
+    Monday,
+    Tuesday,
+    Wednesday,
+    Thursday,
+    Friday,
+    Saturday,
+    Sunday,
+}
+
+/// The n-th given weekday of the month, counting from its end if `nth` is negative:
+/// `{ nth: 2, weekday: Thursday }` is the second Thursday, `{ nth: -1, weekday: Friday }`
+/// is the last Friday
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NthWeekday {
+    pub nth: i8,
+    pub weekday: Weekday,
+}
+
+/// A recurrence rule modeled after RFC 5545's RRULE and EXDATE
+///
+/// Occurrences start at the same UTC time of day as the first one, which always happens
+/// unless excluded; every other occurrence falls in the days selected by the rule:
+///
+/// - `by_weekday`: for daily rules, only these weekdays; for weekly and monthly rules,
+///   each of these weekdays in the week or month
+/// - `by_nth_weekday`: for monthly rules, these weekdays of the month
+/// - `by_month_day`: for monthly rules, these days of the month, counting from its end if negative
+///
+/// Without any of them, weekly, monthly and yearly rules repeat the weekday, day of the month
+/// or day of the year of the first occurrence
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct Recurrence {
+    pub frequency: Frequency,
+    /// Repeat every `interval` days, weeks, months or years
+    pub interval: u32,
+    #[serde(default)]
+    pub by_weekday: Vec<Weekday>,
+    #[serde(default)]
+    pub by_nth_weekday: Vec<NthWeekday>,
+    #[serde(default)]
+    pub by_month_day: Vec<i8>,
+    /// No occurrence starts after this time
+    pub until: Option<Timestamp>,
+    /// Total number of occurrences, counting the excluded ones
+    pub count: Option<u32>,
+    /// Start times of the occurrences that don't happen
+    #[serde(default)]
+    pub exclusions: Vec<Timestamp>,
+}
+
+impl Weekday {
+    /// Monday is 0, Sunday is 6
+    fn index(&self) -> i64 {
+        *self as i64
+    }
+
+    fn index_of_day(days: i64) -> i64 {
+        // 1970-01-01 was a Thursday
+        (days + 3).rem_euclid(7)
+    }
+}
+
+/// The start times of the occurrences of the recurrence starting at `start_time` that start
+/// between `after` and `before`, in chronological order and capped at `MAX_OCCURRENCES`
+///
+/// Each start time comes with the index of its occurrence, which counts the excluded ones
+/// so that excluding an occurrence doesn't shift the indexes of the following ones
+pub fn recurrence_start_times(
+    start_time: Timestamp,
+    recurrence: &Recurrence,
+    after: Timestamp,
+    before: Timestamp,
+) -> Vec<(u64, Timestamp)> {
+    let start = start_time.as_micros();
+    let start_day = start.div_euclid(MICROS_PER_DAY);
+    let time_of_day = start.rem_euclid(MICROS_PER_DAY);
+    let (start_year, start_month, start_month_day) = civil_from_days(start_day);
+    let interval = recurrence.interval.max(1) as i64;
+    let limit = match recurrence.until {
+        Some(until) => before.as_micros().min(until.as_micros().saturating_add(1)),
+        None => before.as_micros(),
+    };
+    let count = recurrence.count.unwrap_or(u32::MAX);
+
+    let mut start_times: Vec<(u64, Timestamp)> = Vec::new();
+    let mut counted: u32 = 0;
+    let mut push = |time: i64, start_times: &mut Vec<(u64, Timestamp)>| {
+        let index = counted as u64;
+        counted += 1;
+        let time = Timestamp::from_micros(time);
+        if time >= after && !recurrence.exclusions.contains(&time) {
+            start_times.push((index, time));
+        }
+        counted < count && start_times.len() < MAX_OCCURRENCES as usize
+    };
+
+    if start >= limit || !push(start, &mut start_times) {
+        return start_times;
+    }
+
+    for period in 0..MAX_PERIODS {
+        let (first_day, days) = match recurrence.frequency {
+            Frequency::Daily => {
+                let day = start_day + period * interval;
+                let matches = recurrence.by_weekday.is_empty()
+                    || recurrence
+                        .by_weekday
+                        .iter()
+                        .any(|weekday| weekday.index() == Weekday::index_of_day(day));
+                (day, if matches { vec![day] } else { vec![] })
+            }
+            Frequency::Weekly => {
+                let week_start =
+                    start_day - Weekday::index_of_day(start_day) + period * interval * 7;
+                let days = if recurrence.by_weekday.is_empty() {
+                    vec![week_start + Weekday::index_of_day(start_day)]
+                } else {
+                    recurrence
+                        .by_weekday
+                        .iter()
+                        .map(|weekday| week_start + weekday.index())
+                        .collect()
+                };
+                (week_start, days)
+            }
+            Frequency::Monthly => {
+                let months = (start_month as i64 - 1) + period * interval;
+                let year = start_year + months.div_euclid(12);
+                let month = months.rem_euclid(12) as u32 + 1;
+                (
+                    days_from_civil(year, month, 1),
+                    month_days(recurrence, year, month, start_month_day),
+                )
+            }
+            Frequency::Yearly => {
+                let year = start_year + period * interval;
+                let days = if start_month_day <= days_in_month(year, start_month) {
+                    vec![days_from_civil(year, start_month, start_month_day)]
+                } else {
+                    vec![]
+                };
+                (days_from_civil(year, 1, 1), days)
+            }
+        };
+
+        let Some(period_start) = first_day
+            .checked_mul(MICROS_PER_DAY)
+            .and_then(|micros| micros.checked_add(time_of_day))
+        else {
+            break;
+        };
+        if period_start >= limit {
+            break;
+        }
+
+        let mut days = days;
+        days.sort();
+        days.dedup();
+        for day in days {
+            let time = day * MICROS_PER_DAY + time_of_day;
+            if time <= start {
+                continue;
+            }
+            if time >= limit || !push(time, &mut start_times) {
+                return start_times;
+            }
+        }
+    }
+
+    start_times
+}
+
+/// The days selected by the monthly rule in the given month
+fn month_days(recurrence: &Recurrence, year: i64, month: u32, start_month_day: u32) -> Vec<i64> {
+    let first_day = days_from_civil(year, month, 1);
+    let month_length = days_in_month(year, month) as i64;
+    let mut month_days: Vec<i64> = Vec::new();
+
+    for month_day in &recurrence.by_month_day {
+        let month_day = *month_day as i64;
+        let month_day = if month_day > 0 {
+            month_day
+        } else {
+            month_length + month_day + 1
+        };
+        if (1..=month_length).contains(&month_day) {
+            month_days.push(month_day);
+        }
+    }
+
+    for weekday in &recurrence.by_weekday {
+        let first = (weekday.index() - Weekday::index_of_day(first_day)).rem_euclid(7) + 1;
+        month_days.extend((first..=month_length).step_by(7));
+    }
+
+    for NthWeekday { nth, weekday } in &recurrence.by_nth_weekday {
+        let month_day = if *nth > 0 {
+            let first = (weekday.index() - Weekday::index_of_day(first_day)).rem_euclid(7) + 1;
+            first + (*nth as i64 - 1) * 7
+        } else {
+            let last_day = first_day + month_length - 1;
+            let last =
+                month_length - (Weekday::index_of_day(last_day) - weekday.index()).rem_euclid(7);
+            last + (*nth as i64 + 1) * 7
+        };
+        if (1..=month_length).contains(&month_day) {
+            month_days.push(month_day);
+        }
+    }
+
+    if recurrence.by_month_day.is_empty()
+        && recurrence.by_weekday.is_empty()
+        && recurrence.by_nth_weekday.is_empty()
+        && start_month_day as i64 <= month_length
+    {
+        month_days.push(start_month_day as i64);
+    }
+
+    month_days
+        .into_iter()
+        .map(|month_day| first_day + month_day - 1)
+        .collect()
+}
+
+pub fn validate_recurrence(
+    start_time: Timestamp,
+    recurrence: &Recurrence,
+) -> ValidateCallbackResult {
+    if recurrence.interval == 0 {
+        return ValidateCallbackResult::Invalid(String::from(
+            "The interval of a recurrence can't be zero",
+        ));
+    }
+    if recurrence.count.is_some() && recurrence.until.is_some() {
+        return ValidateCallbackResult::Invalid(String::from(
+            "A recurrence can't have both a count and an until time",
+        ));
+    }
+    if let Some(count) = recurrence.count {
+        if count == 0 || count > MAX_OCCURRENCES {
+            return ValidateCallbackResult::Invalid(format!(
+                "The count of a recurrence must be between 1 and {MAX_OCCURRENCES}"
+            ));
+        }
+    }
+    if let Some(until) = recurrence.until {
+        if until < start_time {
+            return ValidateCallbackResult::Invalid(String::from(
+                "The until time of a recurrence can't be before its start time",
+            ));
+        }
+    }
+    if !recurrence.by_weekday.is_empty() && recurrence.frequency == Frequency::Yearly {
+        return ValidateCallbackResult::Invalid(String::from(
+            "Only daily, weekly and monthly recurrences can select weekdays",
+        ));
+    }
+    if (!recurrence.by_nth_weekday.is_empty() || !recurrence.by_month_day.is_empty())
+        && recurrence.frequency != Frequency::Monthly
+    {
+        return ValidateCallbackResult::Invalid(String::from(
+            "Only monthly recurrences can select days of the month",
+        ));
+    }
+    if recurrence
+        .by_nth_weekday
+        .iter()
+        .any(|NthWeekday { nth, .. }| *nth == 0 || !(-5..=5).contains(nth))
+    {
+        return ValidateCallbackResult::Invalid(String::from(
+            "The n-th weekday of a month must be between 1 and 5, or between -5 and -1",
+        ));
+    }
+    if recurrence
+        .by_month_day
+        .iter()
+        .any(|month_day| *month_day == 0 || !(-31..=31).contains(month_day))
+    {
+        return ValidateCallbackResult::Invalid(String::from(
+            "The day of a month must be between 1 and 31, or between -31 and -1",
+        ));
+    }
+    if recurrence.until.is_some() {
+        // Expanding the recurrence stops at MAX_OCCURRENCES, so its end time would be wrong
+        let start_times =
+            recurrence_start_times(start_time, recurrence, start_time, Timestamp::MAX);
+        if start_times.len() >= MAX_OCCURRENCES as usize {
+            return ValidateCallbackResult::Invalid(format!(
+                "A recurrence must have less than {MAX_OCCURRENCES} occurrences"
+            ));
+        }
+    }
+    ValidateCallbackResult::Valid
+}
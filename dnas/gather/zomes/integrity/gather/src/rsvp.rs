@@ -0,0 +1,177 @@
+use hdi::prelude::*;
+
+use crate::{
+    deserialize_link_tag, follows_deletion_of, must_get_original_app_entry,
+    must_get_valid_app_entry, validate_not_empty, EntryTypes,
+};
+
+/// Maximum number of guests that an agent can bring along
+pub const MAX_GUESTS: u32 = 10;
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RsvpStatus {
+    Going,
+    Maybe,
+    NotGoing,
+}
+
+/// The answer of its author to whether they'll attend an event
+///
+/// Each agent has a single RSVP per event, which they update when they change their mind
+#[hdk_entry_helper]
+#[derive(Clone, PartialEq)]
+pub struct Rsvp {
+    pub event_hash: ActionHash,
+    pub status: RsvpStatus,
+    /// Number of people that the author brings along, who also take seats
+    #[serde(default)]
+    pub guests: u32,
+    /// Names of some or all of the guests
+    #[serde(default)]
+    pub guest_names: Vec<String>,
+}
+
+impl Rsvp {
+    /// The author and their guests
+    pub fn party_size(&self) -> u32 {
+        1 + self.guests
+    }
+}
+
+/// The tag of the links from an event to the agents that have RSVP'd to it
+#[derive(Serialize, Deserialize, Debug, Clone, SerializedBytes)]
+pub struct RsvpTag {
+    pub status: RsvpStatus,
+    /// The revision of the RSVP that has the status
+    pub rsvp_hash: ActionHash,
+    /// The guests of the RSVP, so that headcounts don't need to fetch every RSVP
+    #[serde(default)]
+    pub guests: u32,
+}
+
+pub fn validate_create_rsvp(
+    _action: EntryCreationAction,
+    rsvp: Rsvp,
+) -> ExternResult<ValidateCallbackResult> {
+    if rsvp.guests > MAX_GUESTS {
+        return Ok(ValidateCallbackResult::Invalid(format!(
+            "Agents can't bring more than {MAX_GUESTS} guests"
+        )));
+    }
+    if rsvp.guest_names.len() > rsvp.guests as usize {
+        return Ok(ValidateCallbackResult::Invalid(String::from(
+            "There can't be more guest names than guests",
+        )));
+    }
+    for name in &rsvp.guest_names {
+        let result = validate_not_empty("name of a guest", name);
+        let ValidateCallbackResult::Valid = result else {
+            return Ok(result);
+        };
+    }
+
+    match must_get_original_app_entry(rsvp.event_hash)? {
+        Some(EntryTypes::Event(_)) => Ok(ValidateCallbackResult::Valid),
+        _ => Ok(ValidateCallbackResult::Invalid(String::from(
+            "An RSVP must be for the original action of an event",
+        ))),
+    }
+}
+
+pub fn validate_update_rsvp(
+    action: Update,
+    rsvp: Rsvp,
+    original_action: EntryCreationAction,
+    original_rsvp: Rsvp,
+) -> ExternResult<ValidateCallbackResult> {
+    if &action.author != original_action.author() {
+        return Ok(ValidateCallbackResult::Invalid(String::from(
+            "Agents can only update their own RSVPs",
+        )));
+    }
+    if rsvp.event_hash != original_rsvp.event_hash {
+        return Ok(ValidateCallbackResult::Invalid(String::from(
+            "The event of an RSVP can't be changed",
+        )));
+    }
+    Ok(ValidateCallbackResult::Valid)
+}
+
+pub fn validate_delete_rsvp(
+    action: Delete,
+    original_action: EntryCreationAction,
+    _original_rsvp: Rsvp,
+) -> ExternResult<ValidateCallbackResult> {
+    if &action.author != original_action.author() {
+        return Ok(ValidateCallbackResult::Invalid(String::from(
+            "Agents can only delete their own RSVPs",
+        )));
+    }
+    Ok(ValidateCallbackResult::Valid)
+}
+
+/// Agents can only link themselves, with the status of one of their RSVPs to the event
+pub fn validate_create_link_event_rsvps(
+    action: CreateLink,
+    base_address: AnyLinkableHash,
+    target_address: AnyLinkableHash,
+    tag: LinkTag,
+) -> ExternResult<ValidateCallbackResult> {
+    let Some(agent) = target_address.into_agent_pub_key() else {
+        return Ok(ValidateCallbackResult::Invalid(String::from(
+            "The target of an EventRsvps link must be an agent",
+        )));
+    };
+    if agent != action.author {
+        return Ok(ValidateCallbackResult::Invalid(String::from(
+            "Agents can only RSVP for themselves",
+        )));
+    }
+    let Some(event_hash) = base_address.into_action_hash() else {
+        return Ok(ValidateCallbackResult::Invalid(String::from(
+            "The base of an EventRsvps link must be an action hash",
+        )));
+    };
+    let Some(rsvp_tag) = deserialize_link_tag::<RsvpTag>(tag) else {
+        return Ok(ValidateCallbackResult::Invalid(String::from(
+            "The tag of an EventRsvps link must be an RsvpTag",
+        )));
+    };
+    let rsvp_record = must_get_valid_record(rsvp_tag.rsvp_hash.clone())?;
+    if rsvp_record.action().author() != &agent {
+        return Ok(ValidateCallbackResult::Invalid(String::from(
+            "The RSVP in the tag of an EventRsvps link must be authored by its target",
+        )));
+    }
+    let Some(EntryTypes::Rsvp(rsvp)) = must_get_valid_app_entry(rsvp_tag.rsvp_hash)? else {
+        return Ok(ValidateCallbackResult::Invalid(String::from(
+            "The tag of an EventRsvps link must reference an RSVP",
+        )));
+    };
+    if rsvp.event_hash != event_hash
+        || rsvp.status != rsvp_tag.status
+        || rsvp.guests != rsvp_tag.guests
+    {
+        return Ok(ValidateCallbackResult::Invalid(String::from(
+            "The RSVP in the tag of an EventRsvps link must be for its base and match the tag",
+        )));
+    }
+    Ok(ValidateCallbackResult::Valid)
+}
+
+pub fn validate_delete_link_event_rsvps(
+    action: DeleteLink,
+    original_action: CreateLink,
+    _base: AnyLinkableHash,
+    _target: AnyLinkableHash,
+    _tag: LinkTag,
+) -> ExternResult<ValidateCallbackResult> {
+    if action.author != original_action.author
+        && !follows_deletion_of(&action, &original_action.base_address)?
+    {
+        return Ok(ValidateCallbackResult::Invalid(String::from(
+            "Agents can only remove their own RSVPs",
+        )));
+    }
+    Ok(ValidateCallbackResult::Valid)
+}
@@ -0,0 +1,168 @@
+use std::collections::BTreeSet;
+
+use hdi::prelude::*;
+
+use crate::{
+    deserialize_link_tag, follows_deletion_of, must_get_original_action_hash,
+    must_get_original_app_entry, must_get_valid_app_entry, path_entry_hash, validate_acting_host,
+    validate_author_can_index, EntryTypes,
+};
+
+pub const EVENTS_SEARCH_INDEX: &str = "events_search_index";
+pub const PROPOSALS_SEARCH_INDEX: &str = "proposals_search_index";
+
+/// Words are filed under their first characters, so this is also the minimum length
+/// of the words that are indexed and searched for
+pub const SEARCH_PREFIX_LENGTH: usize = 3;
+
+/// The tag of the links from the search prefixes to the events and proposals
+/// with words that start with them
+#[derive(Serialize, Deserialize, Debug, Clone, SerializedBytes)]
+pub struct SearchIndexTag {
+    pub word: String,
+    /// The revision of the event or proposal whose title or description has the word
+    pub revision_hash: ActionHash,
+}
+
+/// The distinct lowercased words of the text that are long enough to be indexed
+pub fn search_words(text: &str) -> BTreeSet<String> {
+    text.to_lowercase()
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|word| word.chars().count() >= SEARCH_PREFIX_LENGTH)
+        .map(String::from)
+        .collect()
+}
+
+/// The words of the title and the description, which are what's searchable
+pub fn searchable_words(title: &str, description: &str) -> BTreeSet<String> {
+    let mut words = search_words(title);
+    words.extend(search_words(description));
+    words
+}
+
+/// The path that the word is filed under in the given index, like "events_search_index.caf"
+pub fn search_path(index: &str, word: &str) -> String {
+    let prefix: String = word.chars().take(SEARCH_PREFIX_LENGTH).collect();
+    format!("{index}.{prefix}")
+}
+
+pub fn validate_create_link_events_search_index(
+    action: CreateLink,
+    base_address: AnyLinkableHash,
+    target_address: AnyLinkableHash,
+    tag: LinkTag,
+) -> ExternResult<ValidateCallbackResult> {
+    validate_create_link_search_index(
+        EVENTS_SEARCH_INDEX,
+        action,
+        base_address,
+        target_address,
+        tag,
+    )
+}
+
+pub fn validate_create_link_proposals_search_index(
+    action: CreateLink,
+    base_address: AnyLinkableHash,
+    target_address: AnyLinkableHash,
+    tag: LinkTag,
+) -> ExternResult<ValidateCallbackResult> {
+    validate_create_link_search_index(
+        PROPOSALS_SEARCH_INDEX,
+        action,
+        base_address,
+        target_address,
+        tag,
+    )
+}
+
+/// The revision in the link tag must have the word, and the author must be one of its hosts
+/// or the author of the revision
+fn validate_create_link_search_index(
+    index: &str,
+    action: CreateLink,
+    base_address: AnyLinkableHash,
+    target_address: AnyLinkableHash,
+    tag: LinkTag,
+) -> ExternResult<ValidateCallbackResult> {
+    let Some(target_hash) = target_address.into_action_hash() else {
+        return Ok(ValidateCallbackResult::Invalid(format!(
+            "The target of a link in {index} must be an action hash"
+        )));
+    };
+    let Some(original_entry) = must_get_original_app_entry(target_hash.clone())? else {
+        return Ok(ValidateCallbackResult::Invalid(format!(
+            "The target of a link in {index} must be the original action of an event or a proposal"
+        )));
+    };
+    let Some(search_tag) = deserialize_link_tag::<SearchIndexTag>(tag) else {
+        return Ok(ValidateCallbackResult::Invalid(format!(
+            "The tag of a link in {index} must be a SearchIndexTag"
+        )));
+    };
+    if must_get_original_action_hash(search_tag.revision_hash.clone())? != Some(target_hash) {
+        return Ok(ValidateCallbackResult::Invalid(format!(
+            "The revision in the tag of a link in {index} must be a revision of its target"
+        )));
+    }
+    let revision = must_get_valid_app_entry(search_tag.revision_hash.clone())?;
+    let (words, hosts) = match (index, original_entry, revision) {
+        (EVENTS_SEARCH_INDEX, EntryTypes::Event(_), Some(EntryTypes::Event(event))) => (
+            searchable_words(&event.title, &event.description),
+            event.hosts,
+        ),
+        (PROPOSALS_SEARCH_INDEX, EntryTypes::Proposal(_), Some(EntryTypes::Proposal(proposal))) => {
+            (
+                searchable_words(&proposal.title, &proposal.description),
+                proposal.hosts,
+            )
+        }
+        _ => {
+            return Ok(ValidateCallbackResult::Invalid(format!(
+                "The target of a link in {index} has the wrong entry type"
+            )))
+        }
+    };
+    if !words.contains(&search_tag.word) {
+        return Ok(ValidateCallbackResult::Invalid(format!(
+            "The revision in the tag of a link in {index} must have the word \"{}\"",
+            search_tag.word
+        )));
+    }
+    if base_address
+        != AnyLinkableHash::from(path_entry_hash(&search_path(index, &search_tag.word))?)
+    {
+        return Ok(ValidateCallbackResult::Invalid(format!(
+            "The base of a link in {index} must be the path of the prefix of its word"
+        )));
+    }
+    validate_author_can_index(search_tag.revision_hash, &hosts, &action.author)
+}
+
+/// Only the acting hosts can remove their events and proposals from the search index
+pub fn validate_delete_link_search_index(
+    action: DeleteLink,
+    original_action: CreateLink,
+    _base: AnyLinkableHash,
+    _target: AnyLinkableHash,
+    _tag: LinkTag,
+) -> ExternResult<ValidateCallbackResult> {
+    if follows_deletion_of(&action, &original_action.target_address)? {
+        return Ok(ValidateCallbackResult::Valid);
+    }
+    let Some(search_tag) = deserialize_link_tag::<SearchIndexTag>(original_action.tag) else {
+        return Ok(ValidateCallbackResult::Invalid(String::from(
+            "The tag of a search index link must be a SearchIndexTag",
+        )));
+    };
+    let hosts = match must_get_valid_app_entry(search_tag.revision_hash.clone())? {
+        Some(EntryTypes::Event(event)) => event.hosts,
+        Some(EntryTypes::Proposal(proposal)) => proposal.hosts,
+        _ => {
+            return Ok(ValidateCallbackResult::Invalid(String::from(
+                "The revision in the tag of a search index link must be an event or a proposal",
+            )))
+        }
+    };
+    validate_acting_host(search_tag.revision_hash, &hosts, &action.author)
+}
@@ -0,0 +1,221 @@
+use hdi::prelude::*;
+
+use crate::{
+    LinkTypes, ALL_CANCELLED_EVENTS, ALL_CANCELLED_PROPOSALS, ALL_EXPIRED_PROPOSALS,
+    ALL_OPEN_PROPOSALS, ALL_PAST_EVENTS, ALL_UPCOMING_EVENTS,
+};
+
+/// The status of an event, given by the global collection that links to it
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EventStatus {
+    Upcoming,
+    Past,
+    Cancelled,
+}
+
+impl EventStatus {
+    pub fn from_link_type(link_type: &LinkTypes) -> Option<Self> {
+        match link_type {
+            LinkTypes::UpcomingEvents => Some(EventStatus::Upcoming),
+            LinkTypes::PastEvents => Some(EventStatus::Past),
+            LinkTypes::CancelledEvents => Some(EventStatus::Cancelled),
+            _ => None,
+        }
+    }
+
+    pub fn link_type(&self) -> LinkTypes {
+        match self {
+            EventStatus::Upcoming => LinkTypes::UpcomingEvents,
+            EventStatus::Past => LinkTypes::PastEvents,
+            EventStatus::Cancelled => LinkTypes::CancelledEvents,
+        }
+    }
+
+    pub fn path(&self) -> &'static str {
+        match self {
+            EventStatus::Upcoming => ALL_UPCOMING_EVENTS,
+            EventStatus::Past => ALL_PAST_EVENTS,
+            EventStatus::Cancelled => ALL_CANCELLED_EVENTS,
+        }
+    }
+}
+
+/// The legal transitions between the statuses of an event:
+///
+/// ```text
+///                    Rescheduled
+///                      +---+
+///          Created     v   |      Passed
+/// (none) ----------> Upcoming --------> Past
+///                     |    ^
+///           Cancelled |    | Uncancelled
+///                     v    |
+///                   Cancelled
+/// ```
+///
+/// A transition is stored in the tag of the link to the collection of its next status,
+/// together with the proof that it is allowed and the link of the transition before it,
+/// which must be deleted right before, so that the event is only ever in one collection
+#[derive(Serialize, Deserialize, Debug, Clone, SerializedBytes)]
+#[serde(tag = "type")]
+pub enum EventTransition {
+    Created,
+    /// The event has ended according to the revision in the tag
+    Passed,
+    /// The revision in the tag moved the event to another time bucket
+    Rescheduled,
+    Cancelled {
+        cancellation_hash: ActionHash,
+    },
+    Uncancelled {
+        cancellation_deletion_hash: ActionHash,
+    },
+}
+
+impl EventTransition {
+    pub fn previous_status(&self) -> Option<EventStatus> {
+        match self {
+            EventTransition::Created => None,
+            EventTransition::Passed => Some(EventStatus::Upcoming),
+            EventTransition::Rescheduled => Some(EventStatus::Upcoming),
+            EventTransition::Cancelled { .. } => Some(EventStatus::Upcoming),
+            EventTransition::Uncancelled { .. } => Some(EventStatus::Cancelled),
+        }
+    }
+
+    pub fn next_status(&self) -> EventStatus {
+        match self {
+            EventTransition::Created => EventStatus::Upcoming,
+            EventTransition::Passed => EventStatus::Past,
+            EventTransition::Rescheduled => EventStatus::Upcoming,
+            EventTransition::Cancelled { .. } => EventStatus::Cancelled,
+            EventTransition::Uncancelled { .. } => EventStatus::Upcoming,
+        }
+    }
+}
+
+/// The status of a proposal, given by the global collection that links to it
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProposalStatus {
+    Open,
+    Expired,
+    Cancelled,
+}
+
+impl ProposalStatus {
+    pub fn from_link_type(link_type: &LinkTypes) -> Option<Self> {
+        match link_type {
+            LinkTypes::OpenProposals => Some(ProposalStatus::Open),
+            LinkTypes::ExpiredProposals => Some(ProposalStatus::Expired),
+            LinkTypes::CancelledProposals => Some(ProposalStatus::Cancelled),
+            _ => None,
+        }
+    }
+
+    pub fn link_type(&self) -> LinkTypes {
+        match self {
+            ProposalStatus::Open => LinkTypes::OpenProposals,
+            ProposalStatus::Expired => LinkTypes::ExpiredProposals,
+            ProposalStatus::Cancelled => LinkTypes::CancelledProposals,
+        }
+    }
+
+    pub fn path(&self) -> &'static str {
+        match self {
+            ProposalStatus::Open => ALL_OPEN_PROPOSALS,
+            ProposalStatus::Expired => ALL_EXPIRED_PROPOSALS,
+            ProposalStatus::Cancelled => ALL_CANCELLED_PROPOSALS,
+        }
+    }
+}
+
+/// The legal transitions between the statuses of a proposal:
+///
+/// ```text
+///                  Rescheduled
+///                    +---+
+///          Created   v   |    Expired
+/// (none) ----------> Open ----------> Expired
+///                    |  ^
+///          Cancelled |  | Uncancelled
+///                    v  |
+///                  Cancelled
+/// ```
+///
+/// An open proposal also stops being open when it becomes an event, which is recorded
+/// with a ProposalToEvent link instead of a transition
+///
+/// A transition is stored in the tag of the link to the collection of its next status,
+/// together with the proof that it is allowed and the link of the transition before it,
+/// which must be deleted right before, so that the proposal is only ever in one collection
+#[derive(Serialize, Deserialize, Debug, Clone, SerializedBytes)]
+#[serde(tag = "type")]
+pub enum ProposalTransition {
+    Created,
+    /// The call to action of the proposal has expired
+    Expired,
+    /// The revision in the tag moved the proposal to another time bucket
+    Rescheduled,
+    Cancelled {
+        cancellation_hash: ActionHash,
+    },
+    Uncancelled {
+        cancellation_deletion_hash: ActionHash,
+    },
+}
+
+impl ProposalTransition {
+    pub fn previous_status(&self) -> Option<ProposalStatus> {
+        match self {
+            ProposalTransition::Created => None,
+            ProposalTransition::Expired => Some(ProposalStatus::Open),
+            ProposalTransition::Rescheduled => Some(ProposalStatus::Open),
+            ProposalTransition::Cancelled { .. } => Some(ProposalStatus::Open),
+            ProposalTransition::Uncancelled { .. } => Some(ProposalStatus::Cancelled),
+        }
+    }
+
+    pub fn next_status(&self) -> ProposalStatus {
+        match self {
+            ProposalTransition::Created => ProposalStatus::Open,
+            ProposalTransition::Expired => ProposalStatus::Expired,
+            ProposalTransition::Rescheduled => ProposalStatus::Open,
+            ProposalTransition::Cancelled { .. } => ProposalStatus::Cancelled,
+            ProposalTransition::Uncancelled { .. } => ProposalStatus::Open,
+        }
+    }
+}
+
+/// The tag of the links in the event collections
+///
+/// Links are filed in the time bucket of the start time of the given revision of the event,
+/// and the start time is repeated here so that pages can be sorted without fetching the events
+#[derive(Serialize, Deserialize, Debug, Clone, SerializedBytes)]
+pub struct EventCollectionTag {
+    pub transition: EventTransition,
+    pub revision_hash: ActionHash,
+    pub start_time: Timestamp,
+    /// The create link of the previous transition, which filed the event in the collection
+    /// of the status that this transition starts from, `None` only for the Created transition
+    pub previous_transition: Option<ActionHash>,
+}
+
+/// The tag of the links in the proposal collections
+///
+/// Proposals without time are filed in the time bucket of their creation
+#[derive(Serialize, Deserialize, Debug, Clone, SerializedBytes)]
+pub struct ProposalCollectionTag {
+    pub transition: ProposalTransition,
+    pub revision_hash: ActionHash,
+    pub start_time: Timestamp,
+    /// The create link of the previous transition, which filed the proposal in the collection
+    /// of the status that this transition starts from, `None` only for the Created transition
+    pub previous_transition: Option<ActionHash>,
+}
+
+pub fn deserialize_link_tag<T>(tag: LinkTag) -> Option<T>
+where
+    T: TryFrom<SerializedBytes, Error = SerializedBytesError>,
+{
+    T::try_from(SerializedBytes::from(UnsafeBytes::from(tag.into_inner()))).ok()
+}
@@ -0,0 +1,203 @@
+use std::collections::HashSet;
+
+use hdi::prelude::*;
+
+use crate::{
+    deserialize_link_tag, follows_deletion_of, must_get_original_action_hash,
+    must_get_original_app_entry, must_get_valid_app_entry, path_entry_hash, validate_acting_host,
+    validate_author_can_index, EntryTypes,
+};
+
+pub const EVENT_TAGS: &str = "event_tags";
+pub const PROPOSAL_TAGS: &str = "proposal_tags";
+
+pub const MAX_TAGS: usize = 10;
+pub const MAX_TAG_LENGTH: usize = 32;
+
+/// The tag of the links from the tag anchors to the events and proposals tagged with them
+#[derive(Serialize, Deserialize, Debug, Clone, SerializedBytes)]
+pub struct TaggedTag {
+    pub tag: String,
+    /// The revision of the event or proposal that has the tag
+    pub revision_hash: ActionHash,
+}
+
+/// Lowercases the tag and joins its words with dashes, dropping any punctuation,
+/// so that "Repair Café" and "#repair-café" become the same "repair-café" tag
+pub fn normalize_tag(tag: &str) -> String {
+    tag.to_lowercase()
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .collect::<Vec<&str>>()
+        .join("-")
+}
+
+/// The path of the anchor of the tag in the given collection of tags, like "event_tags.music"
+pub fn tag_path(tags: &str, tag: &str) -> String {
+    format!("{tags}.{tag}")
+}
+
+/// Tags must already be normalized, so that they can be used as path components
+pub fn validate_tags(tags: &[String]) -> ValidateCallbackResult {
+    if tags.len() > MAX_TAGS {
+        return ValidateCallbackResult::Invalid(format!(
+            "There can't be more than {MAX_TAGS} tags"
+        ));
+    }
+    for tag in tags {
+        if tag.is_empty() {
+            return ValidateCallbackResult::Invalid(String::from("Tags can't be empty"));
+        }
+        if tag.chars().count() > MAX_TAG_LENGTH {
+            return ValidateCallbackResult::Invalid(format!(
+                "Tags can't be longer than {MAX_TAG_LENGTH} characters"
+            ));
+        }
+        if normalize_tag(tag) != *tag {
+            return ValidateCallbackResult::Invalid(format!(
+                "The tag \"{tag}\" must be normalized as \"{}\"",
+                normalize_tag(tag)
+            ));
+        }
+    }
+    let unique_tags: HashSet<&String> = tags.iter().collect();
+    if unique_tags.len() != tags.len() {
+        return ValidateCallbackResult::Invalid(String::from("Tags can't contain duplicates"));
+    }
+    ValidateCallbackResult::Valid
+}
+
+pub fn validate_create_link_events_by_tag(
+    action: CreateLink,
+    base_address: AnyLinkableHash,
+    target_address: AnyLinkableHash,
+    tag: LinkTag,
+) -> ExternResult<ValidateCallbackResult> {
+    validate_create_link_by_tag(EVENT_TAGS, action, base_address, target_address, tag)
+}
+
+pub fn validate_create_link_proposals_by_tag(
+    action: CreateLink,
+    base_address: AnyLinkableHash,
+    target_address: AnyLinkableHash,
+    tag: LinkTag,
+) -> ExternResult<ValidateCallbackResult> {
+    validate_create_link_by_tag(PROPOSAL_TAGS, action, base_address, target_address, tag)
+}
+
+/// The revision in the link tag must have the tag, and the author must be one of its hosts
+/// or the author of the revision
+fn validate_create_link_by_tag(
+    tags: &str,
+    action: CreateLink,
+    base_address: AnyLinkableHash,
+    target_address: AnyLinkableHash,
+    tag: LinkTag,
+) -> ExternResult<ValidateCallbackResult> {
+    let Some(target_hash) = target_address.into_action_hash() else {
+        return Ok(ValidateCallbackResult::Invalid(format!(
+            "The target of a link in {tags} must be an action hash"
+        )));
+    };
+    let Some(original_entry) = must_get_original_app_entry(target_hash.clone())? else {
+        return Ok(ValidateCallbackResult::Invalid(format!(
+            "The target of a link in {tags} must be the original action of an event or a proposal"
+        )));
+    };
+    let Some(tagged) = deserialize_link_tag::<TaggedTag>(tag) else {
+        return Ok(ValidateCallbackResult::Invalid(format!(
+            "The tag of a link in {tags} must be a TaggedTag"
+        )));
+    };
+    if must_get_original_action_hash(tagged.revision_hash.clone())? != Some(target_hash) {
+        return Ok(ValidateCallbackResult::Invalid(format!(
+            "The revision in the tag of a link in {tags} must be a revision of its target"
+        )));
+    }
+    let revision = must_get_valid_app_entry(tagged.revision_hash.clone())?;
+    let (revision_tags, hosts) = match (tags, original_entry, revision) {
+        (EVENT_TAGS, EntryTypes::Event(_), Some(EntryTypes::Event(event))) => {
+            (event.tags, event.hosts)
+        }
+        (PROPOSAL_TAGS, EntryTypes::Proposal(_), Some(EntryTypes::Proposal(proposal))) => {
+            (proposal.tags, proposal.hosts)
+        }
+        _ => {
+            return Ok(ValidateCallbackResult::Invalid(format!(
+                "The target of a link in {tags} has the wrong entry type"
+            )))
+        }
+    };
+    if !revision_tags.contains(&tagged.tag) {
+        return Ok(ValidateCallbackResult::Invalid(format!(
+            "The revision in the tag of a link in {tags} must have the tag \"{}\"",
+            tagged.tag
+        )));
+    }
+    if base_address != AnyLinkableHash::from(path_entry_hash(&tag_path(tags, &tagged.tag))?) {
+        return Ok(ValidateCallbackResult::Invalid(format!(
+            "The base of a link in {tags} must be the path of its tag"
+        )));
+    }
+    validate_author_can_index(tagged.revision_hash, &hosts, &action.author)
+}
+
+/// Only the acting hosts can untag their events and proposals
+pub fn validate_delete_link_by_tag(
+    action: DeleteLink,
+    original_action: CreateLink,
+    _base: AnyLinkableHash,
+    _target: AnyLinkableHash,
+    _tag: LinkTag,
+) -> ExternResult<ValidateCallbackResult> {
+    if follows_deletion_of(&action, &original_action.target_address)? {
+        return Ok(ValidateCallbackResult::Valid);
+    }
+    let Some(tagged) = deserialize_link_tag::<TaggedTag>(original_action.tag) else {
+        return Ok(ValidateCallbackResult::Invalid(String::from(
+            "The tag of a link to a tagged event or proposal must be a TaggedTag",
+        )));
+    };
+    let hosts = match must_get_valid_app_entry(tagged.revision_hash.clone())? {
+        Some(EntryTypes::Event(event)) => event.hosts,
+        Some(EntryTypes::Proposal(proposal)) => proposal.hosts,
+        _ => {
+            return Ok(ValidateCallbackResult::Invalid(String::from(
+                "The revision in the tag of a link to a tagged event or proposal must be an event or a proposal",
+            )))
+        }
+    };
+    validate_acting_host(tagged.revision_hash, &hosts, &action.author)
+}
+
+/// TagIndex links are created by `TypedPath::ensure()` to make the tags discoverable
+pub fn validate_create_link_tag_index(
+    _action: CreateLink,
+    _base_address: AnyLinkableHash,
+    target_address: AnyLinkableHash,
+    tag: LinkTag,
+) -> ExternResult<ValidateCallbackResult> {
+    if target_address.into_entry_hash().is_none() {
+        return Ok(ValidateCallbackResult::Invalid(String::from(
+            "The target of a TagIndex link must be the entry hash of a path",
+        )));
+    }
+    if tag.into_inner().is_empty() {
+        return Ok(ValidateCallbackResult::Invalid(String::from(
+            "The tag of a TagIndex link must be the component of its target path",
+        )));
+    }
+    Ok(ValidateCallbackResult::Valid)
+}
+
+pub fn validate_delete_link_tag_index(
+    _action: DeleteLink,
+    _original_action: CreateLink,
+    _base: AnyLinkableHash,
+    _target: AnyLinkableHash,
+    _tag: LinkTag,
+) -> ExternResult<ValidateCallbackResult> {
+    Ok(ValidateCallbackResult::Invalid(String::from(
+        "TagIndex links cannot be deleted",
+    )))
+}
@@ -0,0 +1,92 @@
+use hdi::prelude::*;
+
+pub const MICROS_PER_DAY: i64 = 24 * 60 * 60 * 1_000_000;
+
+/// The UTC year, month and day of the given timestamp
+pub fn day_of(time: Timestamp) -> (i64, u32, u32) {
+    civil_from_days(time.as_micros().div_euclid(MICROS_PER_DAY))
+}
+
+/// The year, month and day of the given number of days since 1970-01-01
+pub fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    // civil_from_days from http://howardhinnant.github.io/date_algorithms.html
+    let days = days + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days.rem_euclid(146_097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1_460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let shifted_month = (5 * day_of_year + 2) / 153;
+    let day = (day_of_year - (153 * shifted_month + 2) / 5 + 1) as u32;
+    let month = (if shifted_month < 10 {
+        shifted_month + 3
+    } else {
+        shifted_month - 9
+    }) as u32;
+    let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+    (year, month, day)
+}
+
+/// The number of days since 1970-01-01 of the given year, month and day
+pub fn days_from_civil(year: i64, month: u32, day: u32) -> i64 {
+    // days_from_civil from http://howardhinnant.github.io/date_algorithms.html
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year.rem_euclid(400);
+    let shifted_month = (if month > 2 { month - 3 } else { month + 9 }) as i64;
+    let day_of_year = (153 * shifted_month + 2) / 5 + day as i64 - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146_097 + day_of_era - 719_468
+}
+
+/// The number of days in the given month
+pub fn days_in_month(year: i64, month: u32) -> u32 {
+    match month {
+        2 if year % 4 == 0 && (year % 100 != 0 || year % 400 == 0) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+/// The path of the day bucket of the given collection in which the given time falls,
+/// like "all_upcoming_events.2023.06.21"
+///
+/// Collections are sharded by day so that no single path gathers all their links
+pub fn time_bucket_path(collection: &str, time: Timestamp) -> String {
+    let (year, month, day) = day_of(time);
+    format!("{collection}.{year:04}.{month:02}.{day:02}")
+}
+
+/// TimeIndex links are created by `TypedPath::ensure()` to make the day buckets of
+/// the collections discoverable
+pub fn validate_create_link_time_index(
+    _action: CreateLink,
+    _base_address: AnyLinkableHash,
+    target_address: AnyLinkableHash,
+    tag: LinkTag,
+) -> ExternResult<ValidateCallbackResult> {
+    if target_address.into_entry_hash().is_none() {
+        return Ok(ValidateCallbackResult::Invalid(String::from(
+            "The target of a TimeIndex link must be the entry hash of a path",
+        )));
+    }
+    if tag.into_inner().is_empty() {
+        return Ok(ValidateCallbackResult::Invalid(String::from(
+            "The tag of a TimeIndex link must be the component of its target path",
+        )));
+    }
+    Ok(ValidateCallbackResult::Valid)
+}
+
+pub fn validate_delete_link_time_index(
+    _action: DeleteLink,
+    _original_action: CreateLink,
+    _base: AnyLinkableHash,
+    _target: AnyLinkableHash,
+    _tag: LinkTag,
+) -> ExternResult<ValidateCallbackResult> {
+    Ok(ValidateCallbackResult::Invalid(String::from(
+        "TimeIndex links cannot be deleted",
+    )))
+}
@@ -0,0 +1,140 @@
+use hdi::prelude::*;
+
+use crate::{deserialize_link_tag, must_get_original_app_entry};
+
+/// The tag of an Updates link whose update merges concurrent revisions
+///
+/// The update itself only follows one of the heads of the revisions, so the tag records the
+/// other heads that it resolves. Links without tag merge nothing
+#[derive(Serialize, Deserialize, Debug, Clone, SerializedBytes)]
+pub struct UpdatesTag {
+    pub merged_heads: Vec<ActionHash>,
+}
+
+pub fn validate_create_link_updates(
+    action: CreateLink,
+    base_address: AnyLinkableHash,
+    target_address: AnyLinkableHash,
+    tag: LinkTag,
+) -> ExternResult<ValidateCallbackResult> {
+    let Some(original_hash) = base_address.into_action_hash() else {
+        return Ok(ValidateCallbackResult::Invalid(String::from(
+            "The base of an Updates link must be an action hash",
+        )));
+    };
+    let Some(update_hash) = target_address.into_action_hash() else {
+        return Ok(ValidateCallbackResult::Invalid(String::from(
+            "The target of an Updates link must be an action hash",
+        )));
+    };
+    if must_get_original_app_entry(original_hash.clone())?.is_none() {
+        return Ok(ValidateCallbackResult::Invalid(String::from(
+            "The base of an Updates link must be the original action of an event or a proposal",
+        )));
+    }
+
+    let update_record = must_get_valid_record(update_hash.clone())?;
+    let Action::Update(update) = update_record.action() else {
+        return Ok(ValidateCallbackResult::Invalid(String::from(
+            "The target of an Updates link must be an update",
+        )));
+    };
+    if update.author != action.author {
+        return Ok(ValidateCallbackResult::Invalid(String::from(
+            "Only the author of an update can link to it",
+        )));
+    }
+
+    let Some(update_original_hash) =
+        must_get_original_action_hash(update.original_action_address.clone())?
+    else {
+        return Ok(ValidateCallbackResult::Invalid(String::from(
+            "Updates can only be chained on top of a create action",
+        )));
+    };
+    if update_original_hash != original_hash {
+        return Ok(ValidateCallbackResult::Invalid(String::from(
+            "The target of an Updates link must be an update of its base",
+        )));
+    }
+
+    if tag.0.is_empty() {
+        return Ok(ValidateCallbackResult::Valid);
+    }
+    let Some(updates_tag) = deserialize_link_tag::<UpdatesTag>(tag) else {
+        return Ok(ValidateCallbackResult::Invalid(String::from(
+            "Malformed Updates link tag",
+        )));
+    };
+    for merged_head in updates_tag.merged_heads {
+        if merged_head == update_hash || merged_head == update.original_action_address {
+            return Ok(ValidateCallbackResult::Invalid(String::from(
+                "An update cannot merge itself or the revision it updates",
+            )));
+        }
+        if must_get_original_action_hash(merged_head)? != Some(original_hash.clone()) {
+            return Ok(ValidateCallbackResult::Invalid(String::from(
+                "An update can only merge revisions of the same event or proposal",
+            )));
+        }
+    }
+
+    Ok(ValidateCallbackResult::Valid)
+}
+
+pub fn validate_delete_link_updates(
+    _action: DeleteLink,
+    _original_action: CreateLink,
+    _base: AnyLinkableHash,
+    _target: AnyLinkableHash,
+    _tag: LinkTag,
+) -> ExternResult<ValidateCallbackResult> {
+    Ok(ValidateCallbackResult::Invalid(String::from(
+        "Updates links cannot be deleted",
+    )))
+}
+
+/// Walks back the chain of updates from the given revision until the create action
+/// that started it, returning `None` if the chain contains any other kind of action
+pub fn must_get_original_action_hash(
+    revision_hash: ActionHash,
+) -> ExternResult<Option<ActionHash>> {
+    let mut previous_hash = revision_hash;
+    loop {
+        let previous_action = must_get_action(previous_hash.clone())?;
+        match previous_action.action() {
+            Action::Create(_) => return Ok(Some(previous_hash)),
+            Action::Update(previous_update) => {
+                previous_hash = previous_update.original_action_address.clone();
+            }
+            _ => return Ok(None),
+        }
+    }
+}
+
+/// Whether the given delete of a link comes right after the deletion of a revision of the
+/// given event or proposal, skipping other deletes of links
+///
+/// Deleting an event or a proposal removes all the links to it, including the ones created
+/// by other agents, which validation can only allow while nothing else has been committed
+pub fn follows_deletion_of(
+    action: &DeleteLink,
+    event_or_proposal_hash: &AnyLinkableHash,
+) -> ExternResult<bool> {
+    let mut previous_hash = action.prev_action.clone();
+    loop {
+        let previous_action = must_get_action(previous_hash.clone())?;
+        match previous_action.action() {
+            Action::DeleteLink(delete_link) => {
+                previous_hash = delete_link.prev_action.clone();
+            }
+            Action::Delete(delete) => {
+                must_get_valid_record(previous_hash)?;
+                let original_hash = must_get_original_action_hash(delete.deletes_address.clone())?;
+                return Ok(original_hash.map(AnyLinkableHash::from).as_ref()
+                    == Some(event_or_proposal_hash));
+            }
+            _ => return Ok(false),
+        }
+    }
+}
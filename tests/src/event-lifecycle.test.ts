@@ -1,14 +1,16 @@
-import { test, assert } from 'vitest';
+import { test, assert, expect } from 'vitest';
 
 import { dhtSync, pause, runScenario } from '@holochain/tryorama';
 import { toPromise } from '@holochain-open-dev/stores';
 
 import {
+  readAndAssertHostInvitation,
   readAndAssertNotification,
   sampleEvent,
   setup,
   waitAndDhtSync,
 } from './utils';
+import { Coordinates } from '../../ui/src/gather/gather/types.js';
 
 test('event: create and cancel', async t => {
   await runScenario(
@@ -23,7 +25,7 @@ test('event: create and cancel', async t => {
       const event = await alice.store.client.createEvent(
         await sampleEvent(alice.store, {
           title: 'Cool Event',
-          hosts: [bob.player.agentPubKey],
+          hosts: [alice.player.agentPubKey, bob.player.agentPubKey],
         })
       );
       assert.ok(event);
@@ -33,6 +35,11 @@ test('event: create and cancel', async t => {
       upcomingEvents = await toPromise(bob.store.allUpcomingEvents);
       assert.equal(upcomingEvents.length, 1);
 
+      await readAndAssertHostInvitation(
+        bob.store,
+        'Cool Event',
+        'Du blev inbjuden att vara värd för detta event.'
+      );
       await readAndAssertNotification(
         bob.store,
         'Cool Event',
@@ -82,10 +89,10 @@ test('event: create and pass', async t => {
           time: {
             type: 'Unique',
             start_time: (Date.now() + 25_000) * 1000,
-            end_time: (Date.now() + 60_000) * 1000,
+            end_time: (Date.now() + 26_000) * 1000,
           },
           title: 'Cool Event',
-          hosts: [bob.player.agentPubKey],
+          hosts: [alice.player.agentPubKey, bob.player.agentPubKey],
         })
       );
       assert.ok(event);
@@ -109,3 +116,414 @@ test('event: create and pass', async t => {
     { timeout: 60000 }
   );
 });
+
+test('event: a cancelled event stays out of the other collections', async t => {
+  await runScenario(
+    async scenario => {
+      const { alice, bob } = await setup(scenario);
+
+      const event = await alice.store.client.createEvent(
+        await sampleEvent(alice.store, {
+          time: {
+            type: 'Unique',
+            start_time: (Date.now() + 2_000) * 1000,
+            end_time: (Date.now() + 3_000) * 1000,
+          },
+        })
+      );
+      await waitAndDhtSync([alice.player, bob.player]);
+
+      await alice.store.cancellationsStore.client.createCancellation(
+        event.actionHash,
+        "I can't make it"
+      );
+      await waitAndDhtSync([alice.player, bob.player]);
+
+      let cancelledEvents = await bob.store.client.getAllCancelledEvents();
+      assert.equal(cancelledEvents.length, 1);
+
+      await pause(3_000);
+
+      // The event has ended, but it can only pass from the upcoming events
+      await expect(
+        bob.store.client.markEventAsPast(event.actionHash)
+      ).rejects.toThrow();
+      await waitAndDhtSync([alice.player, bob.player]);
+
+      assert.equal((await bob.store.client.getAllPastEvents()).length, 0);
+      assert.equal((await bob.store.client.getAllUpcomingEvents()).length, 0);
+      cancelledEvents = await bob.store.client.getAllCancelledEvents();
+      assert.equal(cancelledEvents.length, 1);
+    },
+    true,
+    { timeout: 60_000 }
+  );
+});
+
+test('event: invalid links are rejected', async t => {
+  await runScenario(
+    async scenario => {
+      const { alice, bob } = await setup(scenario);
+
+      const event = await alice.store.client.createEvent(
+        await sampleEvent(alice.store)
+      );
+      const otherEvent = await alice.store.client.createEvent(
+        await sampleEvent(alice.store)
+      );
+      await waitAndDhtSync([alice.player, bob.player]);
+
+      // The event hasn't ended yet
+      await expect(
+        bob.store.client.markEventAsPast(event.actionHash)
+      ).rejects.toThrow();
+
+      // Only events and proposals can be added to my events
+      await expect(
+        bob.store.client.addToMyEvents(event.entry.call_to_action_hash)
+      ).rejects.toThrow();
+
+      // The Updates link of an update of another event
+      await expect(
+        alice.store.client.updateEvent(
+          event.actionHash,
+          otherEvent.actionHash,
+          otherEvent.entry
+        )
+      ).rejects.toThrow();
+
+      const upcomingEvents = await bob.store.client.getAllUpcomingEvents();
+      assert.equal(upcomingEvents.length, 2);
+      const pastEvents = await bob.store.client.getAllPastEvents();
+      assert.equal(pastEvents.length, 0);
+      assert.equal((await bob.store.client.getMyEvents()).length, 0);
+      const revisions = await bob.store.client.getAllEventRevisions(
+        event.actionHash
+      );
+      assert.equal(revisions.length, 1);
+    },
+    true,
+    { timeout: 60_000 }
+  );
+});
+
+test('event: get upcoming events in range', async t => {
+  await runScenario(
+    async scenario => {
+      const { alice, bob } = await setup(scenario);
+
+      const now = Date.now() * 1000;
+      const day = 24 * 60 * 60 * 1000 * 1000;
+
+      for (const days of [1, 2, 10]) {
+        await alice.store.client.createEvent(
+          await sampleEvent(alice.store, {
+            time: {
+              type: 'Unique',
+              start_time: now + days * day,
+              end_time: now + days * day + 60 * 60 * 1000 * 1000,
+            },
+          })
+        );
+      }
+
+      await waitAndDhtSync([alice.player, bob.player]);
+
+      let page = await bob.store.client.getUpcomingEventsInRange(
+        now,
+        now + 5 * day,
+        1
+      );
+      assert.equal(page.links.length, 1);
+      assert.ok(page.next_cursor);
+
+      page = await bob.store.client.getUpcomingEventsInRange(
+        now,
+        now + 5 * day,
+        1,
+        page.next_cursor
+      );
+      assert.equal(page.links.length, 1);
+      assert.notOk(page.next_cursor);
+
+      page = await bob.store.client.getUpcomingEventsInRange(
+        now,
+        now + 20 * day,
+        10
+      );
+      assert.equal(page.links.length, 3);
+      assert.notOk(page.next_cursor);
+
+      const upcomingEvents = await toPromise(bob.store.allUpcomingEvents);
+      assert.equal(upcomingEvents.length, 3);
+    },
+    true,
+    { timeout: 60_000 }
+  );
+});
+
+test('event: get events near', async t => {
+  await runScenario(
+    async scenario => {
+      const { alice, bob } = await setup(scenario);
+
+      const now = Date.now() * 1000;
+      const day = 24 * 60 * 60 * 1000 * 1000;
+      const hour = 60 * 60 * 1000 * 1000;
+      const barcelona = { latitude: 41.3874, longitude: 2.1686 };
+      const girona = { latitude: 41.9794, longitude: 2.8214 };
+
+      const createEventAt = async (
+        coordinates: Coordinates | undefined,
+        days: number
+      ) =>
+        alice.store.client.createEvent(
+          await sampleEvent(alice.store, {
+            location: {
+              type: 'Physical',
+              address: 'Somewhere in Catalonia',
+              coordinates,
+            },
+            time: {
+              type: 'Unique',
+              start_time: now + days * day,
+              end_time: now + days * day + hour,
+            },
+          })
+        );
+      const inBarcelona = await createEventAt(barcelona, 2);
+      const inGirona = await createEventAt(girona, 1);
+      await createEventAt(undefined, 1);
+
+      await waitAndDhtSync([alice.player, bob.player]);
+
+      const nextWeek = { start: now, end: now + 7 * day };
+      let near = await bob.store.client.getEventsNear(barcelona, 10, nextWeek);
+      assert.deepEqual(
+        near.map(link => link.target),
+        [inBarcelona.actionHash]
+      );
+
+      // Girona is about 85km away from Barcelona, and its event starts earlier
+      near = await bob.store.client.getEventsNear(barcelona, 100, nextWeek);
+      assert.deepEqual(
+        near.map(link => link.target),
+        [inGirona.actionHash, inBarcelona.actionHash]
+      );
+
+      near = await bob.store.client.getEventsNear(barcelona, 100, {
+        start: now + 3 * day,
+        end: now + 7 * day,
+      });
+      assert.equal(near.length, 0);
+
+      // Moving the event moves it in the index
+      await alice.store.client.updateEvent(
+        inBarcelona.actionHash,
+        inBarcelona.actionHash,
+        {
+          ...inBarcelona.entry,
+          location: {
+            type: 'Physical',
+            address: 'Somewhere else in Catalonia',
+            coordinates: girona,
+          },
+        }
+      );
+
+      await waitAndDhtSync([alice.player, bob.player]);
+
+      near = await bob.store.client.getEventsNear(barcelona, 10, nextWeek);
+      assert.equal(near.length, 0);
+      near = await bob.store.client.getEventsNear(girona, 10, nextWeek);
+      assert.equal(near.length, 2);
+    },
+    true,
+    { timeout: 60_000 }
+  );
+});
+
+test('event: paginate upcoming events', async t => {
+  await runScenario(
+    async scenario => {
+      const { alice, bob } = await setup(scenario);
+
+      const now = Date.now() * 1000;
+      const day = 24 * 60 * 60 * 1000 * 1000;
+
+      const eventsHashes = [];
+      for (const days of [3, 1, 2]) {
+        const event = await alice.store.client.createEvent(
+          await sampleEvent(alice.store, {
+            time: {
+              type: 'Unique',
+              start_time: now + days * day,
+              end_time: now + days * day + 60 * 60 * 1000 * 1000,
+            },
+          })
+        );
+        eventsHashes.push(event.actionHash);
+      }
+
+      await waitAndDhtSync([alice.player, bob.player]);
+
+      let page = await bob.store.client.getUpcomingEventsPage(2);
+      assert.equal(page.links.length, 2);
+      assert.deepEqual(page.links[0].target, eventsHashes[1]);
+      assert.deepEqual(page.links[1].target, eventsHashes[2]);
+      assert.ok(page.next_cursor);
+
+      page = await bob.store.client.getUpcomingEventsPage(2, page.next_cursor);
+      assert.equal(page.links.length, 1);
+      assert.deepEqual(page.links[0].target, eventsHashes[0]);
+      assert.notOk(page.next_cursor);
+
+      // Empty pages would never advance the cursor
+      await expect(
+        bob.store.client.getUpcomingEventsPage(0)
+      ).rejects.toThrow();
+    },
+    true,
+    { timeout: 60_000 }
+  );
+});
+
+test('event: batch fetch the latest events and hydrated pages', async t => {
+  await runScenario(
+    async scenario => {
+      const { alice, bob } = await setup(scenario);
+
+      const now = Date.now() * 1000;
+      const day = 24 * 60 * 60 * 1000 * 1000;
+
+      const events = [];
+      for (const days of [1, 2]) {
+        events.push(
+          await alice.store.client.createEvent(
+            await sampleEvent(alice.store, {
+              title: `Event in ${days} days`,
+              time: {
+                type: 'Unique',
+                start_time: now + days * day,
+                end_time: now + days * day + 60 * 60 * 1000 * 1000,
+              },
+            })
+          )
+        );
+      }
+      await alice.store.client.updateEvent(
+        events[0].actionHash,
+        events[0].actionHash,
+        {
+          ...events[0].entry,
+          title: 'Updated event',
+        }
+      );
+      await waitAndDhtSync([alice.player, bob.player]);
+
+      await bob.store.client.addMyselfAsInterested(events[1].actionHash);
+      await waitAndDhtSync([alice.player, bob.player]);
+
+      const latest = await bob.store.client.getLatestEvents(
+        events.map(e => e.actionHash)
+      );
+      assert.equal(latest.length, 2);
+      assert.equal(latest[0]!.entry.title, 'Updated event');
+      assert.equal(latest[1]!.entry.title, 'Event in 2 days');
+
+      let page = await bob.store.client.getUpcomingEventsPage(2);
+      assert.notOk(page.hydrated);
+
+      page = await bob.store.client.getUpcomingEventsPage(2, undefined, true);
+      assert.equal(page.hydrated!.length, 2);
+      assert.deepEqual(page.hydrated![0].original_hash, events[0].actionHash);
+      assert.equal(page.hydrated![0].interested_count, 0);
+      assert.equal(page.hydrated![1].interested_count, 1);
+      assert.deepEqual(page.hydrated![1].status, {
+        type: 'Event',
+        status: 'Upcoming',
+      });
+      assert.equal(page.hydrated![0].latest?.type, 'Live');
+
+      // Deleting an event takes it out of the hydrated pages as well
+      await alice.store.client.deleteEvent(events[0].actionHash);
+      await waitAndDhtSync([alice.player, bob.player]);
+
+      page = await bob.store.client.getUpcomingEventsPage(2, undefined, true);
+      assert.equal(page.links.length, 1);
+      assert.equal(page.hydrated!.length, 1);
+      assert.deepEqual(page.hydrated![0].original_hash, events[1].actionHash);
+      assert.equal(page.hydrated![0].interested_count, 1);
+      assert.equal(page.hydrated![0].latest?.type, 'Live');
+    },
+    true,
+    { timeout: 60_000 }
+  );
+});
+
+test('event: delete removes it from every collection', async t => {
+  await runScenario(
+    async scenario => {
+      const { alice, bob } = await setup(scenario);
+
+      const event = await alice.store.client.createEvent(
+        await sampleEvent(alice.store, {
+          title: 'Test Event',
+          tags: ['testing'],
+        })
+      );
+      await waitAndDhtSync([alice.player, bob.player]);
+
+      await bob.store.client.addMyselfAsInterested(event.actionHash);
+      await bob.store.client.rsvp(event.actionHash, 'Going');
+      await waitAndDhtSync([alice.player, bob.player]);
+      await alice.store.client.markPresent(
+        event.actionHash,
+        bob.player.agentPubKey
+      );
+      await waitAndDhtSync([alice.player, bob.player]);
+
+      // Only the hosts can delete an event
+      await expect(
+        bob.store.client.deleteEvent(event.actionHash)
+      ).rejects.toThrow();
+
+      await alice.store.client.deleteEvent(event.actionHash);
+      await waitAndDhtSync([alice.player, bob.player]);
+
+      const upcomingEvents = await bob.store.client.getAllUpcomingEvents();
+      assert.equal(upcomingEvents.length, 0);
+      const tagged = await bob.store.client.getEventsByTag('testing');
+      assert.equal(tagged.length, 0);
+      const found = await bob.store.client.searchEvents('test');
+      assert.equal(found.length, 0);
+      const interested = await bob.store.client.getInterestedIn(
+        event.actionHash
+      );
+      assert.equal(interested.length, 0);
+      await expect(
+        bob.store.client.getEventRsvps(event.actionHash)
+      ).rejects.toThrow();
+      const attendees = await bob.store.client.getAttendees(event.actionHash);
+      assert.equal(attendees.length, 0);
+      assert.equal((await bob.store.client.getMyEvents()).length, 0);
+      assert.equal((await alice.store.client.getMyEvents()).length, 0);
+
+      const latest = await bob.store.client.getLatestEventRevision(
+        event.actionHash
+      );
+      assert.equal(latest?.type, 'Deleted');
+      assert.notOk(await bob.store.client.getLatestEvent(event.actionHash));
+
+      // A deleted event can't be updated
+      await expect(
+        alice.store.client.updateEvent(event.actionHash, event.actionHash, {
+          ...event.entry,
+          title: 'Still here',
+        })
+      ).rejects.toThrow();
+    },
+    true,
+    { timeout: 60_000 }
+  );
+});
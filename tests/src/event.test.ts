@@ -1,11 +1,15 @@
-import { test, assert } from 'vitest';
+import { test, assert, expect } from 'vitest';
 
 import { runScenario, dhtSync } from '@holochain/tryorama';
+import { fakeActionHash, fakeEntryHash } from '@holochain/client';
 import { cleanNodeDecoding } from '@holochain-open-dev/utils/dist/clean-node-decoding.js';
 import { toPromise } from '@holochain-open-dev/stores';
+import { decode } from '@msgpack/msgpack';
 import {
+  readAndAssertHostInvitation,
   readAndAssertNotification,
   sampleEvent,
+  sampleProposal,
   setup,
   waitAndDhtSync,
 } from './utils.js';
@@ -20,13 +24,18 @@ test('create and update event', async t => {
       const event = await alice.store.client.createEvent(
         await sampleEvent(alice.store, {
           title: 'Cool Event',
-          hosts: [bob.player.agentPubKey],
+          hosts: [alice.player.agentPubKey, bob.player.agentPubKey],
         })
       );
       assert.ok(event);
 
       await waitAndDhtSync([alice.player, bob.player]);
 
+      await readAndAssertHostInvitation(
+        bob.store,
+        'Cool Event',
+        'Du blev inbjuden att vara värd för detta event.'
+      );
       await readAndAssertNotification(
         bob.store,
         'Cool Event',
@@ -47,20 +56,24 @@ test('create and update event', async t => {
           'Lorem ipsum dolor sit amet, consectetur adipiscing elit. Sed nec eros quis enim hendrerit aliquet.',
         description:
           'Lorem ipsum dolor sit amet, consectetur adipiscing elit. Sed nec eros quis enim hendrerit aliquet.',
-        image: Buffer.from(
-          new Uint8Array([
-            132, 33, 36, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
-            0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
-          ])
-        ),
-        location:
-          'Lorem ipsum dolor sit amet, consectetur adipiscing elit. Sed nec eros quis enim hendrerit aliquet.',
+        image: event.entry.image,
+        location: {
+          type: 'Physical',
+          address:
+            'Lorem ipsum dolor sit amet, consectetur adipiscing elit. Sed nec eros quis enim hendrerit aliquet.',
+          coordinates: undefined,
+        },
         time: {
           type: 'Unique',
           start_time: 1665499212508,
           end_time: 1665499212508,
         },
-        cost: 'Lorem ipsum dolor sit amet, consectetur adipiscing elit. Sed nec eros quis enim hendrerit aliquet.',
+        cost: {
+          type: 'Fixed',
+          price: { amount: 1000, currency: 'EUR' },
+        },
+        tags: ['music'],
+        capacity: undefined,
       };
 
       let updatedEvent = await alice.store.client.updateEvent(
@@ -95,20 +108,24 @@ test('create and update event', async t => {
           'Lorem ipsum dolor sit amet, consectetur adipiscing elit. Sed nec eros quis enim hendrerit aliquet.',
         description:
           'Lorem ipsum dolor sit amet, consectetur adipiscing elit. Sed nec eros quis enim hendrerit aliquet.',
-        image: Buffer.from(
-          new Uint8Array([
-            132, 33, 36, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
-            0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
-          ])
-        ),
-        location:
-          'Lorem ipsum dolor sit amet, consectetur adipiscing elit. Sed nec eros quis enim hendrerit aliquet.',
+        image: event.entry.image,
+        location: {
+          type: 'Physical',
+          address:
+            'Lorem ipsum dolor sit amet, consectetur adipiscing elit. Sed nec eros quis enim hendrerit aliquet.',
+          coordinates: undefined,
+        },
         time: {
           type: 'Unique',
           start_time: 1665499212508,
           end_time: 1665499212508,
         },
-        cost: 'Lorem ipsum dolor sit amet, consectetur adipiscing elit. Sed nec eros quis enim hendrerit aliquet.',
+        cost: {
+          type: 'Fixed',
+          price: { amount: 1000, currency: 'EUR' },
+        },
+        tags: [],
+        capacity: undefined,
         from_proposal: undefined,
         hosts: event.entry.hosts,
       };
@@ -139,3 +156,1202 @@ test('create and update event', async t => {
     { timeout: 30000 }
   );
 });
+
+test('reject invalid events', async t => {
+  await runScenario(
+    async scenario => {
+      const { alice, bob } = await setup(scenario);
+
+      const event = await sampleEvent(alice.store);
+
+      await expect(
+        alice.store.client.createEvent({ ...event, title: '  ' })
+      ).rejects.toThrow();
+
+      await expect(
+        alice.store.client.createEvent({
+          ...event,
+          time: {
+            type: 'Unique',
+            start_time: Date.now() * 1000,
+            end_time: Date.now() * 1000 - 60 * 60 * 1000 * 1000,
+          },
+        })
+      ).rejects.toThrow();
+
+      // Alice can't create an event that only Bob hosts
+      await expect(
+        alice.store.client.createEvent({
+          ...event,
+          hosts: [bob.player.agentPubKey],
+        })
+      ).rejects.toThrow();
+
+      await expect(
+        alice.store.client.createEvent({
+          ...event,
+          call_to_action_hash: await fakeActionHash(),
+        })
+      ).rejects.toThrow();
+
+      await expect(
+        alice.store.client.createEvent({
+          ...event,
+          image: await fakeEntryHash(),
+        })
+      ).rejects.toThrow();
+
+      const created = await alice.store.client.createEvent(event);
+      assert.ok(created);
+    },
+    true,
+    { timeout: 30000 }
+  );
+});
+
+test('only hosts can update and delete events and proposals', async t => {
+  await runScenario(
+    async scenario => {
+      const { alice, bob } = await setup(scenario);
+
+      const event = await alice.store.client.createEvent(
+        await sampleEvent(alice.store, { title: 'Book club' })
+      );
+      const proposal = await alice.store.client.createProposal(
+        await sampleProposal(alice.store, { title: 'Book club' })
+      );
+      await waitAndDhtSync([alice.player, bob.player]);
+
+      await expect(
+        bob.store.client.updateEvent(event.actionHash, event.actionHash, {
+          ...event.entry,
+          title: 'Not a book club',
+        })
+      ).rejects.toThrow();
+      await expect(
+        bob.store.client.deleteEvent(event.actionHash)
+      ).rejects.toThrow();
+
+      await expect(
+        bob.store.client.updateProposal(
+          proposal.actionHash,
+          proposal.actionHash,
+          {
+            ...proposal.entry,
+            title: 'Not a book club',
+          }
+        )
+      ).rejects.toThrow();
+      await expect(
+        bob.store.client.deleteProposal(proposal.actionHash)
+      ).rejects.toThrow();
+
+      // Adding themselves as a host doesn't make Bob a host either
+      await expect(
+        bob.store.client.updateEvent(event.actionHash, event.actionHash, {
+          ...event.entry,
+          hosts: [...event.entry.hosts, bob.player.agentPubKey],
+        })
+      ).rejects.toThrow();
+
+      const latest = await bob.store.client.getLatestEvent(event.actionHash);
+      assert.equal(latest!.entry.title, 'Book club');
+    },
+    true,
+    { timeout: 30000 }
+  );
+});
+
+test('expand the occurrences of a recurring event', async t => {
+  await runScenario(
+    async scenario => {
+      const { alice, bob } = await setup(scenario);
+
+      const day = 24 * 60 * 60 * 1000 * 1000;
+      const week = 7 * day;
+      // Next Thursday at 18:00 UTC
+      const today = new Date();
+      today.setUTCHours(18, 0, 0, 0);
+      today.setUTCDate(
+        today.getUTCDate() + ((4 - today.getUTCDay() + 7) % 7 || 7)
+      );
+      const firstThursday = today.getTime() * 1000;
+
+      // Every second Thursday except the second one, five times
+      const event = await alice.store.client.createEvent(
+        await sampleEvent(alice.store, {
+          time: {
+            type: 'Recurring',
+            start_time: firstThursday,
+            event_duration: { secs: 2 * 60 * 60, nanos: 0 },
+            recurrence: {
+              frequency: 'Weekly',
+              interval: 2,
+              by_weekday: ['Thursday'],
+              by_nth_weekday: [],
+              by_month_day: [],
+              until: undefined,
+              count: 5,
+              exclusions: [firstThursday + 2 * week],
+            },
+          },
+        })
+      );
+
+      await waitAndDhtSync([alice.player, bob.player]);
+
+      const occurrences = await bob.store.client.getEventOccurrences(
+        event.actionHash,
+        firstThursday - day,
+        firstThursday + 20 * week
+      );
+      assert.deepEqual(
+        occurrences.map(o => o.start_time),
+        [0, 4, 6, 8].map(weeks => firstThursday + weeks * week)
+      );
+      assert.equal(
+        occurrences[0].end_time,
+        firstThursday + 2 * 60 * 60 * 1000 * 1000
+      );
+
+      // Only the occurrences overlapping with the window
+      const inWindow = await bob.store.client.getEventOccurrences(
+        event.actionHash,
+        firstThursday + 3 * week,
+        firstThursday + 5 * week
+      );
+      assert.deepEqual(
+        inWindow.map(o => o.start_time),
+        [firstThursday + 4 * week]
+      );
+    },
+    true,
+    { timeout: 30000 }
+  );
+});
+
+test('create events with structured locations', async t => {
+  await runScenario(
+    async scenario => {
+      const { alice, bob } = await setup(scenario);
+
+      const event = await alice.store.client.createEvent(
+        await sampleEvent(alice.store, {
+          location: {
+            type: 'Hybrid',
+            address: 'Carrer de Mallorca 401, Barcelona',
+            coordinates: { latitude: 41.4036, longitude: 2.1744 },
+            url: 'https://meet.example.org/dinner',
+          },
+        })
+      );
+
+      await waitAndDhtSync([alice.player, bob.player]);
+
+      const record = await bob.store.client.getLatestEvent(event.actionHash);
+      assert.deepEqual(record?.entry.location, event.entry.location);
+
+      // Coordinates out of range are rejected
+      await expect(
+        alice.store.client.createEvent(
+          await sampleEvent(alice.store, {
+            location: {
+              type: 'Physical',
+              address: 'Nowhere',
+              coordinates: { latitude: 91, longitude: 0 },
+            },
+          })
+        )
+      ).rejects.toThrow();
+
+      // Only proposals can leave their location to be announced
+      await expect(
+        alice.store.client.createEvent(
+          await sampleEvent(alice.store, {
+            location: { type: 'ToBeAnnounced' },
+          })
+        )
+      ).rejects.toThrow();
+      const proposal = await alice.store.client.createProposal(
+        await sampleProposal(alice.store, {
+          location: { type: 'ToBeAnnounced' },
+        })
+      );
+      assert.deepEqual(proposal.entry.location, { type: 'ToBeAnnounced' });
+    },
+    true,
+    { timeout: 30000 }
+  );
+});
+
+test('create events with structured and legacy costs', async t => {
+  await runScenario(
+    async scenario => {
+      const { alice } = await setup(scenario);
+
+      const event = await alice.store.client.createEvent(
+        await sampleEvent(alice.store, {
+          cost: {
+            type: 'Tiered',
+            tiers: [
+              { name: 'Early bird', price: { amount: 800, currency: 'EUR' } },
+              { name: 'Regular', price: { amount: 1200, currency: 'EUR' } },
+            ],
+          },
+        })
+      );
+      assert.equal(event.entry.cost?.type, 'Tiered');
+
+      // Costs written as free text by older clients are migrated
+      const legacyCost = async (cost: string) => {
+        const record = await alice.store.client.createEvent(
+          await sampleEvent(alice.store, { cost: cost as any })
+        );
+        return record.entry.cost;
+      };
+      assert.deepEqual(await legacyCost('Free'), { type: 'Free' });
+      assert.deepEqual(await legacyCost('12,50 EUR'), {
+        type: 'Fixed',
+        price: { amount: 1250, currency: 'EUR' },
+      });
+      assert.deepEqual(await legacyCost('Bring some food to share'), {
+        type: 'Other',
+        description: 'Bring some food to share',
+      });
+      assert.equal(await legacyCost(''), undefined);
+
+      await expect(
+        alice.store.client.createEvent(
+          await sampleEvent(alice.store, {
+            cost: {
+              type: 'Fixed',
+              price: { amount: 1000, currency: 'euros' },
+            },
+          })
+        )
+      ).rejects.toThrow();
+      await expect(
+        alice.store.client.createEvent(
+          await sampleEvent(alice.store, {
+            cost: {
+              type: 'PayWhatYouCan',
+              currency: 'EUR',
+              suggested_min: 2000,
+              suggested_max: 1000,
+            },
+          })
+        )
+      ).rejects.toThrow();
+    },
+    true,
+    { timeout: 30000 }
+  );
+});
+
+test('tag events and find them by tag', async t => {
+  await runScenario(
+    async scenario => {
+      const { alice, bob } = await setup(scenario);
+
+      const event = await alice.store.client.createEvent(
+        await sampleEvent(alice.store, {
+          tags: ['music', 'repair-café'],
+        })
+      );
+      await waitAndDhtSync([alice.player, bob.player]);
+
+      // Bob searches with a tag that is not normalized
+      let links = await bob.store.client.getEventsByTag('Music');
+      assert.equal(links.length, 1);
+      assert.deepEqual(links[0].target, event.actionHash);
+      links = await bob.store.client.getEventsByTag('Repair Café');
+      assert.equal(links.length, 1);
+
+      // Alice removes the "music" tag
+      await alice.store.client.updateEvent(
+        event.actionHash,
+        event.actionHash,
+        {
+          ...event.entry,
+          tags: ['repair-café'],
+        }
+      );
+      await waitAndDhtSync([alice.player, bob.player]);
+
+      links = await bob.store.client.getEventsByTag('music');
+      assert.equal(links.length, 0);
+      links = await bob.store.client.getEventsByTag('repair-café');
+      assert.equal(links.length, 1);
+
+      // Tags must be normalized before they are stored
+      await expect(
+        alice.store.client.createEvent(
+          await sampleEvent(alice.store, {
+            tags: ['Live Music'],
+          })
+        )
+      ).rejects.toThrow();
+    },
+    true,
+    { timeout: 30000 }
+  );
+});
+
+test('search events by the words of their title and description', async t => {
+  await runScenario(
+    async scenario => {
+      const { alice, bob } = await setup(scenario);
+
+      const event = await alice.store.client.createEvent(
+        await sampleEvent(alice.store, {
+          title: 'The Repair Café',
+          description: 'Bring your broken toasters and bicycles',
+        })
+      );
+      await alice.store.client.createEvent(
+        await sampleEvent(alice.store, {
+          title: 'Knitting circle',
+          description: 'Bring your own wool',
+        })
+      );
+      await waitAndDhtSync([alice.player, bob.player]);
+
+      // Every word of the query must be the beginning of a word of the event
+      let links = await bob.store.client.searchEvents('repair caf');
+      assert.equal(links.length, 1);
+      assert.deepEqual(links[0].target, event.actionHash);
+      links = await bob.store.client.searchEvents('BRING');
+      assert.equal(links.length, 2);
+      links = await bob.store.client.searchEvents('repair wool');
+      assert.equal(links.length, 0);
+
+      links = await bob.store.client.searchEvents('toasters', 'Upcoming');
+      assert.equal(links.length, 1);
+      links = await bob.store.client.searchEvents('toasters', 'Past');
+      assert.equal(links.length, 0);
+
+      // Alice renames the event, which removes the stale words from the index
+      await alice.store.client.updateEvent(
+        event.actionHash,
+        event.actionHash,
+        {
+          ...event.entry,
+          title: 'The Fix-it Café',
+        }
+      );
+      await waitAndDhtSync([alice.player, bob.player]);
+
+      links = await bob.store.client.searchEvents('repair');
+      assert.equal(links.length, 0);
+      links = await bob.store.client.searchEvents('fix café');
+      assert.equal(links.length, 1);
+    },
+    true,
+    { timeout: 30000 }
+  );
+});
+
+test('create private events for invitees only', async t => {
+  await runScenario(
+    async scenario => {
+      const { alice, bob } = await setup(scenario);
+
+      // Bob publishes his encryption key the first time he uses the app
+      assert.equal((await bob.store.client.getMyInvitations()).length, 0);
+      await waitAndDhtSync([alice.player, bob.player]);
+
+      const event = await sampleEvent(alice.store, {
+        title: 'Dinner at home',
+        location: {
+          type: 'Physical',
+          address: 'My secret address',
+          coordinates: undefined,
+        },
+      });
+      const privateEvent = await alice.store.client.createPrivateEvent(event, [
+        bob.player.agentPubKey,
+      ]);
+      await waitAndDhtSync([alice.player, bob.player]);
+
+      // Bob gets invited, and can decrypt the event
+      const invitations = await bob.store.client.getMyInvitations();
+      assert.equal(invitations.length, 1);
+      assert.deepEqual(invitations[0].target, privateEvent.actionHash);
+      const decrypted = await bob.store.client.getPrivateEvent(
+        privateEvent.actionHash
+      );
+      assert.equal(decrypted?.event.title, 'Dinner at home');
+      assert.deepEqual(decrypted?.event.location, event.location);
+
+      // Private events are not part of the global collections
+      assert.equal((await bob.store.client.getAllUpcomingEvents()).length, 0);
+      assert.equal((await bob.store.client.searchEvents('dinner')).length, 0);
+
+      // Updates are encrypted with a new key for the current invitees
+      await alice.store.client.updatePrivateEvent(
+        privateEvent.actionHash,
+        privateEvent.actionHash,
+        { ...event, title: 'Dinner and board games' },
+        [bob.player.agentPubKey]
+      );
+      await waitAndDhtSync([alice.player, bob.player]);
+      const updated = await bob.store.client.getPrivateEvent(
+        privateEvent.actionHash
+      );
+      assert.equal(updated?.event.title, 'Dinner and board games');
+
+      // Only the hosts and the invitees can be given a key
+      await expect(
+        alice.store.client.createPrivateEvent(event, [])
+      ).rejects.toThrow();
+    },
+    true,
+    { timeout: 30000 }
+  );
+});
+
+test('rsvp to events with limited capacity', async t => {
+  await runScenario(
+    async scenario => {
+      const { alice, bob } = await setup(scenario);
+
+      const event = await alice.store.client.createEvent(
+        await sampleEvent(alice.store, {
+          title: 'Repair workshop',
+          capacity: 1,
+        })
+      );
+
+      await alice.store.client.rsvp(event.actionHash, 'Going');
+      await waitAndDhtSync([alice.player, bob.player]);
+
+      // The only seat is taken, so Bob is waitlisted
+      await bob.store.client.rsvp(event.actionHash, 'Going');
+      await waitAndDhtSync([alice.player, bob.player]);
+
+      let rsvps = await bob.store.client.getEventRsvps(event.actionHash);
+      assert.equal(rsvps.going.length, 1);
+      assert.deepEqual(rsvps.going[0].target, alice.player.agentPubKey);
+      assert.equal(rsvps.waitlist.length, 1);
+      assert.deepEqual(rsvps.waitlist[0].target, bob.player.agentPubKey);
+
+      // Alice declines, which promotes Bob and alerts him
+      await alice.store.client.rsvp(event.actionHash, 'NotGoing');
+      await waitAndDhtSync([alice.player, bob.player]);
+
+      rsvps = await bob.store.client.getEventRsvps(event.actionHash);
+      assert.equal(rsvps.going.length, 1);
+      assert.deepEqual(rsvps.going[0].target, bob.player.agentPubKey);
+      assert.equal(rsvps.waitlist.length, 0);
+      assert.equal(rsvps.not_going.length, 1);
+
+      await readAndAssertNotification(
+        bob.store,
+        'Repair workshop',
+        'En plats blev ledig: du är nu bekräftad som deltagare!'
+      );
+
+      // A capacity of zero is rejected
+      await expect(
+        alice.store.client.createEvent(
+          await sampleEvent(alice.store, { capacity: 0 })
+        )
+      ).rejects.toThrow();
+    },
+    true,
+    { timeout: 30000 }
+  );
+});
+
+test('bring guests to events and count them in the attendance', async t => {
+  await runScenario(
+    async scenario => {
+      const { alice, bob } = await setup(scenario);
+
+      const event = await alice.store.client.createEvent(
+        await sampleEvent(alice.store, {
+          title: 'Potluck',
+          capacity: 4,
+        })
+      );
+
+      // Alice brings her two kids
+      const rsvp = await alice.store.client.rsvp(
+        event.actionHash,
+        'Going',
+        2,
+        ['Ada', 'Grace']
+      );
+      assert.deepEqual(rsvp.entry.guest_names, ['Ada', 'Grace']);
+      await waitAndDhtSync([alice.player, bob.player]);
+
+      // Bob's party of two doesn't fit in the only seat left
+      await bob.store.client.addMyselfAsInterested(event.actionHash);
+      await bob.store.client.rsvp(event.actionHash, 'Going', 1);
+      await waitAndDhtSync([alice.player, bob.player]);
+
+      let summary = await bob.store.client.getAttendanceSummary(
+        event.actionHash
+      );
+      assert.deepEqual(summary, {
+        capacity: 4,
+        going: 3,
+        waitlist: 2,
+        maybe: 0,
+        not_going: 0,
+        interested: 1,
+      });
+
+      // Alice leaves one of her kids at home, which moves her party behind Bob's,
+      // and now both fit
+      await alice.store.client.rsvp(event.actionHash, 'Going', 1, ['Ada']);
+      await waitAndDhtSync([alice.player, bob.player]);
+
+      summary = await alice.store.client.getAttendanceSummary(
+        event.actionHash
+      );
+      assert.equal(summary.going, 4);
+      assert.equal(summary.waitlist, 0);
+
+      await readAndAssertNotification(
+        bob.store,
+        'Potluck',
+        'En plats blev ledig: du är nu bekräftad som deltagare!'
+      );
+
+      // Naming the guest keeps Bob's place at the front of the queue
+      let rsvps = await bob.store.client.getEventRsvps(event.actionHash);
+      const bobLink = rsvps.going[0];
+      assert.deepEqual(bobLink.target, bob.player.agentPubKey);
+      const named = await bob.store.client.rsvp(event.actionHash, 'Going', 1, [
+        'Linus',
+      ]);
+      await waitAndDhtSync([alice.player, bob.player]);
+
+      rsvps = await alice.store.client.getEventRsvps(event.actionHash);
+      assert.deepEqual(rsvps.going[0].create_link_hash, bobLink.create_link_hash);
+      const latest = await alice.store.client.getRsvp(
+        (decode(bobLink.tag) as any).rsvp_hash
+      );
+      assert.deepEqual(latest?.entry.guest_names, ['Linus']);
+      assert.deepEqual(latest?.actionHash, named.actionHash);
+
+      // Names must be of actual guests
+      await expect(
+        alice.store.client.rsvp(event.actionHash, 'Going', 0, ['Ada'])
+      ).rejects.toThrow();
+    },
+    true,
+    { timeout: 30000 }
+  );
+});
+
+test('check attendees in at the event', async t => {
+  await runScenario(
+    async scenario => {
+      const { alice, bob } = await setup(scenario);
+
+      const event = await alice.store.client.createEvent(
+        await sampleEvent(alice.store, {
+          title: 'Tree planting',
+        })
+      );
+      await waitAndDhtSync([alice.player, bob.player]);
+
+      // Only the hosts can issue check-in codes or mark attendees as present
+      const forgedCode = await bob.store.client.createCheckInCode(
+        event.actionHash
+      );
+      await expect(
+        bob.store.client.checkIn(event.actionHash, forgedCode)
+      ).rejects.toThrow();
+      await expect(
+        bob.store.client.markPresent(event.actionHash, bob.player.agentPubKey)
+      ).rejects.toThrow();
+
+      // Bob checks himself in with the code that Alice shows at the event
+      const code = await alice.store.client.createCheckInCode(event.actionHash);
+      const attendance = await bob.store.client.checkIn(event.actionHash, code);
+      assert.equal(attendance.entry.check_in.type, 'WithCode');
+
+      // Alice marks herself as present
+      await alice.store.client.markPresent(
+        event.actionHash,
+        alice.player.agentPubKey
+      );
+      await waitAndDhtSync([alice.player, bob.player]);
+
+      const attendees = await alice.store.client.getAttendees(event.actionHash);
+      assert.equal(attendees.length, 2);
+      assert.deepEqual(attendees[0].target, bob.player.agentPubKey);
+      assert.deepEqual(attendees[1].target, alice.player.agentPubKey);
+    },
+    true,
+    { timeout: 30000 }
+  );
+});
+
+test('co-hosts accept to host events before acting as hosts', async t => {
+  await runScenario(
+    async scenario => {
+      const { alice, bob } = await setup(scenario);
+
+      const event = await alice.store.client.createEvent(
+        await sampleEvent(alice.store, {
+          title: 'Book club',
+          hosts: [alice.player.agentPubKey, bob.player.agentPubKey],
+        })
+      );
+      await waitAndDhtSync([alice.player, bob.player]);
+
+      await readAndAssertHostInvitation(
+        bob.store,
+        'Book club',
+        'Du blev inbjuden att vara värd för detta event.'
+      );
+
+      let hosts = await bob.store.client.getAcceptedHosts(event.actionHash);
+      assert.deepEqual(hosts, [alice.player.agentPubKey]);
+      let hostedEvents = await alice.store.client.getEventsIHost();
+      assert.equal(hostedEvents.length, 1);
+      hostedEvents = await bob.store.client.getEventsIHost();
+      assert.equal(hostedEvents.length, 0);
+
+      // Bob can't act as a host until he accepts
+      const update: Event = { ...event.entry, title: 'Reading club' };
+      await expect(
+        bob.store.client.updateEvent(event.actionHash, event.actionHash, update)
+      ).rejects.toThrow();
+
+      await bob.store.client.acceptHostInvitation(event.actionHash);
+      await waitAndDhtSync([alice.player, bob.player]);
+
+      hosts = await alice.store.client.getAcceptedHosts(event.actionHash);
+      assert.deepEqual(hosts, [
+        alice.player.agentPubKey,
+        bob.player.agentPubKey,
+      ]);
+      hostedEvents = await bob.store.client.getEventsIHost();
+      assert.equal(hostedEvents.length, 1);
+      assert.deepEqual(hostedEvents[0].target, event.actionHash);
+
+      const updatedEvent = await bob.store.client.updateEvent(
+        event.actionHash,
+        event.actionHash,
+        update
+      );
+      assert.equal(updatedEvent.entry.title, 'Reading club');
+    },
+    true,
+    { timeout: 30000 }
+  );
+});
+
+test('co-hosts merge their concurrent updates', async t => {
+  await runScenario(
+    async scenario => {
+      const { alice, bob } = await setup(scenario);
+
+      const event = await alice.store.client.createEvent(
+        await sampleEvent(alice.store, {
+          title: 'Book club',
+          hosts: [alice.player.agentPubKey, bob.player.agentPubKey],
+        })
+      );
+      await waitAndDhtSync([alice.player, bob.player]);
+      await readAndAssertHostInvitation(
+        bob.store,
+        'Book club',
+        'Du blev inbjuden att vara värd för detta event.'
+      );
+      await bob.store.client.acceptHostInvitation(event.actionHash);
+      await waitAndDhtSync([alice.player, bob.player]);
+
+      // Both hosts update the same revision without seeing each other's update
+      await alice.store.client.updateEvent(event.actionHash, event.actionHash, {
+        ...event.entry,
+        title: 'Reading club',
+      });
+      await bob.store.client.updateEvent(event.actionHash, event.actionHash, {
+        ...event.entry,
+        description: 'We read together',
+      });
+      await waitAndDhtSync([alice.player, bob.player]);
+
+      let heads = await alice.store.client.getEventRevisionHeads(
+        event.actionHash
+      );
+      assert.equal(heads.length, 2);
+
+      // Both agents agree on the latest revision
+      const aliceLatest = await alice.store.client.getLatestEvent(
+        event.actionHash
+      );
+      const bobLatest = await bob.store.client.getLatestEvent(event.actionHash);
+      assert.deepEqual(aliceLatest!.actionHash, bobLatest!.actionHash);
+      assert.deepEqual(aliceLatest!.actionHash, heads[0].actionHash);
+      const latest = await bob.store.client.getLatestEventRevision(
+        event.actionHash
+      );
+      assert.equal(latest?.type, 'Live');
+      assert.deepEqual((latest as any).conflicting_heads, [
+        heads[1].actionHash,
+      ]);
+
+      const merged = await bob.store.client.mergeEventRevisions(
+        event.actionHash,
+        {
+          ...event.entry,
+          title: 'Reading club',
+          description: 'We read together',
+        }
+      );
+      await waitAndDhtSync([alice.player, bob.player]);
+
+      heads = await alice.store.client.getEventRevisionHeads(event.actionHash);
+      assert.equal(heads.length, 1);
+      assert.deepEqual(heads[0].actionHash, merged.actionHash);
+      const mergedEvent = await alice.store.client.getLatestEvent(
+        event.actionHash
+      );
+      assert.equal(mergedEvent!.entry.title, 'Reading club');
+      assert.equal(mergedEvent!.entry.description, 'We read together');
+
+      // Without conflicts there is nothing to merge
+      await expect(
+        alice.store.client.mergeEventRevisions(
+          event.actionHash,
+          mergedEvent!.entry
+        )
+      ).rejects.toThrow();
+    },
+    true,
+    { timeout: 30000 }
+  );
+});
+
+test('notifications and changelog say what an update changed', async t => {
+  await runScenario(
+    async scenario => {
+      const { alice, bob } = await setup(scenario);
+
+      const hour = 60 * 60 * 1000 * 1000;
+      const start = Date.UTC(2030, 0, 15, 18, 0) * 1000;
+      const event = await alice.store.client.createEvent(
+        await sampleEvent(alice.store, {
+          title: 'Book club',
+          hosts: [alice.player.agentPubKey, bob.player.agentPubKey],
+          time: {
+            type: 'Unique',
+            start_time: start,
+            end_time: start + 2 * hour,
+          },
+        })
+      );
+      await waitAndDhtSync([alice.player, bob.player]);
+      await readAndAssertHostInvitation(
+        bob.store,
+        'Book club',
+        'Du blev inbjuden att vara värd för detta event.'
+      );
+      await readAndAssertNotification(
+        bob.store,
+        'Book club',
+        'Eventet har skapats.'
+      );
+
+      await alice.store.client.updateEvent(event.actionHash, event.actionHash, {
+        ...event.entry,
+        title: 'Reading club',
+        time: {
+          type: 'Unique',
+          start_time: start + 24 * hour,
+          end_time: start + 26 * hour,
+        },
+      });
+      await waitAndDhtSync([alice.player, bob.player]);
+
+      await readAndAssertNotification(
+        bob.store,
+        'Reading club',
+        'Titeln ändrades från "Book club" till "Reading club". Tiden ändrades från 2030-01-15 18:00 UTC till 2030-01-16 18:00 UTC.'
+      );
+
+      const changelog = await bob.store.client.getEventChangelog(
+        event.actionHash
+      );
+      assert.equal(changelog.length, 1);
+      assert.deepEqual(changelog[0].author, alice.player.agentPubKey);
+      assert.deepEqual(changelog[0].previous_revision_hash, event.actionHash);
+      assert.deepEqual(
+        changelog[0].changes.map(change => change.field),
+        ['Title', 'Time']
+      );
+    },
+    true,
+    { timeout: 30000 }
+  );
+});
+
+test('get the revisions of an event in chronological order', async t => {
+  await runScenario(
+    async scenario => {
+      const { alice, bob } = await setup(scenario);
+
+      const event = await alice.store.client.createEvent(
+        await sampleEvent(alice.store, { title: 'Revision 0' })
+      );
+      let previous = event;
+      for (const i of [1, 2, 3]) {
+        previous = await alice.store.client.updateEvent(
+          event.actionHash,
+          previous.actionHash,
+          { ...event.entry, title: `Revision ${i}` }
+        );
+      }
+      await waitAndDhtSync([alice.player, bob.player]);
+
+      const revisions = await bob.store.client.getAllEventRevisions(
+        event.actionHash
+      );
+      assert.deepEqual(
+        revisions.map(revision => revision.entry.title),
+        ['Revision 0', 'Revision 1', 'Revision 2', 'Revision 3']
+      );
+
+      const latestRevisions = await bob.store.client.getEventRevisions(
+        event.actionHash,
+        2
+      );
+      assert.deepEqual(
+        latestRevisions.map(revision => revision.entry.title),
+        ['Revision 2', 'Revision 3']
+      );
+
+      // An event has no proposal revisions
+      await expect(
+        bob.store.client.getProposalRevisions(event.actionHash)
+      ).rejects.toThrow();
+    },
+    true,
+    { timeout: 30000 }
+  );
+});
+
+test('override and show interest in single occurrences', async t => {
+  await runScenario(
+    async scenario => {
+      const { alice, bob } = await setup(scenario);
+
+      const hour = 60 * 60 * 1000 * 1000;
+      const week = 7 * 24 * hour;
+      const start = Date.now() * 1000 + week;
+
+      const event = await alice.store.client.createEvent(
+        await sampleEvent(alice.store, {
+          hosts: [alice.player.agentPubKey, bob.player.agentPubKey],
+          time: {
+            type: 'Periodic',
+            start_time: start,
+            event_duration: { secs: 2 * 60 * 60, nanos: 0 },
+            period_duration: { secs: 7 * 24 * 60 * 60, nanos: 0 },
+            ocurrences: 4,
+          },
+        })
+      );
+
+      // Durations that overflow a timestamp are rejected
+      const tooLong = { secs: Number.MAX_SAFE_INTEGER, nanos: 0 };
+      await expect(
+        alice.store.client.createEvent(
+          await sampleEvent(alice.store, {
+            time: {
+              type: 'Periodic',
+              start_time: start,
+              event_duration: tooLong,
+              period_duration: tooLong,
+              ocurrences: 4,
+            },
+          })
+        )
+      ).rejects.toThrow();
+
+      const cancellation = await alice.store.client.createOccurrenceOverride({
+        event_hash: event.actionHash,
+        revision_hash: event.actionHash,
+        occurrence_index: 1,
+        change: { type: 'Cancelled' },
+      });
+      await alice.store.client.createOccurrenceOverride({
+        event_hash: event.actionHash,
+        revision_hash: event.actionHash,
+        occurrence_index: 2,
+        change: {
+          type: 'Rescheduled',
+          start_time: start + 2 * week + hour,
+          end_time: start + 2 * week + 3 * hour,
+        },
+      });
+
+      await waitAndDhtSync([alice.player, bob.player]);
+
+      await bob.store.client.addMyselfAsInterestedInOccurrence(
+        event.actionHash,
+        2
+      );
+
+      await waitAndDhtSync([alice.player, bob.player]);
+
+      let occurrences = await bob.store.client.getEventOccurrences(
+        event.actionHash,
+        start,
+        start + 10 * week
+      );
+      assert.deepEqual(
+        occurrences.map(o => [o.index, o.start_time, o.cancelled]),
+        [
+          [0, start, false],
+          [1, start + week, true],
+          [2, start + 2 * week + hour, false],
+          [3, start + 3 * week, false],
+        ]
+      );
+
+      const interested = await alice.store.client.getInterestedInOccurrence(
+        event.actionHash,
+        2
+      );
+      assert.equal(interested.length, 1);
+      assert.equal(
+        (await alice.store.client.getInterestedInOccurrence(event.actionHash, 1))
+          .length,
+        0
+      );
+
+      // Only the hosts that accepted to host can override occurrences
+      await expect(
+        bob.store.client.createOccurrenceOverride({
+          event_hash: event.actionHash,
+          revision_hash: event.actionHash,
+          occurrence_index: 3,
+          change: { type: 'Cancelled' },
+        })
+      ).rejects.toThrow();
+
+      await bob.store.client.acceptHostInvitation(event.actionHash);
+      await waitAndDhtSync([alice.player, bob.player]);
+      const bobCancellation = await bob.store.client.createOccurrenceOverride({
+        event_hash: event.actionHash,
+        revision_hash: event.actionHash,
+        occurrence_index: 3,
+        change: { type: 'Cancelled' },
+      });
+      assert.ok(bobCancellation);
+
+      // The override must act on a revision of its own event
+      const otherEvent = await alice.store.client.createEvent(
+        await sampleEvent(alice.store)
+      );
+      await expect(
+        alice.store.client.createOccurrenceOverride({
+          event_hash: event.actionHash,
+          revision_hash: otherEvent.actionHash,
+          occurrence_index: 0,
+          change: { type: 'Cancelled' },
+        })
+      ).rejects.toThrow();
+
+      // Occurrences past the end of the event can't be overridden
+      await expect(
+        alice.store.client.createOccurrenceOverride({
+          event_hash: event.actionHash,
+          revision_hash: event.actionHash,
+          occurrence_index: 4,
+          change: { type: 'Cancelled' },
+        })
+      ).rejects.toThrow();
+
+      // Deleting the override undoes the cancellation
+      await alice.store.client.deleteOccurrenceOverride(
+        cancellation.actionHash
+      );
+      await waitAndDhtSync([alice.player, bob.player]);
+
+      occurrences = await bob.store.client.getEventOccurrences(
+        event.actionHash,
+        start + week,
+        start + week + 1
+      );
+      assert.equal(occurrences.length, 1);
+      assert.notOk(occurrences[0].cancelled);
+
+      // Deleting the event removes the interest in its occurrences
+      await alice.store.client.deleteEvent(event.actionHash);
+      await waitAndDhtSync([alice.player, bob.player]);
+
+      assert.equal(
+        (await alice.store.client.getInterestedInOccurrence(event.actionHash, 2))
+          .length,
+        0
+      );
+      assert.equal((await bob.store.client.getMyEvents()).length, 0);
+    },
+    true,
+    { timeout: 30000 }
+  );
+});
+
+test('export and import events as iCalendar', async t => {
+  await runScenario(
+    async scenario => {
+      const { alice, bob } = await setup(scenario);
+
+      const event = await alice.store.client.createEvent(
+        await sampleEvent(alice.store, {
+          title: 'Community dinner',
+          cost: {
+            type: 'PayWhatYouCan',
+            currency: 'EUR',
+            suggested_min: 500,
+            suggested_max: 1500,
+          },
+          time: {
+            type: 'Recurring',
+            start_time: Date.UTC(2030, 0, 3, 18) * 1000,
+            event_duration: { secs: 2 * 60 * 60, nanos: 0 },
+            recurrence: {
+              frequency: 'Weekly',
+              interval: 2,
+              by_weekday: ['Thursday'],
+              by_nth_weekday: [],
+              by_month_day: [],
+              until: undefined,
+              count: 10,
+              exclusions: [],
+            },
+          },
+        })
+      );
+
+      const ical = await alice.store.client.exportEventToIcal(
+        event.actionHash
+      );
+      const lines = ical.split('\r\n');
+      assert.equal(lines[0], 'BEGIN:VCALENDAR');
+      assert.include(lines, 'SUMMARY:Community dinner');
+      assert.include(lines, 'DTSTART:20300103T180000Z');
+      assert.include(lines, 'DTEND:20300103T200000Z');
+      assert.include(lines, 'RRULE:FREQ=WEEKLY;INTERVAL=2;BYDAY=TH;COUNT=10');
+      assert.ok(lines.some(line => line.startsWith('ORGANIZER;')));
+
+      let feed = await alice.store.client.exportMyEventsToIcal();
+      assert.equal(feed.match(/BEGIN:VEVENT/g)?.length, 1);
+
+      // Deleted events are left out of the feed
+      const deleted = await alice.store.client.createEvent(
+        await sampleEvent(alice.store)
+      );
+      await alice.store.client.deleteEvent(deleted.actionHash);
+      feed = await alice.store.client.exportMyEventsToIcal();
+      assert.equal(feed.match(/BEGIN:VEVENT/g)?.length, 1);
+
+      await waitAndDhtSync([alice.player, bob.player]);
+
+      // Bob imports the exported event as their own
+      const imported = await bob.store.client.importEventFromIcal(
+        ical,
+        event.entry.image,
+        event.entry.call_to_action_hash
+      );
+      assert.equal(imported.entry.title, 'Community dinner');
+      assert.deepEqual(imported.entry.hosts, [bob.player.agentPubKey]);
+      assert.deepEqual(imported.entry.time, event.entry.time);
+
+      const vcalendar = (...properties: string[]) =>
+        [
+          'BEGIN:VCALENDAR',
+          'BEGIN:VEVENT',
+          'SUMMARY:Imported',
+          'LOCATION:Somewhere in Stockholm',
+          ...properties,
+          'END:VEVENT',
+          'END:VCALENDAR',
+        ].join('\r\n');
+
+      // There is no timezone database in the zome, so only UTC times are supported
+      await expect(
+        bob.store.client.importEventFromIcal(
+          vcalendar('DTSTART;TZID=Europe/Stockholm:20300103T180000'),
+          event.entry.image,
+          event.entry.call_to_action_hash
+        )
+      ).rejects.toThrow();
+      const inUtc = await bob.store.client.importEventFromIcal(
+        vcalendar('DTSTART;TZID=UTC:20300103T180000', 'DURATION:PT2H'),
+        event.entry.image,
+        event.entry.call_to_action_hash
+      );
+      assert.deepEqual(inUtc.entry.time, {
+        type: 'Unique',
+        start_time: Date.UTC(2030, 0, 3, 18) * 1000,
+        end_time: Date.UTC(2030, 0, 3, 20) * 1000,
+      });
+
+      // Durations that overflow are rejected instead of wrapping around
+      await expect(
+        bob.store.client.importEventFromIcal(
+          vcalendar('DTSTART:20300103T180000Z', 'DURATION:P99999999999999999W'),
+          event.entry.image,
+          event.entry.call_to_action_hash
+        )
+      ).rejects.toThrow();
+
+      // Times out of range are rejected
+      await expect(
+        bob.store.client.importEventFromIcal(
+          vcalendar('DTSTART:20301303T180000Z'),
+          event.entry.image,
+          event.entry.call_to_action_hash
+        )
+      ).rejects.toThrow();
+
+      // Timed events without an end last one hour
+      const withoutEnd = await bob.store.client.importEventFromIcal(
+        vcalendar('DTSTART:20300103T180000Z'),
+        event.entry.image,
+        event.entry.call_to_action_hash
+      );
+      assert.deepEqual(withoutEnd.entry.time, {
+        type: 'Unique',
+        start_time: Date.UTC(2030, 0, 3, 18) * 1000,
+        end_time: Date.UTC(2030, 0, 3, 19) * 1000,
+      });
+
+      // Without a LOCATION, the location of the event must be given
+      const withoutLocation = [
+        'BEGIN:VCALENDAR',
+        'BEGIN:VEVENT',
+        'SUMMARY:Imported',
+        'DTSTART:20300103T180000Z',
+        'END:VEVENT',
+        'END:VCALENDAR',
+      ].join('\r\n');
+      await expect(
+        bob.store.client.importEventFromIcal(
+          withoutLocation,
+          event.entry.image,
+          event.entry.call_to_action_hash
+        )
+      ).rejects.toThrow();
+      const located = await bob.store.client.importEventFromIcal(
+        withoutLocation,
+        event.entry.image,
+        event.entry.call_to_action_hash,
+        { type: 'Online', url: 'https://meet.example.org/dinner' }
+      );
+      assert.equal(located.entry.location.type, 'Online');
+    },
+    true,
+    { timeout: 30000 }
+  );
+});
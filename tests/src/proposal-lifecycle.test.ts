@@ -3,7 +3,9 @@ import { test, assert } from 'vitest';
 import { pause, runScenario } from '@holochain/tryorama';
 import { toPromise } from '@holochain-open-dev/stores';
 import {
+  readAndAssertHostInvitation,
   readAndAssertNotification,
+  sampleEvent,
   sampleProposal,
   setup,
   waitAndDhtSync,
@@ -26,13 +28,18 @@ test('proposal: create and cancel', async t => {
       const proposal = await alice.store.client.createProposal(
         await sampleProposal(alice.store, {
           title: 'Cool Proposal',
-          hosts: [bob.player.agentPubKey],
+          hosts: [alice.player.agentPubKey, bob.player.agentPubKey],
         })
       );
       assert.ok(proposal);
 
       await waitAndDhtSync([alice.player, bob.player]);
 
+      await readAndAssertHostInvitation(
+        bob.store,
+        'Cool Proposal',
+        'Du blev inbjuden att vara värd för detta förslag.'
+      );
       await readAndAssertNotification(
         bob.store,
         'Cool Proposal',
@@ -96,7 +103,7 @@ test('proposal: create and expire', async t => {
           alice.store,
           {
             title: 'Cool Proposal',
-            hosts: [bob.player.agentPubKey],
+            hosts: [alice.player.agentPubKey, bob.player.agentPubKey],
           },
           (Date.now() + 30_000) * 1000
         )
@@ -106,6 +113,11 @@ test('proposal: create and expire', async t => {
 
       await waitAndDhtSync([alice.player, bob.player]);
 
+      await readAndAssertHostInvitation(
+        bob.store,
+        'Cool Proposal',
+        'Du blev inbjuden att vara värd för detta förslag.'
+      );
       await readAndAssertNotification(
         bob.store,
         'Cool Proposal',
@@ -153,13 +165,18 @@ test('proposal: create and fulfill', async t => {
       const proposal = await alice.store.client.createProposal(
         await sampleProposal(alice.store, {
           title: 'Cool Proposal',
-          hosts: [bob.player.agentPubKey],
+          hosts: [alice.player.agentPubKey, bob.player.agentPubKey],
         })
       );
       assert.ok(proposal);
 
       await waitAndDhtSync([alice.player, bob.player]);
 
+      await readAndAssertHostInvitation(
+        bob.store,
+        'Cool Proposal',
+        'Du blev inbjuden att vara värd för detta förslag.'
+      );
       await readAndAssertNotification(
         bob.store,
         'Cool Proposal',
@@ -203,3 +220,117 @@ test('proposal: create and fulfill', async t => {
     { timeout: 60_000 }
   );
 });
+
+test('proposal: fulfilled by an agent that is not a host', async t => {
+  await runScenario(
+    async scenario => {
+      const { alice, bob } = await setup(scenario);
+
+      const now = Date.now() * 1000;
+      const day = 24 * 60 * 60 * 1000 * 1000;
+      const barcelona = { latitude: 41.3874, longitude: 2.1686 };
+
+      const proposal = await alice.store.client.createProposal(
+        await sampleProposal(alice.store, {
+          title: 'Community garden',
+          tags: ['gardening'],
+          location: {
+            type: 'Physical',
+            address: 'Somewhere in Barcelona',
+            coordinates: barcelona,
+          },
+        })
+      );
+      await waitAndDhtSync([alice.player, bob.player]);
+
+      // Bob fulfills the needs of the proposal, and so creates and indexes the event
+      await bob.store.assembleStore.client.createAssembly({
+        call_to_action_hash: proposal.entry.call_to_action_hash,
+        satisfactions_hashes: [],
+      });
+      await waitAndDhtSync([alice.player, bob.player]);
+
+      const upcomingEvents = await alice.store.client.getAllUpcomingEvents();
+      assert.equal(upcomingEvents.length, 1);
+
+      const near = await alice.store.client.getEventsNear(barcelona, 10, {
+        start: now,
+        end: now + 7 * day,
+      });
+      assert.equal(near.length, 1);
+      const tagged = await alice.store.client.getEventsByTag('gardening');
+      assert.equal(tagged.length, 1);
+      const found = await alice.store.client.searchEvents('garden');
+      assert.equal(found.length, 1);
+    },
+    true,
+    { timeout: 60_000 }
+  );
+});
+
+test('sweep: moves ended events and expired proposals of the host', async t => {
+  await runScenario(
+    async scenario => {
+      const { alice, bob } = await setup(scenario);
+
+      const now = Date.now() * 1000;
+      const second = 1000 * 1000;
+
+      const proposal = await alice.store.client.createProposal(
+        await sampleProposal(
+          alice.store,
+          { title: 'Expiring proposal' },
+          now + 5 * second
+        )
+      );
+      const event = await alice.store.client.createEvent(
+        await sampleEvent(alice.store, {
+          title: 'Ending event',
+          hosts: [alice.player.agentPubKey, bob.player.agentPubKey],
+          time: {
+            type: 'Unique',
+            start_time: now + 2 * second,
+            end_time: now + 5 * second,
+          },
+        })
+      );
+      await waitAndDhtSync([alice.player, bob.player]);
+
+      await bob.store.client.addMyselfAsInterested(proposal.actionHash);
+      await bob.store.client.acceptHostInvitation(event.actionHash);
+      await waitAndDhtSync([alice.player, bob.player]);
+
+      await pause(6_000);
+
+      // Nothing moves them until the host sweeps
+      assert.equal((await bob.store.client.getAllOpenProposals()).length, 1);
+      assert.equal((await bob.store.client.getAllUpcomingEvents()).length, 1);
+
+      // Only the first accepted host sweeps the event, not its co-hosts
+      await (bob.store.client as any).callZome('sweep_my_events', null);
+      await waitAndDhtSync([alice.player, bob.player]);
+      assert.equal((await bob.store.client.getAllUpcomingEvents()).length, 1);
+
+      await (alice.store.client as any).callZome('sweep_my_events', null);
+      await waitAndDhtSync([alice.player, bob.player]);
+
+      assert.equal((await bob.store.client.getAllOpenProposals()).length, 0);
+      const expiredProposals = await bob.store.client.getAllExpiredProposals();
+      assert.equal(expiredProposals.length, 1);
+      assert.deepEqual(expiredProposals[0].target, proposal.actionHash);
+
+      assert.equal((await bob.store.client.getAllUpcomingEvents()).length, 0);
+      const pastEvents = await bob.store.client.getAllPastEvents();
+      assert.equal(pastEvents.length, 1);
+      assert.deepEqual(pastEvents[0].target, event.actionHash);
+
+      await readAndAssertNotification(
+        bob.store,
+        'Expiring proposal',
+        'Förslaget gick ut utan att nå upp till minimi nivåer för behov.'
+      );
+    },
+    true,
+    { timeout: 60_000 }
+  );
+});
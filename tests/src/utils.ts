@@ -16,6 +16,16 @@ import { GatherStore } from '../../ui/src/gather/gather/gather-store.js';
 import { AlertsStore } from '../../ui/src/alerts/alerts-store.js';
 import { AlertsClient } from '../../ui/src/alerts/alerts-client.js';
 import { decode } from '@msgpack/msgpack';
+import { EntryHash } from '@holochain/client';
+
+export async function sampleImage(gatherStore: GatherStore): Promise<EntryHash> {
+  return gatherStore.client.client.callZome({
+    role_name: 'gather',
+    zome_name: 'file_storage',
+    fn_name: 'create_file_chunk',
+    payload: new Uint8Array([0, 1, 2, 3]),
+  });
+}
 
 export async function sampleProposal(
   gatherStore: GatherStore,
@@ -23,25 +33,29 @@ export async function sampleProposal(
   expiration_time: number = undefined
 ): Promise<Proposal> {
   return {
-    hosts: [],
+    hosts: [gatherStore.client.client.myPubKey],
     title:
       'Lorem ipsum dolor sit amet, consectetur adipiscing elit. Sed nec eros quis enim hendrerit aliquet.',
     description:
       'Lorem ipsum dolor sit amet, consectetur adipiscing elit. Sed nec eros quis enim hendrerit aliquet.',
-    image: Buffer.from(
-      new Uint8Array([
-        132, 33, 36, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
-        0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
-      ])
-    ),
-    location:
-      'Lorem ipsum dolor sit amet, consectetur adipiscing elit. Sed nec eros quis enim hendrerit aliquet.',
+    image: await sampleImage(gatherStore),
+    location: {
+      type: 'Physical',
+      address:
+        'Lorem ipsum dolor sit amet, consectetur adipiscing elit. Sed nec eros quis enim hendrerit aliquet.',
+      coordinates: undefined,
+    },
     time: {
       type: 'Unique',
       start_time: Date.now() * 1000 + 60 * 60 * 1000 * 1000,
       end_time: Date.now() * 1000 + 2 * 60 * 60 * 1000 * 1000,
     },
-    cost: 'Lorem ipsum dolor sit amet, consectetur adipiscing elit. Sed nec eros quis enim hendrerit aliquet.',
+    cost: {
+      type: 'Fixed',
+      price: { amount: 1000, currency: 'EUR' },
+    },
+    tags: [],
+    capacity: undefined,
     call_to_action_hash: (
       await gatherStore.assembleStore.client.createCallToAction(
         await sampleCallToAction(gatherStore.assembleStore.client, {
@@ -58,25 +72,28 @@ export async function sampleEvent(
   partialEvent: Partial<Event> = {}
 ): Promise<Event> {
   return {
-    hosts: [],
+    hosts: [gatherStore.client.client.myPubKey],
     title:
       'Lorem ipsum dolor sit amet, consectetur adipiscing elit. Sed nec eros quis enim hendrerit aliquet.',
     description:
       'Lorem ipsum dolor sit amet, consectetur adipiscing elit. Sed nec eros quis enim hendrerit aliquet.',
-    image: Buffer.from(
-      new Uint8Array([
-        132, 33, 36, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
-        0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
-      ])
-    ),
-    location:
-      'Lorem ipsum dolor sit amet, consectetur adipiscing elit. Sed nec eros quis enim hendrerit aliquet.',
+    image: await sampleImage(gatherStore),
+    location: {
+      type: 'Physical',
+      address:
+        'Lorem ipsum dolor sit amet, consectetur adipiscing elit. Sed nec eros quis enim hendrerit aliquet.',
+      coordinates: undefined,
+    },
     time: {
       type: 'Unique',
       start_time: Date.now() * 1000 + 60 * 60 * 1000 * 1000,
       end_time: Date.now() * 1000 + 2 * 60 * 60 * 1000 * 1000,
     },
-    cost: 'Lorem ipsum dolor sit amet, consectetur adipiscing elit. Sed nec eros quis enim hendrerit aliquet.',
+    cost: {
+      type: 'Fixed',
+      price: { amount: 1000, currency: 'EUR' },
+    },
+    tags: [],
     call_to_action_hash: (
       await gatherStore.assembleStore.client.createCallToAction(
         await sampleCallToAction(gatherStore.assembleStore.client)
@@ -129,6 +146,47 @@ export async function readAndAssertNotification(
   assert.equal(notification.body, body);
 }
 
+/**
+ * Reads the invitation to host the event or proposal, which hosts get
+ * besides the rest of its notifications
+ */
+export async function readAndAssertHostInvitation(
+  store: GatherStore,
+  title: string,
+  body: string
+) {
+  let notifications = await toPromise(store.alertsStore.unreadAlerts);
+  let invitation = notifications.find(n =>
+    n.alert.action.type.endsWith('HostInvitation')
+  );
+
+  if (!invitation) {
+    await pause(5000);
+
+    notifications = await toPromise(store.alertsStore.unreadAlerts);
+    invitation = notifications.find(n =>
+      n.alert.action.type.endsWith('HostInvitation')
+    );
+  }
+
+  assert.ok(invitation, 'There was no host invitation');
+
+  const notification = await (store.client as any).callZome(
+    'get_notification',
+    {
+      notification_hash: invitation!.link.create_link_hash,
+      locale: 'sv',
+    }
+  );
+
+  await store.alertsStore.client.markAlertsAsRead([
+    invitation!.link.create_link_hash,
+  ]);
+
+  assert.equal(notification.title, title);
+  assert.equal(notification.body, body);
+}
+
 export async function setup(scenario: Scenario) {
   const testHappUrl =
     dirname(fileURLToPath(import.meta.url)) + '/../../workdir/gather.happ';
@@ -9,6 +9,7 @@ import { wrapPathInSvg } from '@holochain-open-dev/elements';
 import { msg, str } from '@lit/localize';
 import {
   mdiAccountGroup,
+  mdiAccountPlus,
   mdiAlert,
   mdiCancel,
   mdiCheckBold,
@@ -16,12 +17,13 @@ import {
   mdiCreation,
   mdiHandshake,
   mdiPartyPopper,
+  mdiSeat,
   mdiUndo,
   mdiUndoVariant,
   mdiUpdate,
 } from '@mdi/js';
 import { EntryRecord } from '@holochain-open-dev/utils';
-import { Event, Proposal } from './types';
+import { Event, OccurrenceOverride, Proposal, Rsvp } from './types';
 import { ActionHash } from '@holochain/client';
 
 export type EventAction =
@@ -63,6 +65,22 @@ export type EventAction =
       record: EntryRecord<void>;
       event: EntryRecord<Event>;
     }
+  | {
+      type: 'OccurrenceChanged';
+      record: EntryRecord<OccurrenceOverride>;
+    }
+  | {
+      type: 'RsvpConfirmed';
+      record: EntryRecord<Rsvp>;
+    }
+  | {
+      type: 'EventHostInvitation';
+      record: EntryRecord<Event>;
+    }
+  | {
+      type: 'ProposalHostInvitation';
+      record: EntryRecord<Proposal>;
+    }
   | {
       type: 'CommitmentCreated';
       record: EntryRecord<Commitment>;
@@ -143,6 +161,23 @@ export type EventActionOnlyHash =
       type: 'EventUncancelled';
       action_hash: ActionHash;
     }
+  | {
+      type: 'OccurrenceChanged';
+      action_hash: ActionHash;
+      occurrence_index: number;
+    }
+  | {
+      type: 'RsvpConfirmed';
+      action_hash: ActionHash;
+    }
+  | {
+      type: 'EventHostInvitation';
+      action_hash: ActionHash;
+    }
+  | {
+      type: 'ProposalHostInvitation';
+      action_hash: ActionHash;
+    }
   | {
       type: 'CommitmentCreated';
       action_hash: ActionHash;
@@ -223,6 +258,29 @@ export function messageAndIcon(action: EventAction) {
         message: msg('Event was updated.'),
         icon: wrapPathInSvg(mdiUpdate),
       };
+    case 'OccurrenceChanged':
+      return {
+        message: msg('One of the sessions of the event has changed.'),
+        secondary: msg(
+          str`Session number ${action.record.entry.occurrence_index + 1}.`
+        ),
+        icon: wrapPathInSvg(mdiUpdate),
+      };
+    case 'RsvpConfirmed':
+      return {
+        message: msg('A seat was freed: you are now confirmed to attend!'),
+        icon: wrapPathInSvg(mdiSeat),
+      };
+    case 'EventHostInvitation':
+      return {
+        message: msg('You were invited to host this event.'),
+        icon: wrapPathInSvg(mdiAccountPlus),
+      };
+    case 'ProposalHostInvitation':
+      return {
+        message: msg('You were invited to host this proposal.'),
+        icon: wrapPathInSvg(mdiAccountPlus),
+      };
     case 'CommitmentCreated':
       if (action.record.entry.need_index === 0) {
         return {
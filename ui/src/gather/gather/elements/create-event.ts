@@ -33,6 +33,11 @@ import { EntryRecord } from '@holochain-open-dev/utils';
 import { gatherStoreContext, isMobileContext } from '../context.js';
 import { GatherStore } from '../gather-store.js';
 import { Event, Proposal } from '../types.js';
+import {
+  costFromString,
+  locationFromString,
+  tagsFromString,
+} from '../utils.js';
 
 @localized()
 @customElement('create-event')
@@ -77,7 +82,8 @@ export class CreateEvent extends LitElement {
         : [fields.hosts]
       : [];
     hosts.push(this.gatherStore.client.client.myPubKey);
-    const cost = fields.cost === '' ? undefined : fields.cost;
+    const cost = costFromString(fields.cost);
+    const tags = tagsFromString(fields.tags);
 
     const needs: Array<Need> = needsFields.map((n: string) => JSON.parse(n));
     const participantsNeeds: Need = JSON.parse(fields.participants);
@@ -109,12 +115,15 @@ export class CreateEvent extends LitElement {
               end_time: new Date(fields.end_time).valueOf() * 1000,
             }
           : undefined;
-        const location = !this.locationTbd ? fields.location : undefined;
+        const location = locationFromString(
+          !this.locationTbd ? fields.location : undefined
+        );
         const proposal: Proposal = {
           ...fields,
           hosts,
           time,
           cost,
+          tags,
           location,
           call_to_action_hash: callToActionEntryRecord.actionHash,
         };
@@ -128,6 +137,12 @@ export class CreateEvent extends LitElement {
             start_time: new Date(fields.start_time).valueOf() * 1000,
             end_time: new Date(fields.end_time).valueOf() * 1000,
           },
+          location: locationFromString(fields.location),
+          cost,
+          tags,
+          capacity: fields.capacity
+            ? parseInt(fields.capacity, 10)
+            : undefined,
           from_proposal: undefined,
           call_to_action_hash: callToActionEntryRecord.actionHash,
         };
@@ -400,6 +415,26 @@ export class CreateEvent extends LitElement {
 
         <sl-input name="cost" .label=${msg('Cost')} style="flex: 1"></sl-input>
 
+        <sl-input
+          name="tags"
+          .label=${msg('Tags')}
+          .helpText=${msg('Separated by commas')}
+        ></sl-input>
+
+        ${this.isProposal
+          ? html``
+          : html`
+              <sl-input
+                name="capacity"
+                type="number"
+                min="1"
+                .label=${msg('Capacity')}
+                .helpText=${msg(
+                  'Leave empty for unlimited seats. Once full, new attendees are waitlisted.'
+                )}
+              ></sl-input>
+            `}
+
         <div class="row" style="justify-content: end; gap: 16px">
           ${this.renderBackButton()} ${this.renderNextButton(pageIndex)}
         </div>
@@ -26,6 +26,13 @@ import '@holochain-open-dev/elements/dist/elements/sl-datetime-input.js';
 import { gatherStoreContext } from '../context.js';
 import { GatherStore } from '../gather-store.js';
 import { Event as GatherEvent } from '../types.js';
+import {
+  costToString,
+  editedCost,
+  editedLocation,
+  locationToString,
+  tagsFromString,
+} from '../utils.js';
 
 @localized()
 @customElement('edit-event')
@@ -54,6 +61,13 @@ export class EditEvent extends LitElement {
       ...fields,
       call_to_action_hash: this.currentRecord.entry.call_to_action_hash,
       hosts: this.currentRecord.entry.hosts,
+      location: editedLocation(
+        this.currentRecord.entry.location,
+        fields.location
+      ),
+      cost: editedCost(this.currentRecord.entry.cost, fields.cost),
+      tags: tagsFromString(fields.tags),
+      capacity: fields.capacity ? parseInt(fields.capacity, 10) : undefined,
       time: {
         type: 'Unique',
         start_time: new Date(fields.start_time).valueOf() * 1000,
@@ -141,12 +155,32 @@ export class EditEvent extends LitElement {
           name="location"
           required
           .label=${msg('Location')}
-          .defaultValue=${this.currentRecord.entry.location}
+          .defaultValue=${locationToString(
+            this.currentRecord.entry.location
+          ) || ''}
         ></sl-input>
         <sl-input
           name="cost"
           .label=${msg('Cost')}
-          .defaultValue=${this.currentRecord.entry.cost || ''}
+          .defaultValue=${this.currentRecord.entry.cost
+            ? costToString(this.currentRecord.entry.cost)
+            : ''}
+        ></sl-input>
+        <sl-input
+          name="tags"
+          .label=${msg('Tags')}
+          .helpText=${msg('Separated by commas')}
+          .defaultValue=${this.currentRecord.entry.tags.join(', ')}
+        ></sl-input>
+        <sl-input
+          name="capacity"
+          type="number"
+          min="1"
+          .label=${msg('Capacity')}
+          .helpText=${msg(
+            'Leave empty for unlimited seats. Once full, new attendees are waitlisted.'
+          )}
+          .defaultValue=${this.currentRecord.entry.capacity ?? ''}
         ></sl-input>
 
         <div style="display: flex; flex-direction: row; gap: 8px">
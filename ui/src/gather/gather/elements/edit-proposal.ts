@@ -30,6 +30,13 @@ import {
   Proposal,
   ProposalWithStatus,
 } from '../types.js';
+import {
+  costToString,
+  editedCost,
+  editedLocation,
+  locationToString,
+  tagsFromString,
+} from '../utils.js';
 
 @localized()
 @customElement('edit-proposal')
@@ -45,7 +52,8 @@ export class EditProposal extends LitElement {
 
   firstUpdated() {
     this.timeTbd = !this.proposal.currentProposal.entry.time;
-    this.locationTbd = !this.proposal.currentProposal.entry.location;
+    this.locationTbd =
+      this.proposal.currentProposal.entry.location.type === 'ToBeAnnounced';
     this.shadowRoot?.querySelector('form')!.reset();
   }
 
@@ -75,7 +83,10 @@ export class EditProposal extends LitElement {
           start_time: new Date(fields.start_time).valueOf() * 1000,
           end_time: new Date(fields.end_time).valueOf() * 1000,
         };
-    const location = this.locationTbd ? undefined : fields.location;
+    const location = editedLocation(
+      this.proposal.currentProposal.entry.location,
+      this.locationTbd ? undefined : fields.location
+    );
 
     const proposal: Proposal = {
       ...fields,
@@ -83,6 +94,8 @@ export class EditProposal extends LitElement {
         this.proposal.currentProposal.entry.call_to_action_hash,
       hosts: this.proposal.currentProposal.entry.hosts,
       location,
+      cost: editedCost(this.proposal.currentProposal.entry.cost, fields.cost),
+      tags: tagsFromString(fields.tags),
       time,
     };
 
@@ -242,7 +255,9 @@ export class EditProposal extends LitElement {
             .required=${!this.locationTbd}
             .disabled=${this.locationTbd}
             .label=${msg('Location')}
-            .defaultValue=${this.proposal.currentProposal.entry.location}
+            .defaultValue=${locationToString(
+              this.proposal.currentProposal.entry.location
+            ) || ''}
           ></sl-input>
           <sl-switch
             .checked=${this.locationTbd}
@@ -258,7 +273,15 @@ export class EditProposal extends LitElement {
           name="cost"
           style="flex: 1;"
           .label=${msg('Cost')}
-          .defaultValue=${this.proposal.currentProposal.entry.cost || ''}
+          .defaultValue=${this.proposal.currentProposal.entry.cost
+            ? costToString(this.proposal.currentProposal.entry.cost)
+            : ''}
+        ></sl-input>
+        <sl-input
+          name="tags"
+          .label=${msg('Tags')}
+          .helpText=${msg('Separated by commas')}
+          .defaultValue=${this.proposal.currentProposal.entry.tags.join(', ')}
         ></sl-input>
 
         <div style="display: flex; flex-direction: row; gap: 8px">
@@ -29,6 +29,7 @@ import {
   mdiFormatListChecks,
   mdiMapMarker,
   mdiPencil,
+  mdiTagMultiple,
   mdiTimeline,
 } from '@mdi/js';
 import { SlDrawer } from '@shoelace-style/shoelace';
@@ -72,6 +73,7 @@ import './edit-event.js';
 import { gatherStoreContext, isMobileContext } from '../context.js';
 import { GatherStore } from '../gather-store.js';
 import { EventWithStatus } from '../types.js';
+import { costToString, locationToString } from '../utils.js';
 import { ParticipateDialog } from './participate-dialog.js';
 import { styles } from '../../../styles.js';
 
@@ -366,7 +368,7 @@ export class EventDetail extends LitElement {
               .src=${wrapPathInSvg(mdiMapMarker)}
             ></sl-icon>
             <span style="white-space: pre-line"
-              >${event.currentEvent.entry.location}</span
+              >${locationToString(event.currentEvent.entry.location)}</span
             >
           </div>
 
@@ -405,10 +407,25 @@ export class EventDetail extends LitElement {
                   .src=${wrapPathInSvg(mdiCash)}
                 ></sl-icon>
                 <span style="white-space: pre-line"
-                  >${event.currentEvent.entry.cost}</span
+                  >${costToString(event.currentEvent.entry.cost)}</span
                 >
               </div>`
             : html``}
+
+          ${event.currentEvent.entry.tags.length > 0
+            ? html` <div
+                style="display: flex; flex-direction: row; align-items: center; gap: 4px; flex-wrap: wrap"
+              >
+                <sl-icon
+                  style="font-size: 24px"
+                  title=${msg('tags')}
+                  .src=${wrapPathInSvg(mdiTagMultiple)}
+                ></sl-icon>
+                ${event.currentEvent.entry.tags.map(
+                  tag => html`<sl-tag size="small" pill>${tag}</sl-tag>`
+                )}
+              </div>`
+            : html``}
         </div>
       </sl-card>
     `;
@@ -29,6 +29,7 @@ import '@holochain-open-dev/file-storage/dist/elements/show-image.js';
 import { gatherStoreContext, isMobileContext } from '../context.js';
 import { GatherStore } from '../gather-store.js';
 import { Event } from '../types.js';
+import { locationToString } from '../utils.js';
 
 @localized()
 @customElement('event-summary')
@@ -112,7 +113,7 @@ export class EventSummary extends LitElement {
                   .src=${wrapPathInSvg(mdiMapMarker)}
                 ></sl-icon>
                 <span style="white-space: pre-line"
-                  >${event.entry.location}</span
+                  >${locationToString(event.entry.location)}</span
                 >
               </div>
               <div
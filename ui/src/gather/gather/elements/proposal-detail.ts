@@ -25,6 +25,7 @@ import {
   mdiFormatListChecks,
   mdiMapMarker,
   mdiPencil,
+  mdiTagMultiple,
   mdiTimeline,
 } from '@mdi/js';
 
@@ -63,6 +64,7 @@ import './event-detail.js';
 import { gatherStoreContext, isMobileContext } from '../context.js';
 import { GatherStore } from '../gather-store.js';
 import { ProposalWithStatus } from '../types.js';
+import { costToString, locationToString } from '../utils.js';
 import { ParticipateDialog } from './participate-dialog.js';
 import { styles } from '../../../styles.js';
 import { styleMap } from 'lit/directives/style-map.js';
@@ -325,9 +327,8 @@ export class ProposalDetail extends LitElement {
               .src=${wrapPathInSvg(mdiMapMarker)}
             ></sl-icon>
             <span style="white-space: pre-line"
-              >${proposal.currentProposal.entry.location
-                ? proposal.currentProposal.entry.location
-                : msg('To Be Defined')}</span
+              >${locationToString(proposal.currentProposal.entry.location) ||
+                msg('To Be Defined')}</span
             >
           </div>
           <div class="row" style="align-items: center; gap: 4px">
@@ -367,11 +368,26 @@ export class ProposalDetail extends LitElement {
                   .src=${wrapPathInSvg(mdiCash)}
                 ></sl-icon>
                 <span style="white-space: pre-line"
-                  >${proposal.currentProposal.entry.cost}</span
+                  >${costToString(proposal.currentProposal.entry.cost)}</span
                 >
               </div>`
             : html``}
 
+          ${proposal.currentProposal.entry.tags.length > 0
+            ? html` <div
+                style="display: flex; flex-direction: row; align-items: center; gap: 4px; flex-wrap: wrap"
+              >
+                <sl-icon
+                  style="font-size: 24px"
+                  title=${msg('tags')}
+                  .src=${wrapPathInSvg(mdiTagMultiple)}
+                ></sl-icon>
+                ${proposal.currentProposal.entry.tags.map(
+                  tag => html`<sl-tag size="small" pill>${tag}</sl-tag>`
+                )}
+              </div>`
+            : html``}
+
           <span class="placeholder"
             >${msg(
               'Edit the proposal and decide on a time and location to create the event.'
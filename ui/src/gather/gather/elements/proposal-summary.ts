@@ -30,6 +30,7 @@ import '@darksoil/assemble/dist/elements/call-to-action-progress.js';
 import { gatherStoreContext, isMobileContext } from '../context.js';
 import { GatherStore } from '../gather-store.js';
 import { ProposalWithStatus } from '../types.js';
+import { locationToString } from '../utils.js';
 import './call-to-action-needs.js';
 
 @localized()
@@ -116,9 +117,8 @@ export class ProposalSummary extends LitElement {
                   .src=${wrapPathInSvg(mdiMapMarker)}
                 ></sl-icon>
                 <span style="white-space: pre-line"
-                  >${proposal.currentProposal.entry.location
-                    ? proposal.currentProposal.entry.location
-                    : msg('To Be Defined')}</span
+                  >${locationToString(proposal.currentProposal.entry.location) ||
+                    msg('To Be Defined')}</span
                 >
               </div>
               <div
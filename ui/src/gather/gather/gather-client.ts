@@ -4,12 +4,35 @@ import {
   AgentPubKey,
   AppAgentClient,
   CreateLink,
+  EntryHash,
   Link,
   Record,
   SignedActionHashed,
 } from '@holochain/client';
 
-import { Proposal, Event, GatherSignal } from './types';
+import {
+  Proposal,
+  Event,
+  GatherSignal,
+  CollectionCursor,
+  CollectionPage,
+  EventOccurrence,
+  OccurrenceOverride,
+  Coordinates,
+  Location,
+  EventCollectionStatus,
+  ProposalCollectionStatus,
+  PrivateEvent,
+  Rsvp,
+  RsvpStatus,
+  EventRsvps,
+  AttendanceSummary,
+  Attendance,
+  CheckInCode,
+  HostAcceptance,
+  LatestRevision,
+  RevisionChanges,
+} from './types';
 
 export interface GetEventOutput {
   event: EntryRecord<Event>;
@@ -34,13 +57,31 @@ export class GatherClient extends ZomeClient<GatherSignal> {
   async getLatestEvent(
     eventHash: ActionHash
   ): Promise<EntryRecord<Event> | undefined> {
-    const output: any | undefined = await this.callZome(
+    const record: Record | undefined = await this.callZome(
       'get_latest_event',
       eventHash
     );
-    if (!output) return undefined;
+    return record ? new EntryRecord(record) : undefined;
+  }
 
-    return new EntryRecord(output);
+  // Resolves the latest revisions of many events in a single call
+  async getLatestEvents(
+    eventHashes: Array<ActionHash>
+  ): Promise<Array<EntryRecord<Event> | undefined>> {
+    const records: Array<Record | undefined> = await this.callZome(
+      'get_latest_events',
+      eventHashes
+    );
+    return records.map(record =>
+      record ? new EntryRecord<Event>(record) : undefined
+    );
+  }
+
+  // The latest revision of the event with its conflicting heads, or its deletion
+  async getLatestEventRevision(
+    eventHash: ActionHash
+  ): Promise<LatestRevision | undefined> {
+    return this.callZome('get_latest_event_revision', eventHash);
   }
 
   async getOriginalEvent(
@@ -65,6 +106,18 @@ export class GatherClient extends ZomeClient<GatherSignal> {
 
     return records.map(r => new EntryRecord(r));
   }
+  async getEventRevisions(
+    eventHash: ActionHash,
+    limit?: number
+  ): Promise<Array<EntryRecord<Event>>> {
+    const records: Record[] = await this.callZome('get_event_revisions', {
+      original_hash: eventHash,
+      limit,
+    });
+
+    return records.map(r => new EntryRecord(r));
+  }
+
 
   async updateEvent(
     originalEventHash: ActionHash,
@@ -79,6 +132,187 @@ export class GatherClient extends ZomeClient<GatherSignal> {
     return new EntryRecord(record);
   }
 
+  async mergeEventRevisions(
+    originalEventHash: ActionHash,
+    mergedEvent: Event
+  ): Promise<EntryRecord<Event>> {
+    const record = await this.callZome('merge_event_revisions', {
+      original_event_hash: originalEventHash,
+      merged_event: mergedEvent,
+    });
+    return new EntryRecord(record);
+  }
+
+  async getEventRevisionHeads(
+    eventHash: ActionHash
+  ): Promise<Array<EntryRecord<Event>>> {
+    const records: Record[] = await this.callZome(
+      'get_event_revision_heads',
+      eventHash
+    );
+    return records.map(r => new EntryRecord(r));
+  }
+
+  getEventChangelog(eventHash: ActionHash): Promise<Array<RevisionChanges>> {
+    return this.callZome('get_event_changelog', eventHash);
+  }
+
+  deleteEvent(originalEventHash: ActionHash): Promise<ActionHash> {
+    return this.callZome('delete_event', originalEventHash);
+  }
+
+  getEventOccurrences(
+    eventHash: ActionHash,
+    from: number,
+    to: number
+  ): Promise<Array<EventOccurrence>> {
+    return this.callZome('get_event_occurrences', {
+      event_hash: eventHash,
+      from,
+      to,
+    });
+  }
+
+  /** Private events */
+
+  async createPrivateEvent(
+    event: Event,
+    invitees: Array<AgentPubKey>
+  ): Promise<EntryRecord<PrivateEvent>> {
+    const record: Record = await this.callZome('create_private_event', {
+      event,
+      invitees,
+    });
+    return new EntryRecord(record);
+  }
+
+  async getPrivateEvent(privateEventHash: ActionHash): Promise<
+    | {
+        record: EntryRecord<PrivateEvent>;
+        event: Event;
+      }
+    | undefined
+  > {
+    const output = await this.callZome('get_private_event', privateEventHash);
+    if (!output) return undefined;
+
+    return {
+      record: new EntryRecord(output.record),
+      event: output.event,
+    };
+  }
+
+  async updatePrivateEvent(
+    originalPrivateEventHash: ActionHash,
+    previousPrivateEventHash: ActionHash,
+    updatedEvent: Event,
+    invitees: Array<AgentPubKey>
+  ): Promise<EntryRecord<PrivateEvent>> {
+    const record = await this.callZome('update_private_event', {
+      original_private_event_hash: originalPrivateEventHash,
+      previous_private_event_hash: previousPrivateEventHash,
+      updated_event: updatedEvent,
+      invitees,
+    });
+    return new EntryRecord(record);
+  }
+
+  getMyInvitations(): Promise<Array<Link>> {
+    return this.callZome('get_my_invitations', null);
+  }
+
+  dismissInvitation(privateEventHash: ActionHash): Promise<void> {
+    return this.callZome('dismiss_invitation', privateEventHash);
+  }
+
+  /** iCalendar */
+
+  exportEventToIcal(eventHash: ActionHash): Promise<string> {
+    return this.callZome('export_event_to_ical', eventHash);
+  }
+
+  exportMyEventsToIcal(): Promise<string> {
+    return this.callZome('export_my_events_to_ical', null);
+  }
+
+  // The location is only used if the VEVENT has no LOCATION
+  async importEventFromIcal(
+    ical: string,
+    image: EntryHash,
+    callToActionHash: ActionHash,
+    location?: Location
+  ): Promise<EntryRecord<Event>> {
+    const record = await this.callZome('import_event_from_ical', {
+      ical,
+      image,
+      call_to_action_hash: callToActionHash,
+      location,
+    });
+    return new EntryRecord(record);
+  }
+
+  /** Occurrence overrides */
+
+  async createOccurrenceOverride(
+    occurrenceOverride: OccurrenceOverride
+  ): Promise<EntryRecord<OccurrenceOverride>> {
+    const record = await this.callZome(
+      'create_occurrence_override',
+      occurrenceOverride
+    );
+    return new EntryRecord(record);
+  }
+
+  async getOccurrenceOverride(
+    overrideHash: ActionHash
+  ): Promise<EntryRecord<OccurrenceOverride> | undefined> {
+    const output: any | undefined = await this.callZome(
+      'get_occurrence_override',
+      overrideHash
+    );
+    if (!output) return undefined;
+
+    return new EntryRecord(output);
+  }
+
+  deleteOccurrenceOverride(overrideHash: ActionHash): Promise<void> {
+    return this.callZome('delete_occurrence_override', overrideHash);
+  }
+
+  getOccurrenceOverrides(eventHash: ActionHash): Promise<Array<Link>> {
+    return this.callZome('get_occurrence_overrides', eventHash);
+  }
+
+  async addMyselfAsInterestedInOccurrence(
+    eventHash: ActionHash,
+    occurrenceIndex: number
+  ): Promise<void> {
+    return this.callZome('add_myself_as_interested_in_occurrence', {
+      event_hash: eventHash,
+      occurrence_index: occurrenceIndex,
+    });
+  }
+
+  async removeMyselfAsInterestedInOccurrence(
+    eventHash: ActionHash,
+    occurrenceIndex: number
+  ): Promise<void> {
+    return this.callZome('remove_myself_as_interested_in_occurrence', {
+      event_hash: eventHash,
+      occurrence_index: occurrenceIndex,
+    });
+  }
+
+  getInterestedInOccurrence(
+    eventHash: ActionHash,
+    occurrenceIndex: number
+  ): Promise<Array<Link>> {
+    return this.callZome('get_interested_in_occurrence', {
+      event_hash: eventHash,
+      occurrence_index: occurrenceIndex,
+    });
+  }
+
   /** Proposal */
 
   async createProposal(proposal: Proposal): Promise<EntryRecord<Proposal>> {
@@ -90,13 +324,31 @@ export class GatherClient extends ZomeClient<GatherSignal> {
   async getLatestProposal(
     proposalHash: ActionHash
   ): Promise<EntryRecord<Proposal> | undefined> {
-    const output: any | undefined = await this.callZome(
+    const record: Record | undefined = await this.callZome(
       'get_latest_proposal',
       proposalHash
     );
-    if (!output) return undefined;
+    return record ? new EntryRecord(record) : undefined;
+  }
 
-    return new EntryRecord(output);
+  // Resolves the latest revisions of many proposals in a single call
+  async getLatestProposals(
+    proposalHashes: Array<ActionHash>
+  ): Promise<Array<EntryRecord<Proposal> | undefined>> {
+    const records: Array<Record | undefined> = await this.callZome(
+      'get_latest_proposals',
+      proposalHashes
+    );
+    return records.map(record =>
+      record ? new EntryRecord<Proposal>(record) : undefined
+    );
+  }
+
+  // The latest revision of the proposal with its conflicting heads, or its deletion
+  async getLatestProposalRevision(
+    proposalHash: ActionHash
+  ): Promise<LatestRevision | undefined> {
+    return this.callZome('get_latest_proposal_revision', proposalHash);
   }
 
   async getOriginalProposal(
@@ -121,6 +373,18 @@ export class GatherClient extends ZomeClient<GatherSignal> {
 
     return records.map(r => new EntryRecord(r));
   }
+  async getProposalRevisions(
+    proposalHash: ActionHash,
+    limit?: number
+  ): Promise<Array<EntryRecord<Proposal>>> {
+    const records: Record[] = await this.callZome('get_proposal_revisions', {
+      original_hash: proposalHash,
+      limit,
+    });
+
+    return records.map(r => new EntryRecord(r));
+  }
+
 
   async updateProposal(
     originalProposalHash: ActionHash,
@@ -135,18 +399,61 @@ export class GatherClient extends ZomeClient<GatherSignal> {
     return new EntryRecord(record);
   }
 
+  async mergeProposalRevisions(
+    originalProposalHash: ActionHash,
+    mergedProposal: Proposal
+  ): Promise<EntryRecord<Proposal>> {
+    const record = await this.callZome('merge_proposal_revisions', {
+      original_proposal_hash: originalProposalHash,
+      merged_proposal: mergedProposal,
+    });
+    return new EntryRecord(record);
+  }
+
+  async getProposalRevisionHeads(
+    proposalHash: ActionHash
+  ): Promise<Array<EntryRecord<Proposal>>> {
+    const records: Record[] = await this.callZome(
+      'get_proposal_revision_heads',
+      proposalHash
+    );
+    return records.map(r => new EntryRecord(r));
+  }
+
+  getProposalChangelog(
+    proposalHash: ActionHash
+  ): Promise<Array<RevisionChanges>> {
+    return this.callZome('get_proposal_changelog', proposalHash);
+  }
+
+  deleteProposal(originalProposalHash: ActionHash): Promise<ActionHash> {
+    return this.callZome('delete_proposal', originalProposalHash);
+  }
+
   getEventsForProposal(proposalHash: ActionHash): Promise<Array<Link>> {
     return this.callZome('get_events_for_proposal', proposalHash);
   }
 
   /** All Events */
 
-  markEventAsUpcoming(eventHash: ActionHash): Promise<void> {
-    return this.callZome('mark_event_as_upcoming', eventHash);
+  markEventAsUpcoming(
+    eventHash: ActionHash,
+    cancellationDeletionHash: ActionHash
+  ): Promise<void> {
+    return this.callZome('mark_event_as_upcoming', {
+      event_hash: eventHash,
+      cancellation_deletion_hash: cancellationDeletionHash,
+    });
   }
 
-  markEventAsCancelled(eventHash: ActionHash): Promise<void> {
-    return this.callZome('mark_event_as_cancelled', eventHash);
+  markEventAsCancelled(
+    eventHash: ActionHash,
+    cancellationHash: ActionHash
+  ): Promise<void> {
+    return this.callZome('mark_event_as_cancelled', {
+      event_hash: eventHash,
+      cancellation_hash: cancellationHash,
+    });
   }
 
   markEventAsPast(eventHash: ActionHash): Promise<void> {
@@ -157,26 +464,152 @@ export class GatherClient extends ZomeClient<GatherSignal> {
     return this.callZome('get_all_upcoming_events', null);
   }
 
+  getUpcomingEventsPage(
+    limit: number,
+    cursor?: CollectionCursor,
+    hydrate = false
+  ): Promise<CollectionPage> {
+    return this.callZome('get_upcoming_events_page', {
+      limit,
+      cursor,
+      hydrate,
+    });
+  }
+
+  getUpcomingEventsInRange(
+    start: number,
+    end: number,
+    limit: number,
+    cursor?: CollectionCursor,
+    hydrate = false
+  ): Promise<CollectionPage> {
+    return this.callZome('get_upcoming_events_in_range', {
+      start,
+      end,
+      limit,
+      cursor,
+      hydrate,
+    });
+  }
+
+  // The upcoming events within the radius of the coordinates that overlap with the time window
+  getEventsNear(
+    coordinates: Coordinates,
+    radiusKm: number,
+    timeWindow: { start: number; end: number }
+  ): Promise<Array<Link>> {
+    return this.callZome('get_events_near', {
+      latitude: coordinates.latitude,
+      longitude: coordinates.longitude,
+      radius_km: radiusKm,
+      time_window: timeWindow,
+    });
+  }
+
+  getEventsByTag(tag: string): Promise<Array<Link>> {
+    return this.callZome('get_events_by_tag', tag);
+  }
+
+  getProposalsByTag(tag: string): Promise<Array<Link>> {
+    return this.callZome('get_proposals_by_tag', tag);
+  }
+
+  searchEvents(
+    query: string,
+    statusFilter?: EventCollectionStatus
+  ): Promise<Array<Link>> {
+    return this.callZome('search_events', {
+      query,
+      status_filter: statusFilter,
+    });
+  }
+
+  searchProposals(
+    query: string,
+    statusFilter?: ProposalCollectionStatus
+  ): Promise<Array<Link>> {
+    return this.callZome('search_proposals', {
+      query,
+      status_filter: statusFilter,
+    });
+  }
+
+  getCancelledEventsPage(
+    limit: number,
+    cursor?: CollectionCursor,
+    hydrate = false
+  ): Promise<CollectionPage> {
+    return this.callZome('get_cancelled_events_page', {
+      limit,
+      cursor,
+      hydrate,
+    });
+  }
+
   getAllCancelledEvents(): Promise<Array<Link>> {
     return this.callZome('get_all_cancelled_events', null);
   }
 
+  getPastEventsPage(
+    limit: number,
+    cursor?: CollectionCursor,
+    hydrate = false
+  ): Promise<CollectionPage> {
+    return this.callZome('get_past_events_page', { limit, cursor, hydrate });
+  }
+
   getAllPastEvents(): Promise<Array<Link>> {
     return this.callZome('get_all_past_events', null);
   }
 
   /** All Proposals */
 
-  markProposalAsOpen(proposalHash: ActionHash): Promise<void> {
-    return this.callZome('mark_proposal_as_open', proposalHash);
+  markProposalAsOpen(
+    proposalHash: ActionHash,
+    cancellationDeletionHash: ActionHash
+  ): Promise<void> {
+    return this.callZome('mark_proposal_as_open', {
+      proposal_hash: proposalHash,
+      cancellation_deletion_hash: cancellationDeletionHash,
+    });
   }
 
   markProposalAsExpired(proposalHash: ActionHash): Promise<void> {
     return this.callZome('mark_proposal_as_expired', proposalHash);
   }
 
-  markProposalAsCancelled(proposalHash: ActionHash): Promise<void> {
-    return this.callZome('mark_proposal_as_cancelled', proposalHash);
+  markProposalAsCancelled(
+    proposalHash: ActionHash,
+    cancellationHash: ActionHash
+  ): Promise<void> {
+    return this.callZome('mark_proposal_as_cancelled', {
+      proposal_hash: proposalHash,
+      cancellation_hash: cancellationHash,
+    });
+  }
+
+  getOpenProposalsPage(
+    limit: number,
+    cursor?: CollectionCursor,
+    hydrate = false
+  ): Promise<CollectionPage> {
+    return this.callZome('get_open_proposals_page', { limit, cursor, hydrate });
+  }
+
+  getOpenProposalsInRange(
+    start: number,
+    end: number,
+    limit: number,
+    cursor?: CollectionCursor,
+    hydrate = false
+  ): Promise<CollectionPage> {
+    return this.callZome('get_open_proposals_in_range', {
+      start,
+      end,
+      limit,
+      cursor,
+      hydrate,
+    });
   }
 
   async getAllOpenProposals(): Promise<Array<Link>> {
@@ -191,6 +624,30 @@ export class GatherClient extends ZomeClient<GatherSignal> {
     return this.callZome('get_all_expired_proposals', null);
   }
 
+  getCancelledProposalsPage(
+    limit: number,
+    cursor?: CollectionCursor,
+    hydrate = false
+  ): Promise<CollectionPage> {
+    return this.callZome('get_cancelled_proposals_page', {
+      limit,
+      cursor,
+      hydrate,
+    });
+  }
+
+  getExpiredProposalsPage(
+    limit: number,
+    cursor?: CollectionCursor,
+    hydrate = false
+  ): Promise<CollectionPage> {
+    return this.callZome('get_expired_proposals_page', {
+      limit,
+      cursor,
+      hydrate,
+    });
+  }
+
   /** My Events  */
 
   async getMyEvents(): Promise<Array<Link>> {
@@ -220,4 +677,103 @@ export class GatherClient extends ZomeClient<GatherSignal> {
   ): Promise<void> {
     return this.callZome('remove_myself_as_interested', eventOrProposalHash);
   }
+
+  /** RSVPs */
+
+  async rsvp(
+    eventHash: ActionHash,
+    status: RsvpStatus,
+    guests = 0,
+    guestNames: Array<string> = []
+  ): Promise<EntryRecord<Rsvp>> {
+    const record: Record = await this.callZome('rsvp', {
+      event_hash: eventHash,
+      status,
+      guests,
+      guest_names: guestNames,
+    });
+    return new EntryRecord(record);
+  }
+
+  async getRsvp(rsvpHash: ActionHash): Promise<EntryRecord<Rsvp> | undefined> {
+    const record: Record | undefined = await this.callZome(
+      'get_rsvp',
+      rsvpHash
+    );
+    return record ? new EntryRecord(record) : undefined;
+  }
+
+  async getEventRsvps(eventHash: ActionHash): Promise<EventRsvps> {
+    return this.callZome('get_event_rsvps', eventHash);
+  }
+
+  async getAttendanceSummary(
+    eventHash: ActionHash
+  ): Promise<AttendanceSummary> {
+    return this.callZome('get_attendance_summary', eventHash);
+  }
+
+  /** Check-in */
+
+  async createCheckInCode(eventHash: ActionHash): Promise<CheckInCode> {
+    return this.callZome('create_check_in_code', eventHash);
+  }
+
+  async checkIn(
+    eventHash: ActionHash,
+    code: CheckInCode
+  ): Promise<EntryRecord<Attendance>> {
+    const record: Record = await this.callZome('check_in', {
+      event_hash: eventHash,
+      code,
+    });
+    return new EntryRecord(record);
+  }
+
+  async markPresent(
+    eventHash: ActionHash,
+    attendee: AgentPubKey
+  ): Promise<EntryRecord<Attendance>> {
+    const record: Record = await this.callZome('mark_present', {
+      event_hash: eventHash,
+      attendee,
+    });
+    return new EntryRecord(record);
+  }
+
+  async getAttendance(
+    attendanceHash: ActionHash
+  ): Promise<EntryRecord<Attendance> | undefined> {
+    const record: Record | undefined = await this.callZome(
+      'get_attendance',
+      attendanceHash
+    );
+    return record ? new EntryRecord(record) : undefined;
+  }
+
+  async getAttendees(eventHash: ActionHash): Promise<Array<Link>> {
+    return this.callZome('get_attendees', eventHash);
+  }
+
+  /** Co-hosts */
+
+  async acceptHostInvitation(
+    eventOrProposalHash: ActionHash
+  ): Promise<EntryRecord<HostAcceptance>> {
+    const record: Record = await this.callZome(
+      'accept_host_invitation',
+      eventOrProposalHash
+    );
+    return new EntryRecord(record);
+  }
+
+  async getAcceptedHosts(
+    eventOrProposalHash: ActionHash
+  ): Promise<Array<AgentPubKey>> {
+    return this.callZome('get_accepted_hosts', eventOrProposalHash);
+  }
+
+  async getEventsIHost(): Promise<Array<Link>> {
+    return this.callZome('get_events_i_host', null);
+  }
 }
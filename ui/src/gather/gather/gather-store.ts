@@ -196,7 +196,10 @@ export class GatherStore {
               proposal.entry.call_to_action_hash.toString() ===
               callToAction.actionHash.toString()
             ) {
-              if (proposal.entry.time && proposal.entry.location) {
+              if (
+                proposal.entry.time &&
+                proposal.entry.location.type !== 'ToBeAnnounced'
+              ) {
                 await this.client.createEvent({
                   ...(proposal.entry as Event),
                   from_proposal: {
@@ -304,7 +307,10 @@ export class GatherStore {
             const eventStore = this.events.get(cancelledHash);
             const event = await toPromise(eventStore.latestVersion);
             if ('from_proposal' in event.entry) {
-              await this.client.markEventAsCancelled(cancelledHash);
+              await this.client.markEventAsCancelled(
+                cancelledHash,
+                signal.action.hashed.hash
+              );
               await this.notifyOfEventAction(cancelledHash, {
                 type: 'EventCancelled',
                 action_hash: signal.action.hashed.hash,
@@ -318,7 +324,10 @@ export class GatherStore {
             const proposalStore = this.proposals.get(cancelledHash);
             const proposal = await toPromise(proposalStore.latestVersion);
             if ('hosts' in proposal.entry) {
-              await this.client.markProposalAsCancelled(cancelledHash);
+              await this.client.markProposalAsCancelled(
+                cancelledHash,
+                signal.action.hashed.hash
+              );
 
               await this.notifyOfProposalAction(cancelledHash, {
                 type: 'ProposalCancelled',
@@ -376,7 +385,10 @@ export class GatherStore {
             const eventStore = this.events.get(cancelledHash);
             const event = await toPromise(eventStore.latestVersion);
             if ('from_proposal' in event.entry) {
-              await this.client.markEventAsUpcoming(cancelledHash);
+              await this.client.markEventAsUpcoming(
+                cancelledHash,
+                signal.action.hashed.hash
+              );
               await this.notifyOfEventAction(cancelledHash, {
                 type: 'EventUncancelled',
                 action_hash: signal.action.hashed.content.deletes_address,
@@ -390,7 +402,10 @@ export class GatherStore {
             const proposalStore = this.proposals.get(cancelledHash);
             const proposal = await toPromise(proposalStore.latestVersion);
             if ('hosts' in proposal.entry) {
-              await this.client.markProposalAsOpen(cancelledHash);
+              await this.client.markProposalAsOpen(
+                cancelledHash,
+                signal.action.hashed.hash
+              );
               await this.notifyOfProposalAction(cancelledHash, {
                 type: 'ProposalUncancelled',
                 action_hash: signal.action.hashed.content.deletes_address,
@@ -491,6 +506,14 @@ export class GatherStore {
     };
   });
 
+  occurrenceOverrides = new LazyHoloHashMap((overrideHash: ActionHash) =>
+    immutableEntryStore(() => this.client.getOccurrenceOverride(overrideHash))
+  );
+
+  rsvps = new LazyHoloHashMap((rsvpHash: ActionHash) =>
+    immutableEntryStore(() => this.client.getRsvp(rsvpHash))
+  );
+
   proposals = new LazyHoloHashMap((proposalHash: ActionHash) => {
     const latestVersion = latestVersionOfEntryStore(this.client, () =>
       this.client.getLatestProposal(proposalHash)
@@ -1316,6 +1339,32 @@ export class GatherStore {
                   event,
                 })
               );
+            case 'OccurrenceChanged':
+              return pipe(
+                this.occurrenceOverrides.get(actionHash),
+                occurrenceOverride => ({
+                  type: 'OccurrenceChanged',
+                  record: occurrenceOverride,
+                })
+              );
+            case 'RsvpConfirmed':
+              return pipe(this.rsvps.get(actionHash), rsvp => ({
+                type: 'RsvpConfirmed',
+                record: rsvp,
+              }));
+            case 'EventHostInvitation':
+              return pipe(this.events.get(actionHash).latestVersion, event => ({
+                type: 'EventHostInvitation',
+                record: event,
+              }));
+            case 'ProposalHostInvitation':
+              return pipe(
+                this.proposals.get(actionHash).latestVersion,
+                proposal => ({
+                  type: 'ProposalHostInvitation',
+                  record: proposal,
+                })
+              );
             case 'CommitmentCreated':
               return pipe(
                 this.assembleStore.commitments.get(actionHash).entry,
@@ -1,24 +1,159 @@
 import { CallToAction } from '@darksoil/assemble';
 import { EntryRecord, ActionCommittedSignal } from '@holochain-open-dev/utils';
-import { ActionHash, AgentPubKey, EntryHash } from '@holochain/client';
+import {
+  ActionHash,
+  AgentPubKey,
+  EntryHash,
+  Link,
+  Record,
+  SignedActionHashed,
+} from '@holochain/client';
 
 export interface IndexedHash {
   type: 'event' | 'proposal';
   hash: ActionHash;
 }
 
+// Serialization of Rust's std::time::Duration
+export interface Duration {
+  secs: number;
+  nanos: number;
+}
+
 export type EventTime =
   | {
       type: 'Periodic';
       start_time: number;
-      event_duration: number;
-      period_duration: number;
+      event_duration: Duration;
+      period_duration: Duration;
       ocurrences: number | undefined;
     }
   | {
       type: 'Unique';
       start_time: number;
       end_time: number;
+    }
+  | {
+      type: 'Recurring';
+      start_time: number;
+      event_duration: Duration;
+      recurrence: Recurrence;
+    };
+
+export type Frequency = 'Daily' | 'Weekly' | 'Monthly' | 'Yearly';
+
+export type Weekday =
+  | 'Monday'
+  | 'Tuesday'
+  | 'Wednesday'
+  | 'Thursday'
+  | 'Friday'
+  | 'Saturday'
+  | 'Sunday';
+
+export interface NthWeekday {
+  nth: number; // Negative counts from the end of the month
+  weekday: Weekday;
+}
+
+export interface Recurrence {
+  frequency: Frequency;
+  interval: number;
+  by_weekday: Array<Weekday>;
+  by_nth_weekday: Array<NthWeekday>;
+  by_month_day: Array<number>;
+  until: number | undefined;
+  count: number | undefined;
+  exclusions: Array<number>;
+}
+
+export interface Coordinates {
+  latitude: number;
+  longitude: number;
+}
+
+export type Location =
+  | {
+      type: 'Physical';
+      address: string;
+      coordinates: Coordinates | undefined;
+    }
+  | {
+      type: 'Online';
+      url: string;
+    }
+  | {
+      type: 'Hybrid';
+      address: string;
+      coordinates: Coordinates | undefined;
+      url: string;
+    }
+  | {
+      type: 'ToBeAnnounced';
+    };
+
+// An occurrence of an event with its overrides applied
+export interface EventOccurrence {
+  index: number;
+  start_time: number;
+  end_time: number;
+  location: Location;
+  cancelled: boolean;
+}
+
+export type OccurrenceChange =
+  | {
+      type: 'Cancelled';
+    }
+  | {
+      type: 'Rescheduled';
+      start_time: number;
+      end_time: number;
+    }
+  | {
+      type: 'Relocated';
+      location: Location;
+    };
+
+export interface OccurrenceOverride {
+  event_hash: ActionHash;
+  revision_hash: ActionHash;
+  occurrence_index: number;
+  change: OccurrenceChange;
+}
+
+// An amount of money in the minor unit of its currency, like cents for EUR
+export interface Price {
+  amount: number;
+  currency: string; // ISO 4217 code, like "EUR"
+}
+
+export interface CostTier {
+  name: string;
+  price: Price;
+}
+
+export type Cost =
+  | {
+      type: 'Free';
+    }
+  | {
+      type: 'Fixed';
+      price: Price;
+    }
+  | {
+      type: 'PayWhatYouCan';
+      currency: string;
+      suggested_min: number | undefined;
+      suggested_max: number | undefined;
+    }
+  | {
+      type: 'Tiered';
+      tiers: Array<CostTier>;
+    }
+  | {
+      type: 'Other';
+      description: string;
     };
 
 export interface FromProposal {
@@ -31,9 +166,12 @@ export interface Event {
   title: string;
   description: string;
   image: EntryHash;
-  location: string;
+  location: Location;
   time: EventTime;
-  cost: string | undefined;
+  cost: Cost | undefined;
+  tags: Array<string>;
+  // Maximum number of confirmed attendees, the rest are waitlisted
+  capacity: number | undefined;
   call_to_action_hash: ActionHash;
   from_proposal: FromProposal | undefined;
 }
@@ -43,12 +181,117 @@ export interface Proposal {
   title: string;
   description: string;
   image: EntryHash;
-  location: string | undefined;
+  location: Location;
   time: EventTime | undefined;
-  cost: string | undefined;
+  cost: Cost | undefined;
+  tags: Array<string>;
   call_to_action_hash: ActionHash;
 }
 
+export interface XSalsa20Poly1305EncryptedData {
+  nonce: Uint8Array;
+  encrypted_data: Uint8Array;
+}
+
+export interface EncryptedKey {
+  agent: AgentPubKey;
+  sender: Uint8Array;
+  recipient: Uint8Array;
+  encrypted_key: XSalsa20Poly1305EncryptedData;
+}
+
+// An invite-only event, whose Event can only be decrypted by its hosts and invitees
+export interface PrivateEvent {
+  hosts: Array<AgentPubKey>;
+  invitees: Array<AgentPubKey>;
+  encrypted_event: XSalsa20Poly1305EncryptedData;
+  keys: Array<EncryptedKey>;
+}
+
+export type RsvpStatus = 'Going' | 'Maybe' | 'NotGoing';
+
+export interface Rsvp {
+  event_hash: ActionHash;
+  status: RsvpStatus;
+  guests: number;
+  guest_names: Array<string>;
+}
+
+// The latest RSVP link of each agent to an event, grouped by status
+export interface EventRsvps {
+  going: Array<Link>;
+  waitlist: Array<Link>;
+  maybe: Array<Link>;
+  not_going: Array<Link>;
+}
+
+// The headcounts of an event, which count the guests of each agent
+export interface AttendanceSummary {
+  capacity: number | undefined;
+  going: number;
+  waitlist: number;
+  maybe: number;
+  not_going: number;
+  interested: number;
+}
+
+// Short-lived code shown by a host, for attendees to check themselves in
+export interface CheckInCode {
+  host: AgentPubKey;
+  revision_hash: ActionHash;
+  issued_at: number;
+  expires_at: number;
+  signature: Uint8Array;
+}
+
+export type CheckIn =
+  | {
+      type: 'ByHost';
+      revision_hash: ActionHash;
+    }
+  | {
+      type: 'WithCode';
+      code: CheckInCode;
+    };
+
+export interface Attendance {
+  event_hash: ActionHash;
+  attendee: AgentPubKey;
+  check_in: CheckIn;
+}
+
+// The consent of an agent to host an event or proposal listing them as a host
+export interface HostAcceptance {
+  event_or_proposal_hash: ActionHash;
+  host: AgentPubKey;
+}
+
+// A field of an event or proposal that changed from one revision to the next
+export type FieldChange =
+  | { field: 'Title'; from: string; to: string }
+  | { field: 'Description'; from: string; to: string }
+  | { field: 'Image'; from: EntryHash; to: EntryHash }
+  | { field: 'Location'; from: Location; to: Location }
+  | { field: 'Time'; from: EventTime | undefined; to: EventTime | undefined }
+  | { field: 'Cost'; from: Cost | undefined; to: Cost | undefined }
+  | { field: 'Tags'; from: Array<string>; to: Array<string> }
+  | { field: 'Hosts'; from: Array<AgentPubKey>; to: Array<AgentPubKey> }
+  | { field: 'Capacity'; from: number | undefined; to: number | undefined };
+
+export interface RevisionChanges {
+  revision_hash: ActionHash;
+  previous_revision_hash: ActionHash;
+  author: AgentPubKey;
+  timestamp: number;
+  changes: Array<FieldChange>;
+}
+
+// The latest revision of an event or proposal, or the deletion that removed it,
+// along with the revisions made concurrently that a host still needs to merge
+export type LatestRevision =
+  | { type: 'Live'; record: Record; conflicting_heads: Array<ActionHash> }
+  | { type: 'Deleted'; deletion: SignedActionHashed };
+
 export type EventStatus = 'upcoming_event' | 'past_event' | 'cancelled_event';
 
 export type ProposalStatus =
@@ -58,6 +301,10 @@ export type ProposalStatus =
   | { type: 'fulfilled_proposal'; assemblyHash: ActionHash }
   | { type: 'actual_event'; eventHash: ActionHash };
 
+// The statuses of the global collections in the zome, used to filter searches
+export type EventCollectionStatus = 'Upcoming' | 'Past' | 'Cancelled';
+export type ProposalCollectionStatus = 'Open' | 'Expired' | 'Cancelled';
+
 export interface ProposalWithStatus {
   originalActionHash: ActionHash;
   currentProposal: EntryRecord<Proposal>;
@@ -71,6 +318,30 @@ export interface EventWithStatus {
   status: EventStatus;
 }
 
+export interface CollectionCursor {
+  start_time: number;
+  create_link_hash: ActionHash;
+}
+
+export type CollectionStatus =
+  | { type: 'Event'; status: EventCollectionStatus }
+  | { type: 'Proposal'; status: ProposalCollectionStatus };
+
+// The latest state of the event or proposal that a link of a page targets
+export interface HydratedLink {
+  original_hash: ActionHash;
+  latest: LatestRevision | undefined;
+  interested_count: number;
+  status: CollectionStatus;
+}
+
+export interface CollectionPage {
+  links: Array<Link>;
+  next_cursor: CollectionCursor | undefined;
+  // Only present when the page was requested hydrated
+  hydrated: Array<HydratedLink> | undefined;
+}
+
 export type GatherSignal = ActionCommittedSignal<EntryTypes, any>;
 
 export type EntryTypes =
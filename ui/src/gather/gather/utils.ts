@@ -21,7 +21,15 @@ import {
 } from '@holochain/client';
 import { Event as EventCalendarEvent } from '@scoped-elements/event-calendar/dist/types.js';
 import { cloneDeepWith } from 'lodash-es';
-import { Event, Proposal } from './types.js';
+import {
+  Cost,
+  Duration,
+  Event,
+  EventTime,
+  Location,
+  Price,
+  Proposal,
+} from './types.js';
 
 export function isExpired(callToAction: CallToAction) {
   return (
@@ -30,8 +38,183 @@ export function isExpired(callToAction: CallToAction) {
   );
 }
 
+export function durationInMicros(duration: Duration): number {
+  return duration.secs * 1_000_000 + Math.floor(duration.nanos / 1000);
+}
+
+// Mirrors EventTime::end_time() in the integrity zome, which validates marking an event as past
+// Recurring events only get an upper bound here, their exact end needs expanding the recurrence
+export function endTime(time: EventTime): number | undefined {
+  if (time.type === 'Unique') return time.end_time;
+  const eventDuration = durationInMicros(time.event_duration);
+  if (time.type === 'Recurring') {
+    if (time.recurrence.until === undefined) return undefined;
+    return time.recurrence.until + eventDuration;
+  }
+  if (time.ocurrences === undefined) return undefined;
+  return (
+    time.start_time +
+    Math.max(time.ocurrences - 1, 0) * durationInMicros(time.period_duration) +
+    eventDuration
+  );
+}
+
+// The text that describes the location, undefined if it's still To Be Announced
+export function locationToString(location: Location): string | undefined {
+  switch (location.type) {
+    case 'Physical':
+      return location.address;
+    case 'Online':
+      return location.url;
+    case 'Hybrid':
+      return `${location.address} (${location.url})`;
+    default:
+      return undefined;
+  }
+}
+
+// Builds the location typed in a text field, which is online if it's a link
+export function locationFromString(text: string | undefined): Location {
+  const trimmed = text?.trim();
+  if (!trimmed) return { type: 'ToBeAnnounced' };
+  if (/^https?:\/\//.test(trimmed)) return { type: 'Online', url: trimmed };
+  return { type: 'Physical', address: trimmed, coordinates: undefined };
+}
+
+// Keeps the previous location, with its coordinates, unless its text was edited
+export function editedLocation(
+  previous: Location,
+  text: string | undefined
+): Location {
+  if (text?.trim() === locationToString(previous)) return previous;
+  return locationFromString(text);
+}
+
+const ZERO_DECIMAL_CURRENCIES =
+  'BIF CLP DJF GNF ISK JPY KMF KRW PYG RWF UGX UYI VND VUV XAF XOF XPF'.split(' ');
+const THREE_DECIMAL_CURRENCIES = 'BHD IQD JOD KWD LYD OMR TND'.split(' ');
+
+// Mirrors currency_decimals() in the integrity zome
+export function currencyDecimals(currency: string): number {
+  if (ZERO_DECIMAL_CURRENCIES.includes(currency)) return 0;
+  if (THREE_DECIMAL_CURRENCIES.includes(currency)) return 3;
+  return 2;
+}
+
+export function priceToString(price: Price): string {
+  const decimals = currencyDecimals(price.currency);
+  return `${(price.amount / 10 ** decimals).toFixed(decimals)} ${
+    price.currency
+  }`;
+}
+
+// Mirrors the Display implementation of Cost in the integrity zome
+export function costToString(cost: Cost): string {
+  switch (cost.type) {
+    case 'Free':
+      return 'Free';
+    case 'Fixed':
+      return priceToString(cost.price);
+    case 'PayWhatYouCan': {
+      const price = (amount: number) =>
+        priceToString({ amount, currency: cost.currency });
+      const min = cost.suggested_min;
+      const max = cost.suggested_max;
+      if (min !== undefined && max !== undefined)
+        return `Pay what you can, from ${price(min)} to ${price(max)}`;
+      if (min !== undefined) return `Pay what you can, from ${price(min)}`;
+      if (max !== undefined) return `Pay what you can, up to ${price(max)}`;
+      return `Pay what you can, in ${cost.currency}`;
+    }
+    case 'Tiered':
+      return cost.tiers
+        .map(tier => `${tier.name}: ${priceToString(tier.price)}`)
+        .join(', ');
+    default:
+      return cost.description;
+  }
+}
+
+function parseAmount(text: string, decimals: number): number | undefined {
+  const [whole, fraction = '', ...rest] = text.replace(',', '.').split('.');
+  if (rest.length > 0 || !/^\d+$/.test(whole) || !/^\d*$/.test(fraction))
+    return undefined;
+  if (fraction.length > decimals) return undefined;
+  return (
+    parseInt(whole, 10) * 10 ** decimals +
+    (fraction ? parseInt(fraction.padEnd(decimals, '0'), 10) : 0)
+  );
+}
+
+function parsePrice(text: string): Price | undefined {
+  const symbols: Array<[string, string]> = [
+    ['€', 'EUR'],
+    ['$', 'USD'],
+    ['£', 'GBP'],
+  ];
+  for (const [symbol, currency] of symbols) {
+    if (text.startsWith(symbol) || text.endsWith(symbol)) {
+      const amount = parseAmount(
+        text.replace(symbol, '').trim(),
+        currencyDecimals(currency)
+      );
+      return amount === undefined ? undefined : { amount, currency };
+    }
+  }
+
+  const parts = text.split(/\s+/);
+  if (parts.length !== 2) return undefined;
+  const isCurrency = (s: string) => /^[A-Z]{3}$/.test(s);
+  const [amountText, currency] = isCurrency(parts[0].toUpperCase())
+    ? [parts[1], parts[0].toUpperCase()]
+    : [parts[0], parts[1].toUpperCase()];
+  if (!isCurrency(currency)) return undefined;
+  const amount = parseAmount(amountText, currencyDecimals(currency));
+  return amount === undefined ? undefined : { amount, currency };
+}
+
+// Mirrors parse_legacy_cost() in the integrity zome, undefined if the text is empty
+export function costFromString(text: string | undefined): Cost | undefined {
+  const trimmed = text?.trim();
+  if (!trimmed) return undefined;
+  if (['free', 'gratis', '0'].includes(trimmed.toLowerCase()))
+    return { type: 'Free' };
+  const price = parsePrice(trimmed);
+  if (!price) return { type: 'Other', description: trimmed };
+  if (price.amount === 0) return { type: 'Free' };
+  return { type: 'Fixed', price };
+}
+
+// Keeps the previous cost, with its tiers or ranges, unless its text was edited
+export function editedCost(
+  previous: Cost | undefined,
+  text: string | undefined
+): Cost | undefined {
+  if (previous && text?.trim() === costToString(previous)) return previous;
+  return costFromString(text);
+}
+
+// Mirrors MAX_TAGS in the integrity zome
+export const MAX_TAGS = 10;
+
+// Mirrors normalize_tag() in the integrity zome
+export function normalizeTag(tag: string): string {
+  return tag
+    .toLowerCase()
+    .split(/[^\p{L}\p{N}]/u)
+    .filter(word => word.length > 0)
+    .join('-');
+}
+
+// Parses comma-separated tags, dropping the empty and repeated ones
+export function tagsFromString(text: string | undefined): Array<string> {
+  const tags = (text || '').split(',').map(normalizeTag);
+  return [...new Set(tags.filter(tag => tag.length > 0))];
+}
+
 export function isPast(event: Event) {
-  return event.time.start_time < Date.now() * 1000;
+  const end = endTime(event.time);
+  return end !== undefined && end <= Date.now() * 1000;
 }
 
 export function eventToEventCalendar(